@@ -0,0 +1,99 @@
+use amethyst::{
+    core::{bundle::SystemBundle, SystemExt},
+    ecs::prelude::{DispatcherBuilder, World},
+    renderer::{
+        plugins::{RenderFlat2D, RenderToWindow},
+        types::DefaultBackend,
+        RenderingBundle,
+    },
+    ui::{RenderUi, UiBundle},
+    Error,
+};
+use std::path::PathBuf;
+
+use crate::input::GameBindings;
+use crate::states::SimulationState;
+use crate::systems;
+
+//Game logic only, runs the same with or without a window.
+//Everything here reads PlayerInput rather than the InputHandler, and runs after
+//the input has been replayed and recorded.
+pub struct SimulationBundle {
+    //Systems that sample devices into PlayerInput
+    input: &'static [&'static str],
+}
+
+impl SimulationBundle {
+    //Headless, PlayerInput is written before each tick
+    pub fn headless() -> SimulationBundle {
+        SimulationBundle { input: &[] }
+    }
+
+    //Runs after the systems that sample the devices
+    pub fn after(input: &'static [&'static str]) -> SimulationBundle {
+        SimulationBundle { input }
+    }
+}
+
+impl<'a, 'b> SystemBundle<'a, 'b> for SimulationBundle {
+    fn build(self, _world: &mut World, builder: &mut DispatcherBuilder<'a, 'b>) -> Result<(), Error> {
+        builder.add(systems::InputReplaySystem.pausable(SimulationState::Running), "input_replay", self.input);
+        builder.add(systems::InputRecordSystem.pausable(SimulationState::Running), "input_record", &["input_replay"]);
+        builder.add(systems::TargetingSystem.pausable(SimulationState::Running), "targeting", &["input_record"]);
+        builder.add(systems::CapacitorSystem.pausable(SimulationState::Running), "capacitor", &["input_record"]);
+        builder.add(systems::PowerSystem.pausable(SimulationState::Running), "power", &["capacitor"]);
+        // Thrust is only applied once the power grid has been given out
+        builder.add(systems::PlayerControlSystem.pausable(SimulationState::Running), "player_control", &["power"]);
+        builder.add(systems::NpcSystem.pausable(SimulationState::Running), "npc", &["player_control"]);
+        builder.add(systems::MiningSystem.pausable(SimulationState::Running), "mining", &["targeting", "power", "npc"]);
+        builder.add(systems::FragmentationSystem.pausable(SimulationState::Running), "fragmentation", &["mining"]);
+        builder.add(systems::DriftSystem.pausable(SimulationState::Running), "drift", &["fragmentation"]);
+        builder.add(systems::CollisionSystem.pausable(SimulationState::Running), "collision", &["player_control", "drift"]);
+        builder.add(systems::PickupSystem.pausable(SimulationState::Running), "pickup", &["collision"]);
+        builder.add(systems::ScannerSystem.pausable(SimulationState::Running), "scanner", &["power", "targeting", "fragmentation"]);
+        builder.add(systems::RefinerySystem.pausable(SimulationState::Running), "refinery", &["pickup"]);
+        builder.add(systems::FuelSystem.pausable(SimulationState::Running), "fuel", &["player_control", "refinery"]);
+        builder.add(systems::JumpSystem.pausable(SimulationState::Running), "jump", &["fuel"]);
+        builder.add(systems::DockingSystem.pausable(SimulationState::Running), "docking", &["collision"]);
+        builder.add(systems::CombatSystem.pausable(SimulationState::Running), "combat", &["npc", "power", "targeting"]);
+        builder.add(systems::MarketSystem.pausable(SimulationState::Running), "market", &["npc"]);
+        builder.add(systems::ContractSystem::default().pausable(SimulationState::Running), "contracts", &["mining", "combat", "docking"]);
+        builder.add(systems::EventDirectorSystem.pausable(SimulationState::Running), "director", &["player_control"]);
+        builder.add(systems::RegenSystem.pausable(SimulationState::Running), "regen", &["fragmentation"]);
+        Ok(())
+    }
+}
+
+//Rendering, ui and everything that only matters with a window
+pub struct PresentationBundle {
+    display_config_path: PathBuf,
+}
+
+impl PresentationBundle {
+    pub fn new(display_config_path: PathBuf) -> PresentationBundle {
+        PresentationBundle { display_config_path }
+    }
+}
+
+impl<'a, 'b> SystemBundle<'a, 'b> for PresentationBundle {
+    fn build(self, world: &mut World, builder: &mut DispatcherBuilder<'a, 'b>) -> Result<(), Error> {
+        builder.add(systems::BackgroundMovementSystem.pausable(SimulationState::Running), "background_movement", &["player_control"]);
+        builder.add(systems::ZoomSystem, "camera_zoom_system", &["input_system"]);
+        builder.add(systems::RadarSystem::default(), "radar", &["player_control"]);
+        builder.add(systems::HudSystem, "hud", &["mining", "targeting", "scanner", "refinery", "fuel"]);
+        builder.add(systems::ScanDisplaySystem, "scan_display", &["scanner"]);
+
+        UiBundle::<GameBindings>::new().build(world, builder)?;
+        RenderingBundle::<DefaultBackend>::new()
+            // The RenderToWindow plugin provides all the scaffolding for opening a window and drawing on it
+            .with_plugin(
+                RenderToWindow::from_config_path(self.display_config_path)?
+                    .with_clear([0.0, 0.0, 0.0, 1.0]),
+            )
+            // RenderFlat2D plugin is used to render entities with a `SpriteRender` component.
+            .with_plugin(RenderFlat2D::default())
+            .with_plugin(RenderUi::default())
+            .build(world, builder)?;
+        Ok(())
+    }
+}
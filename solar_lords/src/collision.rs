@@ -0,0 +1,166 @@
+use amethyst::core::transform::Transform;
+
+//Collision shape in the entity's local space, before its Transform is applied
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Circle(f32),
+    //Convex, points listed counter clockwise
+    Polygon(Vec<(f32, f32)>),
+}
+
+impl Shape {
+    //Box covering a sprite
+    pub fn sprite_box(width: f32, height: f32) -> Shape {
+        let (x, y) = (width * 0.5, height * 0.5);
+        Shape::Polygon(vec![(-x, -y), (x, -y), (x, y), (-x, y)])
+    }
+
+    //Circle inside a sprite, for round things like astroids
+    pub fn sprite_circle(width: f32, height: f32) -> Shape {
+        Shape::Circle(width.min(height) * 0.5)
+    }
+
+    //Places the shape in the world using the Transform's position, z rotation and x scale
+    pub fn placed(&self, transform: &Transform) -> WorldShape {
+        let position = transform.translation();
+        let scale = transform.scale().x;
+        match self {
+            Shape::Circle(radius) => WorldShape::Circle((position.x, position.y), radius * scale),
+            Shape::Polygon(points) => {
+                let angle = transform.rotation().euler_angles().2;
+                let (sin, cos) = angle.sin_cos();
+                WorldShape::Polygon(
+                    points
+                        .iter()
+                        .map(|(x, y)| {
+                            let (x, y) = (x * scale, y * scale);
+                            (position.x + x * cos - y * sin, position.y + x * sin + y * cos)
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+//A shape in world coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorldShape {
+    Circle((f32, f32), f32),
+    Polygon(Vec<(f32, f32)>),
+}
+
+//How two overlapping shapes touch. The normal points from the first shape to the second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    pub normal: (f32, f32),
+    pub depth: f32,
+}
+
+impl WorldShape {
+    pub fn center(&self) -> (f32, f32) {
+        match self {
+            WorldShape::Circle(center, _) => *center,
+            WorldShape::Polygon(points) => {
+                let count = points.len().max(1) as f32;
+                let (x, y) = points.iter().fold((0.0, 0.0), |sum, point| (sum.0 + point.0, sum.1 + point.1));
+                (x / count, y / count)
+            }
+        }
+    }
+
+    //Radius of a circle around the center holding the whole shape, used by the broadphase
+    pub fn bounding_radius(&self) -> f32 {
+        match self {
+            WorldShape::Circle(_, radius) => *radius,
+            WorldShape::Polygon(points) => {
+                let center = self.center();
+                points
+                    .iter()
+                    .map(|point| length((point.0 - center.0, point.1 - center.1)))
+                    .fold(0.0, f32::max)
+            }
+        }
+    }
+
+    //Lowest and highest point of the shape along an axis
+    fn project(&self, axis: (f32, f32)) -> (f32, f32) {
+        match self {
+            WorldShape::Circle(center, radius) => {
+                let middle = dot(*center, axis);
+                (middle - radius, middle + radius)
+            }
+            WorldShape::Polygon(points) => points.iter().fold((std::f32::MAX, std::f32::MIN), |(low, high), point| {
+                let projected = dot(*point, axis);
+                (low.min(projected), high.max(projected))
+            }),
+        }
+    }
+
+    //Separating axes worth testing against the other shape
+    fn axes(&self, other: &WorldShape) -> Vec<(f32, f32)> {
+        match self {
+            WorldShape::Polygon(points) => (0..points.len())
+                .map(|i| {
+                    let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                    (b.1 - a.1, a.0 - b.0)
+                })
+                .collect(),
+            // A circle only adds the axis towards the nearest point of the other shape
+            WorldShape::Circle(center, _) => {
+                let nearest = match other {
+                    WorldShape::Circle(other_center, _) => *other_center,
+                    WorldShape::Polygon(points) => *points
+                        .iter()
+                        .min_by(|a, b| {
+                            let a = length((a.0 - center.0, a.1 - center.1));
+                            let b = length((b.0 - center.0, b.1 - center.1));
+                            a.partial_cmp(&b).unwrap()
+                        })
+                        .unwrap_or(center),
+                };
+                vec![(nearest.0 - center.0, nearest.1 - center.1)]
+            }
+        }
+    }
+
+    //Separating axis test, None if the shapes don't overlap
+    pub fn contact(&self, other: &WorldShape) -> Option<Contact> {
+        let mut best: Option<Contact> = None;
+        for axis in self.axes(other).into_iter().chain(other.axes(self)) {
+            let axis_length = length(axis);
+            if axis_length <= std::f32::EPSILON {
+                continue;
+            }
+            let axis = (axis.0 / axis_length, axis.1 / axis_length);
+            let (low, high) = self.project(axis);
+            let (other_low, other_high) = other.project(axis);
+            let depth = high.min(other_high) - low.max(other_low);
+            if depth <= 0.0 {
+                return None;
+            }
+            if best.map_or(true, |best| depth < best.depth) {
+                best = Some(Contact { normal: axis, depth });
+            }
+        }
+
+        // Shapes sitting exactly on top of each other have no useful axis
+        let mut contact = best.unwrap_or(Contact {
+            normal: (1.0, 0.0),
+            depth: self.bounding_radius() + other.bounding_radius(),
+        });
+        let (from, to) = (self.center(), other.center());
+        if dot((to.0 - from.0, to.1 - from.1), contact.normal) < 0.0 {
+            contact.normal = (-contact.normal.0, -contact.normal.1);
+        }
+        Some(contact)
+    }
+}
+
+pub fn dot(a: (f32, f32), b: (f32, f32)) -> f32 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+pub fn length(a: (f32, f32)) -> f32 {
+    dot(a, a).sqrt()
+}
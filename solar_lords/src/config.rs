@@ -0,0 +1,885 @@
+use serde::{Deserialize, Serialize};
+
+use crate::solar_lords::{AstroidSize, Faction, Good, MiningTool, NpcRole, Ore, SectorEventKind, Weapon};
+
+//Background Config
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BackgroundConfig {
+    pub layers: Vec<LayerConfig>,
+    #[serde(default)]
+    pub starfields: Vec<StarfieldConfig>,
+}
+
+//A single tiled parallax layer
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LayerConfig {
+    pub sprite: usize,
+    pub depth: f32,
+    pub scale: f32,
+    pub z: f32,
+    pub tile_width: f32,
+    pub tile_height: f32,
+}
+
+//Randomly scattered star particles
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StarfieldConfig {
+    pub count: usize,
+    pub depth: f32,
+    pub z: f32,
+    pub field_width: f32,
+    pub field_height: f32,
+}
+
+//Radar Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RadarConfig {
+    pub range: f32,
+    pub map_range: f32,
+    pub size: f32,
+    pub blip_size: f32,
+    pub cell_size: f32,
+    pub reveal_radius: f32,
+}
+
+impl Default for RadarConfig {
+    fn default() -> Self {
+        RadarConfig {
+            range: 1500.0,
+            map_range: 3000.0,
+            size: 180.0,
+            blip_size: 5.0,
+            cell_size: 500.0,
+            reveal_radius: 600.0,
+        }
+    }
+}
+
+//Astroid Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AstroidConfig {
+    pub large: FragmentConfig,
+    pub medium: FragmentConfig,
+    pub small: FragmentConfig,
+    pub scatter_speed: f32,
+    pub drag: f32,
+}
+
+//How one astroid size breaks apart
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FragmentConfig {
+    pub scale: f32,
+    pub split_at: u32,
+    pub pieces: u32,
+}
+
+impl AstroidConfig {
+    pub fn size(&self, size: AstroidSize) -> &FragmentConfig {
+        match size {
+            AstroidSize::Large => &self.large,
+            AstroidSize::Medium => &self.medium,
+            AstroidSize::Small => &self.small,
+        }
+    }
+}
+
+impl Default for AstroidConfig {
+    fn default() -> Self {
+        AstroidConfig {
+            large: FragmentConfig {
+                scale: 1.0,
+                split_at: 200,
+                pieces: 2,
+            },
+            medium: FragmentConfig {
+                scale: 0.6,
+                split_at: 40,
+                pieces: 2,
+            },
+            small: FragmentConfig {
+                scale: 0.35,
+                split_at: 10,
+                pieces: 2,
+            },
+            scatter_speed: 40.0,
+            drag: 0.5,
+        }
+    }
+}
+
+//Pickup Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PickupConfig {
+    pub chunk_size: u32,
+    pub eject_speed: f32,
+    pub lifetime: f32,
+    pub collect_radius: f32,
+    pub tractor_range: f32,
+    pub tractor_pull: f32,
+}
+
+impl Default for PickupConfig {
+    fn default() -> Self {
+        PickupConfig {
+            chunk_size: 10,
+            eject_speed: 30.0,
+            lifetime: 60.0,
+            collect_radius: 40.0,
+            tractor_range: 300.0,
+            tractor_pull: 200.0,
+        }
+    }
+}
+
+//Scanner Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScannerConfig {
+    pub pulse_speed: f32,
+    pub pulse_range: f32,
+    pub cooldown: f32,
+    pub energy_cost: f32,
+    pub detail_range: f32,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        ScannerConfig {
+            pulse_speed: 600.0,
+            pulse_range: 1200.0,
+            cooldown: 5.0,
+            energy_cost: 30.0,
+            detail_range: 400.0,
+        }
+    }
+}
+
+//Power Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PowerConfig {
+    pub heat_dissipation: f32,
+    pub overheat_recover: f32,
+    pub shield_regen: f32,
+    pub shield_regen_draw: f32,
+    pub thrust_draw: f32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        PowerConfig {
+            heat_dissipation: 5.0,
+            overheat_recover: 0.25,
+            shield_regen: 2.0,
+            shield_regen_draw: 4.0,
+            thrust_draw: 3.0,
+        }
+    }
+}
+
+//Collision Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CollisionConfig {
+    pub cell_size: f32,
+    pub restitution: f32,
+    pub damage_threshold: f32,
+    pub damage_per_speed: f32,
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        CollisionConfig {
+            cell_size: 200.0,
+            restitution: 1.0,
+            damage_threshold: 40.0,
+            damage_per_speed: 0.2,
+        }
+    }
+}
+
+//Fuel Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FuelConfig {
+    pub thrust_burn: f32,
+    pub jump_cost: f32,
+    pub per_refined_unit: f32,
+    pub station_price: f32,
+    pub dock_range: f32,
+    pub distress_delay: f32,
+    pub distress_fuel: f32,
+    pub distress_fee: f32,
+}
+
+impl Default for FuelConfig {
+    fn default() -> Self {
+        FuelConfig {
+            thrust_burn: 0.5,
+            jump_cost: 40.0,
+            per_refined_unit: 4.0,
+            station_price: 2.0,
+            dock_range: 200.0,
+            distress_delay: 30.0,
+            distress_fuel: 25.0,
+            distress_fee: 100.0,
+        }
+    }
+}
+
+//Shipyard Config, every hull a player can fly
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ShipyardConfig {
+    pub starting_ship: String,
+    pub ships: Vec<ShipClass>,
+}
+
+//Base stats of a hull before any modules are fitted
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ShipClass {
+    pub name: String,
+    pub price: f32,
+    pub hull: u32,
+    pub shield: u32,
+    pub cargo: u32,
+    pub speed: f32,
+    pub capacitor: f32,
+    pub recharge: f32,
+    pub fuel: f32,
+    pub mass: f32,
+    pub sprite: usize,
+    pub scale: f32,
+    pub slots: ModuleSlots,
+    //Fitting limits, modules can't be bigger than module_size
+    //and together can't use more power grid or CPU than this
+    pub module_size: u32,
+    pub power: f32,
+    pub cpu: f32,
+}
+
+//How many modules of each kind a hull can carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModuleSlots {
+    pub mining: u32,
+    pub weapon: u32,
+    pub utility: u32,
+    pub defense: u32,
+}
+
+impl ShipyardConfig {
+    pub fn class(&self, name: &str) -> Option<&ShipClass> {
+        self.ships.iter().find(|ship| ship.name == name)
+    }
+
+    pub fn starting_class(&self) -> ShipClass {
+        self.class(&self.starting_ship).cloned().unwrap_or_default()
+    }
+}
+
+impl Default for ShipClass {
+    fn default() -> Self {
+        ShipClass {
+            name: "Miner".to_string(),
+            price: 2000.0,
+            hull: 100,
+            shield: 100,
+            cargo: 500,
+            speed: 1.0,
+            capacitor: 100.0,
+            recharge: 10.0,
+            fuel: 100.0,
+            mass: 10.0,
+            sprite: 0,
+            scale: 1.0,
+            slots: ModuleSlots {
+                mining: 2,
+                weapon: 1,
+                utility: 1,
+                defense: 1,
+            },
+            module_size: 3,
+            power: 50.0,
+            cpu: 50.0,
+        }
+    }
+}
+
+impl Default for ShipyardConfig {
+    fn default() -> Self {
+        let miner = ShipClass::default();
+        ShipyardConfig {
+            starting_ship: miner.name.clone(),
+            ships: vec![
+                miner.clone(),
+                ShipClass {
+                    name: "Hauler".to_string(),
+                    price: 4000.0,
+                    hull: 150,
+                    shield: 60,
+                    cargo: 1500,
+                    speed: 0.7,
+                    capacitor: 80.0,
+                    recharge: 8.0,
+                    fuel: 150.0,
+                    mass: 25.0,
+                    sprite: 7,
+                    scale: 1.3,
+                    slots: ModuleSlots {
+                        mining: 1,
+                        weapon: 0,
+                        utility: 2,
+                        defense: 1,
+                    },
+                    module_size: 2,
+                    power: 30.0,
+                    cpu: 30.0,
+                    ..miner.clone()
+                },
+                ShipClass {
+                    name: "Corvette".to_string(),
+                    price: 6000.0,
+                    hull: 180,
+                    shield: 150,
+                    cargo: 200,
+                    speed: 1.3,
+                    capacitor: 150.0,
+                    recharge: 15.0,
+                    fuel: 90.0,
+                    mass: 14.0,
+                    sprite: 8,
+                    slots: ModuleSlots {
+                        mining: 1,
+                        weapon: 3,
+                        utility: 1,
+                        defense: 2,
+                    },
+                    module_size: 3,
+                    power: 80.0,
+                    cpu: 70.0,
+                    ..miner.clone()
+                },
+                ShipClass {
+                    name: "Explorer".to_string(),
+                    price: 3000.0,
+                    hull: 80,
+                    shield: 80,
+                    cargo: 250,
+                    speed: 1.6,
+                    capacitor: 120.0,
+                    recharge: 12.0,
+                    fuel: 250.0,
+                    mass: 8.0,
+                    sprite: 9,
+                    scale: 1.5,
+                    slots: ModuleSlots {
+                        mining: 1,
+                        weapon: 1,
+                        utility: 3,
+                        defense: 1,
+                    },
+                    module_size: 2,
+                    power: 40.0,
+                    cpu: 60.0,
+                    ..miner
+                },
+            ],
+        }
+    }
+}
+
+//Modules Config, everything that can be fitted to a hull
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ModuleConfig {
+    pub starting_modules: Vec<String>,
+    pub modules: Vec<ModuleDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ModuleDef {
+    pub name: String,
+    pub slot: SlotKind,
+    pub size: u32,
+    pub power: f32,
+    pub cpu: f32,
+    pub effect: ModuleEffect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SlotKind {
+    Mining,
+    Weapon,
+    Utility,
+    Defense,
+}
+
+impl SlotKind {
+    pub const ALL: [SlotKind; 4] = [SlotKind::Mining, SlotKind::Weapon, SlotKind::Utility, SlotKind::Defense];
+}
+
+//What a module does once fitted, stat bonuses are added to the hull's
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum ModuleEffect {
+    MiningTool(MiningTool),
+    Weapon(Weapon),
+    Cargo(u32),
+    Capacitor(f32),
+    Fuel(f32),
+    Speed(f32),
+    HeatCapacity(f32),
+    Shield(u32),
+    Hull(u32),
+}
+
+impl ModuleConfig {
+    pub fn module(&self, name: &str) -> Option<&ModuleDef> {
+        self.modules.iter().find(|module| module.name == name)
+    }
+
+    pub fn starting_loadout(&self) -> Vec<ModuleDef> {
+        self.starting_modules
+            .iter()
+            .filter_map(|name| self.module(name).cloned())
+            .collect()
+    }
+}
+
+impl Default for ModuleConfig {
+    fn default() -> Self {
+        let module = |name: &str, slot: SlotKind, size: u32, power: f32, cpu: f32, effect: ModuleEffect| ModuleDef {
+            name: name.to_string(),
+            slot,
+            size,
+            power,
+            cpu,
+            effect,
+        };
+        ModuleConfig {
+            starting_modules: vec!["Laser".to_string(), "Pulse Laser".to_string()],
+            modules: vec![
+                module("Laser", SlotKind::Mining, 1, 5.0, 5.0, ModuleEffect::MiningTool(MiningTool::Laser)),
+                module("Mining Laser", SlotKind::Mining, 1, 8.0, 10.0, ModuleEffect::MiningTool(MiningTool::MiningLaser)),
+                module(
+                    "Particle Disruptor",
+                    SlotKind::Mining,
+                    2,
+                    12.0,
+                    15.0,
+                    ModuleEffect::MiningTool(MiningTool::ParticleDisruptor),
+                ),
+                module(
+                    "Matter Disintegrator",
+                    SlotKind::Mining,
+                    3,
+                    18.0,
+                    25.0,
+                    ModuleEffect::MiningTool(MiningTool::MatterDisintegrator),
+                ),
+                module("Pulse Laser", SlotKind::Weapon, 1, 10.0, 10.0, ModuleEffect::Weapon(Weapon::Laser)),
+                module("Plasma Launcher", SlotKind::Weapon, 2, 15.0, 20.0, ModuleEffect::Weapon(Weapon::PlasmaLauncher)),
+                module("Void Beam", SlotKind::Weapon, 3, 25.0, 30.0, ModuleEffect::Weapon(Weapon::VoidBeam)),
+                module("Cargo Expander", SlotKind::Utility, 1, 0.0, 5.0, ModuleEffect::Cargo(250)),
+                module("Capacitor Bank", SlotKind::Utility, 1, 0.0, 5.0, ModuleEffect::Capacitor(50.0)),
+                module("Auxiliary Tank", SlotKind::Utility, 1, 0.0, 3.0, ModuleEffect::Fuel(50.0)),
+                module("Afterburner", SlotKind::Utility, 2, 4.0, 10.0, ModuleEffect::Speed(0.3)),
+                module("Heat Sink", SlotKind::Utility, 1, 2.0, 5.0, ModuleEffect::HeatCapacity(50.0)),
+                module("Shield Booster", SlotKind::Defense, 1, 6.0, 8.0, ModuleEffect::Shield(50)),
+                module("Armor Plating", SlotKind::Defense, 1, 0.0, 2.0, ModuleEffect::Hull(50)),
+            ],
+        }
+    }
+}
+
+//Recipes Config
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RecipeBook {
+    pub refining: Vec<Recipe>,
+    pub crafting: Vec<Blueprint>,
+}
+
+//Ore in, goods out after a number of seconds
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Recipe {
+    pub name: String,
+    pub inputs: Vec<(Ore, u32)>,
+    pub outputs: Vec<(Good, u32)>,
+    pub seconds: f32,
+}
+
+//Goods spent in the workshop to build a module, named as in the module catalogue
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Blueprint {
+    pub name: String,
+    pub cost: Vec<(Good, u32)>,
+    pub module: String,
+}
+
+impl RecipeBook {
+    pub fn recipe(&self, name: &str) -> Option<&Recipe> {
+        self.refining.iter().find(|recipe| recipe.name == name)
+    }
+}
+
+impl Default for RecipeBook {
+    fn default() -> Self {
+        let recipe = |name: &str, inputs: Vec<(Ore, u32)>, outputs: Vec<(Good, u32)>, seconds: f32| Recipe {
+            name: name.to_string(),
+            inputs,
+            outputs,
+            seconds,
+        };
+        let blueprint = |name: &str, cost: Vec<(Good, u32)>| Blueprint {
+            name: name.to_string(),
+            cost,
+            module: name.to_string(),
+        };
+        RecipeBook {
+            refining: vec![
+                recipe("Water", vec![(Ore::Ice, 10)], vec![(Good::Water, 8)], 4.0),
+                recipe("Fuel", vec![(Ore::Ice, 20)], vec![(Good::Fuel, 5)], 8.0),
+                recipe("Alloy", vec![(Ore::Iron, 10), (Ore::Copper, 5)], vec![(Good::Alloy, 5)], 10.0),
+                recipe("Lenses", vec![(Ore::Diamond, 5)], vec![(Good::Lenses, 1)], 15.0),
+                recipe("Energy Cells", vec![(Ore::Plasma, 5)], vec![(Good::EnergyCells, 2)], 12.0),
+            ],
+            crafting: vec![
+                blueprint("Mining Laser", vec![(Good::Alloy, 20)]),
+                blueprint("Particle Disruptor", vec![(Good::Alloy, 40), (Good::Lenses, 5)]),
+                blueprint(
+                    "Matter Disintegrator",
+                    vec![(Good::Alloy, 60), (Good::Lenses, 10), (Good::EnergyCells, 10)],
+                ),
+                blueprint("Plasma Launcher", vec![(Good::Alloy, 30), (Good::EnergyCells, 10)]),
+                blueprint("Void Beam", vec![(Good::Alloy, 50), (Good::Lenses, 10), (Good::EnergyCells, 20)]),
+                blueprint("Cargo Expander", vec![(Good::Alloy, 15)]),
+                blueprint("Capacitor Bank", vec![(Good::Alloy, 10), (Good::EnergyCells, 5)]),
+                blueprint("Auxiliary Tank", vec![(Good::Alloy, 10), (Good::Water, 10)]),
+                blueprint("Afterburner", vec![(Good::Alloy, 25), (Good::EnergyCells, 5)]),
+                blueprint("Heat Sink", vec![(Good::Alloy, 10), (Good::Water, 20)]),
+                blueprint("Shield Booster", vec![(Good::Alloy, 20), (Good::EnergyCells, 10)]),
+                blueprint("Armor Plating", vec![(Good::Alloy, 30)]),
+            ],
+        }
+    }
+}
+
+//Market Config, what stations pay for ore
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MarketConfig {
+    pub ore_prices: Vec<(Ore, f32)>,
+    //Stock at which an ore sells for its base price, prices rise below it and fall above it
+    pub stock_target: f32,
+    pub price_floor: f32,
+    pub price_ceiling: f32,
+    //Units per second the stock drifts back towards the target
+    pub restock_rate: f32,
+}
+
+impl MarketConfig {
+    pub fn base_price(&self, ore: Ore) -> f32 {
+        self.ore_prices
+            .iter()
+            .find(|(priced, _)| *priced == ore)
+            .map_or(0.0, |(_, price)| *price)
+    }
+}
+
+impl Default for MarketConfig {
+    fn default() -> Self {
+        MarketConfig {
+            ore_prices: vec![
+                (Ore::Ice, 2.0),
+                (Ore::Copper, 5.0),
+                (Ore::Iron, 4.0),
+                (Ore::Gold, 15.0),
+                (Ore::Diamond, 30.0),
+                (Ore::Plasma, 50.0),
+            ],
+            stock_target: 500.0,
+            price_floor: 0.25,
+            price_ceiling: 2.0,
+            restock_rate: 2.0,
+        }
+    }
+}
+
+//NPC Config, the ships sharing a sector with the player
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NpcConfig {
+    //Seconds between an NPC picking its goal
+    pub think_interval: f32,
+    pub mining_range: f32,
+    //Rock per second an NPC miner takes out of an astroid
+    pub mining_rate: f32,
+    pub dock_range: f32,
+    //How far out traders go to leave the sector
+    pub exit_distance: f32,
+    pub archetypes: Vec<NpcArchetype>,
+}
+
+//Stats of one kind of NPC ship, speeds are in units per second
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NpcArchetype {
+    pub role: NpcRole,
+    pub faction: Faction,
+    pub name: String,
+    pub hull: u32,
+    pub speed: f32,
+    pub cargo: u32,
+    //Weapon damage per second and reach, unarmed ships have 0
+    pub damage: f32,
+    pub weapon_range: f32,
+    //How far it notices other ships
+    pub sight: f32,
+    pub scale: f32,
+    //How many turn up in a sector, inclusive
+    pub count: (u32, u32),
+}
+
+impl NpcConfig {
+    pub fn archetype(&self, role: NpcRole) -> Option<&NpcArchetype> {
+        self.archetypes.iter().find(|archetype| archetype.role == role)
+    }
+}
+
+impl Default for NpcConfig {
+    fn default() -> Self {
+        NpcConfig {
+            think_interval: 1.0,
+            mining_range: 120.0,
+            mining_rate: 20.0,
+            dock_range: 150.0,
+            exit_distance: 2000.0,
+            archetypes: vec![
+                NpcArchetype {
+                    role: NpcRole::Miner,
+                    faction: Faction::MinersGuild,
+                    name: "Miner".to_string(),
+                    hull: 60,
+                    speed: 40.0,
+                    cargo: 200,
+                    damage: 0.0,
+                    weapon_range: 0.0,
+                    sight: 500.0,
+                    scale: 0.6,
+                    count: (1, 3),
+                },
+                NpcArchetype {
+                    role: NpcRole::Trader,
+                    faction: Faction::StationAuthority,
+                    name: "Trader".to_string(),
+                    hull: 80,
+                    speed: 50.0,
+                    cargo: 300,
+                    damage: 0.0,
+                    weapon_range: 0.0,
+                    sight: 600.0,
+                    scale: 0.8,
+                    count: (0, 2),
+                },
+                NpcArchetype {
+                    role: NpcRole::Pirate,
+                    faction: Faction::Pirates,
+                    name: "Pirate".to_string(),
+                    hull: 70,
+                    speed: 55.0,
+                    cargo: 100,
+                    damage: 8.0,
+                    weapon_range: 250.0,
+                    sight: 700.0,
+                    scale: 0.7,
+                    count: (0, 2),
+                },
+            ],
+        }
+    }
+}
+
+//Faction Config, how the player's standing with each faction moves and what it changes.
+//Standing runs from -100 to 100.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FactionConfig {
+    pub starting: Vec<(Faction, f32)>,
+    //Factions at war with each other, their ships fight on sight
+    pub rivals: Vec<(Faction, Faction)>,
+    //Ships of a faction the player is below this with treat the player as an enemy
+    pub hostile_below: f32,
+    //Stations turn the player away below this standing with the station authority
+    pub dock_below: f32,
+    //Standing lost per point of hull damage done to a faction's ship
+    pub attack_penalty: f32,
+    //Standing lost for wrecking a faction's ship, and gained with its rivals
+    pub kill_penalty: f32,
+    pub kill_bonus: f32,
+    //Miners' guild standing gained per unit of ore sold
+    pub sale_bonus: f32,
+    //Ore sells for up to this fraction more at full station authority standing,
+    //and this much less at the bottom
+    pub price_bonus: f32,
+}
+
+impl FactionConfig {
+    pub fn at_war(&self, a: Faction, b: Faction) -> bool {
+        self.rivals.iter().any(|pair| *pair == (a, b) || *pair == (b, a))
+    }
+
+    pub fn rivals_of(&self, faction: Faction) -> Vec<Faction> {
+        Faction::ALL
+            .iter()
+            .filter(|other| self.at_war(faction, **other))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for FactionConfig {
+    fn default() -> Self {
+        FactionConfig {
+            starting: vec![
+                (Faction::MinersGuild, 0.0),
+                (Faction::Pirates, -50.0),
+                (Faction::StationAuthority, 0.0),
+            ],
+            rivals: vec![
+                (Faction::Pirates, Faction::MinersGuild),
+                (Faction::Pirates, Faction::StationAuthority),
+            ],
+            hostile_below: -25.0,
+            dock_below: -50.0,
+            attack_penalty: 0.5,
+            kill_penalty: 20.0,
+            kill_bonus: 5.0,
+            sale_bonus: 0.01,
+            price_bonus: 0.2,
+        }
+    }
+}
+
+//Contract Config, the work station boards offer
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ContractConfig {
+    //Contracts on each station's board
+    pub offers: usize,
+    //Most contracts the player can have going at once
+    pub max_active: usize,
+    //Ores stations ask for and how much of them, inclusive
+    pub deliveries: Vec<(Ore, u32, u32)>,
+    //Reward per unit delivered, as a multiple of the ore's base price
+    pub delivery_premium: f32,
+    pub delivery_time: f32,
+    //Ships wanted per bounty, inclusive, and the reward for each
+    pub bounty_count: (u32, u32),
+    pub bounty_reward: f32,
+    pub bounty_time: f32,
+    //How many sectors away surveys go, and the reward per sector travelled
+    pub survey_range: i32,
+    pub survey_reward: f32,
+    pub survey_time: f32,
+    //Standing gained with the issuer on completion, and lost on running out of time
+    pub standing_reward: f32,
+    pub failure_penalty: f32,
+}
+
+impl Default for ContractConfig {
+    fn default() -> Self {
+        ContractConfig {
+            offers: 3,
+            max_active: 5,
+            deliveries: vec![
+                (Ore::Ice, 200, 600),
+                (Ore::Copper, 100, 500),
+                (Ore::Iron, 100, 500),
+                (Ore::Gold, 20, 100),
+            ],
+            delivery_premium: 1.5,
+            delivery_time: 600.0,
+            bounty_count: (1, 2),
+            bounty_reward: 300.0,
+            bounty_time: 900.0,
+            survey_range: 3,
+            survey_reward: 150.0,
+            survey_time: 1200.0,
+            standing_reward: 10.0,
+            failure_penalty: 5.0,
+        }
+    }
+}
+
+//Event Config, the director that throws meteor showers, solar flares, pirate raids
+//and comets at the player. Danger runs from 0 at home to 1 far out.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EventConfig {
+    //Seconds between events in the safest and the most dangerous sectors,
+    //each wait is rolled from half to one and a half times this
+    pub quiet_interval: f32,
+    pub danger_interval: f32,
+    //Danger added per jump away from the home sector
+    pub danger_per_sector: f32,
+    //How likely each event is, the danger weight is added in full at danger 1
+    pub events: Vec<(SectorEventKind, f32, f32)>,
+    //Events arrive from this far away from the ship
+    pub spawn_distance: f32,
+    //Meteors per shower, inclusive, and how fast they come in
+    pub meteor_count: (u32, u32),
+    pub meteor_speed: f32,
+    //Seconds a flare lasts and shield drained per second
+    pub flare_duration: f32,
+    pub flare_drain: f32,
+    //Pirates per raid, inclusive
+    pub raid_size: (u32, u32),
+    //Comets carry mostly plasma and cross the sector at a steady speed
+    pub comet_plasma: f32,
+    pub comet_resources: u32,
+    pub comet_speed: f32,
+}
+
+impl EventConfig {
+    pub fn danger(&self, sector: (i32, i32)) -> f32 {
+        let jumps = (sector.0.abs() + sector.1.abs()) as f32;
+        (jumps * self.danger_per_sector).min(1.0)
+    }
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        EventConfig {
+            quiet_interval: 240.0,
+            danger_interval: 60.0,
+            danger_per_sector: 0.2,
+            events: vec![
+                (SectorEventKind::MeteorShower, 1.0, 1.0),
+                (SectorEventKind::SolarFlare, 1.0, 0.5),
+                (SectorEventKind::PirateRaid, 0.0, 2.0),
+                (SectorEventKind::CometFlyby, 0.5, 0.5),
+            ],
+            spawn_distance: 800.0,
+            meteor_count: (4, 8),
+            meteor_speed: 450.0,
+            flare_duration: 15.0,
+            flare_drain: 4.0,
+            raid_size: (2, 3),
+            comet_plasma: 0.6,
+            comet_resources: 1500,
+            comet_speed: 40.0,
+        }
+    }
+}
+
+//Regen Config, how depleted sectors grow new astroids. Each sector rolls its own
+//policy from these ranges and keeps regrowing while the player is elsewhere.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RegenConfig {
+    //Seconds between new astroids, inclusive range rolled per sector
+    pub respawn_interval: (f32, f32),
+    //Most astroids a sector regrows to, inclusive range rolled per sector
+    pub max_astroids: (u32, u32),
+    //How far each ore's share of new astroids can wander every interval
+    pub ore_drift: f32,
+    //New astroids keep at least this far from the ship
+    pub clearance: f32,
+}
+
+impl Default for RegenConfig {
+    fn default() -> Self {
+        RegenConfig {
+            respawn_interval: (90.0, 180.0),
+            max_astroids: (4, 7),
+            ore_drift: 0.1,
+            clearance: 250.0,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use rand::{rngs::StdRng, Rng};
+
+use crate::config::{ContractConfig, FactionConfig, MarketConfig, NpcConfig};
+use crate::solar_lords::{Contract, ContractKind, Faction};
+
+//Kinds of contract a board can roll
+#[derive(Clone, Copy)]
+enum Offer {
+    Deliver,
+    Bounty,
+    Survey,
+}
+
+//Rolls the contracts on a station's board. Deliveries and bounties come from the
+//station authority, surveys from the miners' guild.
+pub fn generate_contracts(
+    rng: &mut StdRng,
+    station: &str,
+    sector: (i32, i32),
+    config: &ContractConfig,
+    market: &MarketConfig,
+    npcs: &NpcConfig,
+    factions: &FactionConfig,
+) -> Vec<Contract> {
+    // Bounties go on the ships the station is at war with
+    let wanted = npcs
+        .archetypes
+        .iter()
+        .filter(|archetype| factions.at_war(Faction::StationAuthority, archetype.faction))
+        .map(|archetype| archetype.role)
+        .collect::<Vec<_>>();
+    let mut kinds = Vec::new();
+    if !config.deliveries.is_empty() {
+        kinds.push(Offer::Deliver);
+    }
+    if !wanted.is_empty() {
+        kinds.push(Offer::Bounty);
+    }
+    if config.survey_range > 0 {
+        kinds.push(Offer::Survey);
+    }
+    if kinds.is_empty() {
+        return Vec::new();
+    }
+
+    (0..config.offers)
+        .map(|_| match kinds[rng.gen_range(0, kinds.len())] {
+            Offer::Deliver => {
+                let (ore, min, max) = config.deliveries[rng.gen_range(0, config.deliveries.len())];
+                let amount = roll(rng, min, max);
+                Contract::new(
+                    ContractKind::Deliver { ore, amount },
+                    Faction::StationAuthority,
+                    station.to_string(),
+                    amount as f32 * market.base_price(ore) * config.delivery_premium,
+                    config.standing_reward,
+                    config.delivery_time,
+                )
+            }
+            Offer::Bounty => {
+                let role = wanted[rng.gen_range(0, wanted.len())];
+                let count = roll(rng, config.bounty_count.0, config.bounty_count.1);
+                Contract::new(
+                    ContractKind::Destroy { role, count },
+                    Faction::StationAuthority,
+                    station.to_string(),
+                    count as f32 * config.bounty_reward,
+                    config.standing_reward,
+                    config.bounty_time,
+                )
+            }
+            Offer::Survey => {
+                let range = config.survey_range;
+                // Anywhere in range but the sector the station is in
+                let target = loop {
+                    let target = (
+                        sector.0 + rng.gen_range(-range, range + 1),
+                        sector.1 + rng.gen_range(-range, range + 1),
+                    );
+                    if target != sector {
+                        break target;
+                    }
+                };
+                // Jumps only go to the four neighbours
+                let distance = (target.0 - sector.0).abs() + (target.1 - sector.1).abs();
+                Contract::new(
+                    ContractKind::Survey { sector: target },
+                    Faction::MinersGuild,
+                    station.to_string(),
+                    distance as f32 * config.survey_reward,
+                    config.standing_reward,
+                    config.survey_time,
+                )
+            }
+        })
+        .collect()
+}
+
+//A whole number in the inclusive range
+fn roll(rng: &mut StdRng, min: u32, max: u32) -> u32 {
+    if max > min {
+        rng.gen_range(min, max + 1)
+    } else {
+        min
+    }
+}
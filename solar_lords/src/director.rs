@@ -0,0 +1,117 @@
+use rand::{rngs::StdRng, Rng};
+use std::f32::consts::PI;
+
+use crate::config::EventConfig;
+use crate::solar_lords::{Astroid, AstroidSize, EventSpawn, Ore, SectorEventKind, Velocity};
+
+//Average seconds between events, dangerous sectors see them more often
+pub fn event_interval(config: &EventConfig, danger: f32) -> f32 {
+    let danger = danger.max(0.0).min(1.0);
+    config.quiet_interval + (config.danger_interval - config.quiet_interval) * danger
+}
+
+//Seconds until the next event, from half to one and a half times the interval
+pub fn next_wait(rng: &mut StdRng, config: &EventConfig, danger: f32) -> f32 {
+    event_interval(config, danger) * rng.gen_range(0.5, 1.5)
+}
+
+//Picks an event by weight, None if nothing can happen at this danger
+pub fn choose_event(rng: &mut StdRng, config: &EventConfig, danger: f32) -> Option<SectorEventKind> {
+    let weights = config
+        .events
+        .iter()
+        .map(|(kind, weight, danger_weight)| (*kind, (weight + danger_weight * danger).max(0.0)))
+        .collect::<Vec<_>>();
+    let total = weights.iter().map(|(_, weight)| weight).sum::<f32>();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0.0, total);
+    for (kind, weight) in weights.iter() {
+        if roll < *weight {
+            return Some(*kind);
+        }
+        roll -= weight;
+    }
+    weights.iter().rev().find(|(_, weight)| *weight > 0.0).map(|(kind, _)| *kind)
+}
+
+//What an event brings in around the ship at the center. Flares bring nothing,
+//the director system drains shields while one lasts.
+pub fn plan_event(rng: &mut StdRng, config: &EventConfig, kind: SectorEventKind, center: (f32, f32)) -> Vec<EventSpawn> {
+    let distance = config.spawn_distance;
+    match kind {
+        SectorEventKind::MeteorShower => {
+            // All from one side, each aimed somewhere close to the ship
+            let side = rng.gen_range(0.0, 2.0 * PI);
+            (0..roll(rng, config.meteor_count.0, config.meteor_count.1))
+                .map(|_| {
+                    let angle = side + rng.gen_range(-0.3, 0.3);
+                    let position = (center.0 + angle.cos() * distance, center.1 + angle.sin() * distance);
+                    let aim = (
+                        center.0 + rng.gen_range(-150.0, 150.0),
+                        center.1 + rng.gen_range(-150.0, 150.0),
+                    );
+                    let resources = rng.gen_range(100, 200);
+                    EventSpawn::Meteor {
+                        astroid: Astroid::random(rng).fragment(AstroidSize::Small, resources),
+                        position,
+                        velocity: towards(position, aim, config.meteor_speed),
+                    }
+                })
+                .collect()
+        }
+        SectorEventKind::SolarFlare => Vec::new(),
+        SectorEventKind::PirateRaid => {
+            let angle = rng.gen_range(0.0, 2.0 * PI);
+            (0..roll(rng, config.raid_size.0, config.raid_size.1))
+                .map(|_| EventSpawn::Raider {
+                    position: (
+                        center.0 + angle.cos() * distance + rng.gen_range(-100.0, 100.0),
+                        center.1 + angle.sin() * distance + rng.gen_range(-100.0, 100.0),
+                    ),
+                })
+                .collect()
+        }
+        SectorEventKind::CometFlyby => {
+            // Crosses past the ship to the far side and moves on
+            let angle = rng.gen_range(0.0, 2.0 * PI);
+            let position = (center.0 + angle.cos() * distance, center.1 + angle.sin() * distance);
+            let miss = rng.gen_range(-200.0, 200.0);
+            let exit = (
+                center.0 - angle.cos() * distance - angle.sin() * miss,
+                center.1 - angle.sin() * distance + angle.cos() * miss,
+            );
+            let travel = ((exit.0 - position.0).powi(2) + (exit.1 - position.1).powi(2)).sqrt();
+            let plasma = config.comet_plasma.max(0.0).min(1.0);
+            let mut composition = vec![(Ore::Plasma, plasma)];
+            if plasma < 1.0 {
+                composition.push((Ore::Ice, 1.0 - plasma));
+            }
+            vec![EventSpawn::Comet {
+                astroid: Astroid::with_composition(composition, config.comet_resources),
+                position,
+                velocity: towards(position, exit, config.comet_speed),
+                lifetime: if config.comet_speed > 0.0 { travel / config.comet_speed } else { 0.0 },
+            }]
+        }
+    }
+}
+
+fn towards(from: (f32, f32), to: (f32, f32), speed: f32) -> Velocity {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length <= 0.0 {
+        return Velocity::default();
+    }
+    Velocity::new(dx / length * speed, dy / length * speed)
+}
+
+//A whole number in the inclusive range
+fn roll(rng: &mut StdRng, min: u32, max: u32) -> u32 {
+    if max > min {
+        rng.gen_range(min, max + 1)
+    } else {
+        min
+    }
+}
@@ -0,0 +1,105 @@
+use crate::config::{ModuleDef, ModuleEffect, ShipClass, SlotKind};
+use crate::solar_lords::{MiningTool, Weapon};
+
+//Heat a ship can take before modules add to it
+pub const BASE_HEAT_CAPACITY: f32 = 100.0;
+
+//Slots of this kind the hull has
+pub fn slot_count(class: &ShipClass, kind: SlotKind) -> u32 {
+    match kind {
+        SlotKind::Mining => class.slots.mining,
+        SlotKind::Weapon => class.slots.weapon,
+        SlotKind::Utility => class.slots.utility,
+        SlotKind::Defense => class.slots.defense,
+    }
+}
+
+pub fn power_used(modules: &[ModuleDef]) -> f32 {
+    modules.iter().map(|module| module.power).sum()
+}
+
+pub fn cpu_used(modules: &[ModuleDef]) -> f32 {
+    modules.iter().map(|module| module.cpu).sum()
+}
+
+//Checks a loadout against the hull, the error says what doesn't fit
+pub fn validate(class: &ShipClass, modules: &[ModuleDef]) -> Result<(), String> {
+    for kind in SlotKind::ALL.iter() {
+        let used = modules.iter().filter(|module| module.slot == *kind).count() as u32;
+        if used > slot_count(class, *kind) {
+            return Err(format!("The {} only has {} {:?} slots", class.name, slot_count(class, *kind), kind));
+        }
+    }
+    if let Some(module) = modules.iter().find(|module| module.size > class.module_size) {
+        return Err(format!("The {} is too big for the {}", module.name, class.name));
+    }
+    if power_used(modules) > class.power {
+        return Err(format!("Not enough power grid, {:.0}/{:.0}", power_used(modules), class.power));
+    }
+    if cpu_used(modules) > class.cpu {
+        return Err(format!("Not enough CPU, {:.0}/{:.0}", cpu_used(modules), class.cpu));
+    }
+    Ok(())
+}
+
+//The hull's stats with every fitted module added on
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShipStats {
+    pub hull: u32,
+    pub shield: u32,
+    pub cargo: u32,
+    pub speed: f32,
+    pub capacitor: f32,
+    pub recharge: f32,
+    pub fuel: f32,
+    pub heat_capacity: f32,
+}
+
+impl ShipStats {
+    pub fn new(class: &ShipClass, modules: &[ModuleDef]) -> ShipStats {
+        let mut stats = ShipStats {
+            hull: class.hull,
+            shield: class.shield,
+            cargo: class.cargo,
+            speed: class.speed,
+            capacitor: class.capacitor,
+            recharge: class.recharge,
+            fuel: class.fuel,
+            heat_capacity: BASE_HEAT_CAPACITY,
+        };
+        for module in modules.iter() {
+            match module.effect {
+                ModuleEffect::Cargo(amount) => stats.cargo += amount,
+                ModuleEffect::Capacitor(amount) => stats.capacitor += amount,
+                ModuleEffect::Fuel(amount) => stats.fuel += amount,
+                ModuleEffect::Speed(amount) => stats.speed += amount,
+                ModuleEffect::HeatCapacity(amount) => stats.heat_capacity += amount,
+                ModuleEffect::Shield(amount) => stats.shield += amount,
+                ModuleEffect::Hull(amount) => stats.hull += amount,
+                ModuleEffect::MiningTool(_) | ModuleEffect::Weapon(_) => {}
+            }
+        }
+        stats
+    }
+}
+
+//Best mining tool in the loadout, only one beam runs at a time
+pub fn mining_tool(modules: &[ModuleDef]) -> Option<MiningTool> {
+    modules
+        .iter()
+        .filter_map(|module| match module.effect {
+            ModuleEffect::MiningTool(tool) => Some(tool),
+            _ => None,
+        })
+        .max_by_key(|tool| tool.tier())
+}
+
+pub fn weapon(modules: &[ModuleDef]) -> Option<Weapon> {
+    modules
+        .iter()
+        .filter_map(|module| match module.effect {
+            ModuleEffect::Weapon(weapon) => Some(weapon),
+            _ => None,
+        })
+        .max_by_key(|weapon| weapon.tier())
+}
@@ -0,0 +1,180 @@
+use amethyst::{
+    assets::{AssetStorage, Handle},
+    core::{bundle::SystemBundle, timing::Time, transform::{Transform, TransformBundle}},
+    ecs::prelude::{Dispatcher, DispatcherBuilder, Entity, Join},
+    prelude::*,
+    renderer::SpriteSheet,
+};
+use crate::bundle::SimulationBundle;
+use crate::config::NpcConfig;
+use crate::input::PlayerInput;
+use crate::replay::Recording;
+use crate::save::SaveGame;
+use crate::solar_lords::{
+    enter_sector, init_mining_ship, init_sector, spawn_astroid, spawn_npc, spawn_regrown_astroids, spawn_sector_events,
+    spawn_station, Astroid, JumpRequest, Npc, NpcRole, Ore, OrePickup, RadarContact, SectorEvents, SectorRegen, Velocity,
+};
+use crate::states::SimulationState;
+
+//Fixed timestep used for every headless tick
+pub const TICK_SECONDS: f32 = 1.0 / 60.0;
+
+//Steps the SimulationBundle without a window, renderer or input devices.
+//Inputs are scripted by writing PlayerInput before each tick.
+pub struct HeadlessRunner<'a, 'b> {
+    pub world: World,
+    pub ticks: u64,
+    dispatcher: Dispatcher<'a, 'b>,
+}
+
+impl<'a, 'b> HeadlessRunner<'a, 'b> {
+    pub fn new() -> amethyst::Result<HeadlessRunner<'a, 'b>> {
+        let mut world = World::new();
+        let mut builder = DispatcherBuilder::new();
+        TransformBundle::new().build(&mut world, &mut builder)?;
+        SimulationBundle::headless().build(&mut world, &mut builder)?;
+
+        let mut dispatcher = builder.build();
+        dispatcher.setup(&mut world);
+
+        // Components only the presentation side reads still need registering
+        world.register::<RadarContact>();
+        world.insert(AssetStorage::<SpriteSheet>::default());
+        world.insert(Time::default());
+        world.insert(SimulationState::Running);
+
+        Ok(HeadlessRunner {
+            world,
+            ticks: 0,
+            dispatcher,
+        })
+    }
+
+    //Handle to a sprite sheet that is never loaded, so entities still get a SpriteRender
+    pub fn sprite_sheet(&self) -> Handle<SpriteSheet> {
+        self.world
+            .read_resource::<AssetStorage<SpriteSheet>>()
+            .allocate()
+    }
+
+    pub fn spawn_player(&mut self) -> Entity {
+        let sprite_sheet = self.sprite_sheet();
+        init_mining_ship(&mut self.world, sprite_sheet)
+    }
+
+    pub fn spawn_astroid(&mut self, astroid: Astroid, x: f32, y: f32, i: u32) -> Entity {
+        let sprite_sheet = self.sprite_sheet();
+        spawn_astroid(&mut self.world, sprite_sheet, astroid, x, y, i)
+    }
+
+    pub fn spawn_pickup(&mut self, ore: Ore, amount: u32, x: f32, y: f32) -> Entity {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(x, y, 0.0);
+        self.world
+            .create_entity()
+            .with(OrePickup::new(ore, amount))
+            .with(Velocity::default())
+            .with(transform)
+            .build()
+    }
+
+    pub fn spawn_station(&mut self, name: &str, x: f32, y: f32) -> Entity {
+        let sprite_sheet = self.sprite_sheet();
+        spawn_station(&mut self.world, sprite_sheet, name.to_string(), x, y)
+    }
+
+    //An NPC with the default stats for its role, named after the role and a number
+    pub fn spawn_npc(&mut self, role: NpcRole, x: f32, y: f32) -> Entity {
+        let sprite_sheet = self.sprite_sheet();
+        let archetype = self
+            .world
+            .entry::<NpcConfig>()
+            .or_insert_with(NpcConfig::default)
+            .archetype(role)
+            .cloned()
+            .expect("Every role has an archetype");
+        let index = self.world.read_storage::<Npc>().join().count();
+        let name = format!("{} {}", archetype.name, index);
+        spawn_npc(&mut self.world, sprite_sheet, archetype, name, x, y, index as u64)
+    }
+
+    //The same layout SectorState starts with for this seed
+    pub fn spawn_sector(&mut self, seed: u64) -> Entity {
+        let player = self.spawn_player();
+        let sprite_sheet = self.sprite_sheet();
+        init_sector(&mut self.world, sprite_sheet, seed);
+        player
+    }
+
+    //The same start SectorState makes from a save
+    pub fn load_game(&mut self, mut save: SaveGame) -> Entity {
+        let player = self.spawn_player();
+        let galaxy = save.galaxy.take();
+        let sprite_sheet = self.sprite_sheet();
+        match galaxy {
+            Some(galaxy) => galaxy.resume(&mut self.world, sprite_sheet),
+            None => init_sector(&mut self.world, sprite_sheet, 0),
+        }
+        save.apply(&mut self.world, player);
+        player
+    }
+
+    pub fn step(&mut self, input: PlayerInput) {
+        self.step_for(input, TICK_SECONDS);
+    }
+
+    //A tick of any length, as a windowed frame would be
+    pub fn step_for(&mut self, input: PlayerInput, delta: f32) {
+        self.world.insert(input);
+        {
+            let mut time = self.world.write_resource::<Time>();
+            time.set_delta_seconds(delta);
+            time.increment_frame_number();
+        }
+        self.dispatcher.dispatch(&self.world);
+        self.world.maintain();
+        // Jumps swap the sector out between ticks, as the SectorState does
+        let jump = self.world.write_resource::<JumpRequest>().0.take();
+        if let Some(sector) = jump {
+            let sprite_sheet = self.sprite_sheet();
+            enter_sector(&mut self.world, sprite_sheet, sector);
+        }
+        let planned = self
+            .world
+            .try_fetch::<SectorEvents>()
+            .map_or(false, |events| !events.pending.is_empty());
+        if planned {
+            let sprite_sheet = self.sprite_sheet();
+            spawn_sector_events(&mut self.world, sprite_sheet);
+        }
+        let regrown = self
+            .world
+            .try_fetch::<SectorRegen>()
+            .map_or(false, |regen| !regen.pending.is_empty());
+        if regrown {
+            let sprite_sheet = self.sprite_sheet();
+            spawn_regrown_astroids(&mut self.world, sprite_sheet);
+        }
+        self.ticks += 1;
+    }
+
+    //Runs a number of ticks, asking the script for each tick's input
+    pub fn run<F>(&mut self, ticks: u64, mut script: F)
+    where
+        F: FnMut(u64, &World) -> PlayerInput,
+    {
+        for _ in 0..ticks {
+            let input = script(self.ticks, &self.world);
+            self.step(input);
+        }
+    }
+
+    //Rebuilds the recorded sector and feeds it every recorded input at its recorded length
+    pub fn replay(&mut self, recording: &Recording) -> Entity {
+        let player = self.spawn_sector(recording.seed);
+        for (input, delta) in recording.ticks_with_deltas() {
+            self.step_for(input.clone(), delta);
+        }
+        player
+    }
+}
@@ -0,0 +1,226 @@
+use amethyst::{
+    config::Config,
+    input::{Axis, BindingTypes, Bindings, Button, InputHandler},
+    utils::application_root_dir,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+//Typed input bindings, a typo in input.ron fails to parse instead of panicking in a system
+#[derive(Debug)]
+pub struct GameBindings;
+
+impl BindingTypes for GameBindings {
+    type Axis = AxisBinding;
+    type Action = ActionBinding;
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AxisBinding {
+    MoveX,
+    MoveY,
+    Zoom,
+    AimX,
+    AimY,
+    MineTrigger,
+    FireTrigger,
+}
+
+impl AxisBinding {
+    pub const ALL: [AxisBinding; 7] = [
+        AxisBinding::MoveX,
+        AxisBinding::MoveY,
+        AxisBinding::Zoom,
+        AxisBinding::AimX,
+        AxisBinding::AimY,
+        AxisBinding::MineTrigger,
+        AxisBinding::FireTrigger,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ActionBinding {
+    Mine,
+    Fire,
+    Dock,
+    Tractor,
+    Scan,
+    Workshop,
+    Jump,
+    Distress,
+}
+
+impl ActionBinding {
+    pub const ALL: [ActionBinding; 8] = [
+        ActionBinding::Mine,
+        ActionBinding::Fire,
+        ActionBinding::Dock,
+        ActionBinding::Tractor,
+        ActionBinding::Scan,
+        ActionBinding::Workshop,
+        ActionBinding::Jump,
+        ActionBinding::Distress,
+    ];
+}
+
+//Controls for a single tick, sampled by the InputSampleSystem and MouseRaycastSystem,
+//replayed from a recording or written directly by the headless runner.
+//Simulation systems only read this.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlayerInput {
+    pub move_x: f32,
+    pub move_y: f32,
+    pub mine: bool,
+    pub fire: bool,
+    pub tractor: bool,
+    pub scan: bool,
+    //Dock, jump and distress are only set on the tick the button goes down
+    pub dock: bool,
+    pub jump: bool,
+    pub distress: bool,
+    pub aim: Option<Aim>,
+}
+
+//Mouse aim is a point in the world, stick aim a direction from the ship
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum Aim {
+    Point(f32, f32),
+    Direction(f32, f32),
+}
+
+//How far a trigger has to be pulled to count as pressed
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+pub fn mine_pressed(input: &InputHandler<GameBindings>) -> bool {
+    input.action_is_down(&ActionBinding::Mine).unwrap_or(false)
+        || input.axis_value(&AxisBinding::MineTrigger).unwrap_or(0.0) > TRIGGER_THRESHOLD
+}
+
+pub fn fire_pressed(input: &InputHandler<GameBindings>) -> bool {
+    input.action_is_down(&ActionBinding::Fire).unwrap_or(false)
+        || input.axis_value(&AxisBinding::FireTrigger).unwrap_or(0.0) > TRIGGER_THRESHOLD
+}
+
+//Whether the mining beam follows the mouse or the right stick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AimMode {
+    Mouse,
+    Gamepad,
+}
+
+impl Default for AimMode {
+    fn default() -> Self {
+        AimMode::Mouse
+    }
+}
+
+//Names of every axis and action without a binding
+pub fn missing_bindings(bindings: &Bindings<GameBindings>) -> Vec<String> {
+    let mut missing = Vec::new();
+    for axis in AxisBinding::ALL.iter() {
+        if bindings.axis(axis).is_none() {
+            missing.push(format!("axis {:?}", axis));
+        }
+    }
+    for action in ActionBinding::ALL.iter() {
+        if bindings.action_bindings(action).next().is_none() {
+            missing.push(format!("action {:?}", action));
+        }
+    }
+    missing
+}
+
+//Shipped defaults live in config/input.ron, rebinding from the settings screen
+//writes the full set to config/input_user.ron which then takes priority.
+pub fn load_bindings() -> amethyst::Result<Bindings<GameBindings>> {
+    let config_dir = application_root_dir()?.join("config");
+    let mut bindings = Bindings::<GameBindings>::load(config_dir.join("input.ron"))?;
+
+    let user_path = user_bindings_path()?;
+    if user_path.exists() {
+        match Bindings::<GameBindings>::load(&user_path) {
+            Ok(user_bindings) => overlay_bindings(&mut bindings, &user_bindings),
+            Err(err) => log::warn!("Ignoring user bindings {:?}: {}", user_path, err),
+        }
+    }
+
+    for missing in missing_bindings(&bindings) {
+        log::warn!("No input bound for {}, it will never trigger", missing);
+    }
+    Ok(bindings)
+}
+
+pub fn save_user_bindings(bindings: &Bindings<GameBindings>) -> amethyst::Result<()> {
+    bindings.write(user_bindings_path()?)?;
+    Ok(())
+}
+
+fn user_bindings_path() -> amethyst::Result<PathBuf> {
+    Ok(application_root_dir()?.join("config").join("input_user.ron"))
+}
+
+//Replaces every axis and action found in the user bindings, keeping defaults for the rest
+fn overlay_bindings(bindings: &mut Bindings<GameBindings>, user: &Bindings<GameBindings>) {
+    for id in user.axes() {
+        if let Some(axis) = user.axis(id) {
+            bindings.remove_axis(id);
+            if let Err(err) = bindings.insert_axis(*id, axis.clone()) {
+                log::warn!("Could not apply user binding for {:?}: {}", id, err);
+            }
+        }
+    }
+    for id in user.actions() {
+        clear_action(bindings, id);
+        for combo in user.action_bindings(id) {
+            if let Err(err) = bindings.insert_action_binding(*id, combo.iter().cloned()) {
+                log::warn!("Could not apply user binding for {:?}: {}", id, err);
+            }
+        }
+    }
+}
+
+pub fn clear_action(bindings: &mut Bindings<GameBindings>, id: &ActionBinding) {
+    let combos = bindings
+        .action_bindings(id)
+        .map(|combo| combo.to_vec())
+        .collect::<Vec<Vec<Button>>>();
+    for combo in combos {
+        bindings.remove_action_binding(id, &combo);
+    }
+}
+
+//Short label for a binding shown on the settings screen
+pub fn describe_axis(axis: &Axis) -> String {
+    match axis {
+        Axis::Emulated { pos, neg } => format!("{} / {}", describe_button(pos), describe_button(neg)),
+        Axis::MouseWheel { horizontal } => {
+            if *horizontal {
+                "Mouse wheel (horizontal)".to_string()
+            } else {
+                "Mouse wheel".to_string()
+            }
+        }
+        Axis::Controller {
+            controller_id,
+            axis,
+            ..
+        } => format!("Pad {} {:?}", controller_id, axis),
+        Axis::Multiple(axes) => axes
+            .iter()
+            .map(describe_axis)
+            .collect::<Vec<String>>()
+            .join(", "),
+        other => format!("{:?}", other),
+    }
+}
+
+pub fn describe_button(button: &Button) -> String {
+    match button {
+        Button::Key(key) => format!("{:?}", key),
+        Button::Mouse(mouse_button) => format!("Mouse {:?}", mouse_button),
+        Button::Controller(controller_id, controller_button) => {
+            format!("Pad {} {:?}", controller_id, controller_button)
+        }
+        other => format!("{:?}", other),
+    }
+}
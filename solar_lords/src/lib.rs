@@ -0,0 +1,15 @@
+pub mod bundle;
+pub mod collision;
+pub mod config;
+pub mod contracts;
+pub mod director;
+pub mod fitting;
+pub mod headless;
+pub mod input;
+pub mod npc;
+pub mod regen;
+pub mod replay;
+pub mod save;
+pub mod solar_lords;
+pub mod states;
+pub mod systems;
@@ -1,21 +1,22 @@
-
 use amethyst::{
-    core::transform::TransformBundle,
+    core::{transform::TransformBundle, SystemExt},
     prelude::*,
-    renderer::{
-        plugins::{RenderFlat2D, RenderToWindow},
-        types::DefaultBackend,
-        RenderingBundle,
-    },
-    input::{InputBundle, StringBindings},
-    ui::{RenderUi, UiBundle},
+    config::Config,
+    input::InputBundle,
     utils::application_root_dir,
+    CoreApplication, StateEventReader,
 };
 
-mod solar_lords;
-mod systems;
-
-use crate::solar_lords::SectorState;
+use solar_lords::bundle::{PresentationBundle, SimulationBundle};
+use solar_lords::config::{
+    AstroidConfig, BackgroundConfig, CollisionConfig, ContractConfig, EventConfig, FactionConfig, FuelConfig, MarketConfig,
+    ModuleConfig, NpcConfig, PickupConfig, PowerConfig, RadarConfig, RecipeBook, RegenConfig, ScannerConfig, ShipyardConfig,
+};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::{self, GameBindings, PlayerInput};
+use solar_lords::replay::{InputTape, Recording};
+use solar_lords::states::{GameStateEvent, MainMenuState, SimulationState};
+use solar_lords::systems;
 
 
 
@@ -24,41 +25,99 @@ use crate::solar_lords::SectorState;
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
 
+    // `--headless <ticks>` steps the simulation without opening a window
+    // `--record <file>` saves every tick's input, `--replay <file>` plays it back
+    let args = std::env::args().collect::<Vec<String>>();
+    let flag = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .map(|index| args.get(index + 1).cloned())
+    };
+    let replay = match flag("--replay").flatten() {
+        Some(path) => Some(Recording::open(path)?),
+        None => None,
+    };
+    if let Some(ticks) = flag("--headless") {
+        let ticks = ticks.and_then(|ticks| ticks.parse().ok()).unwrap_or(600);
+        return run_headless(ticks, replay);
+    }
+    let tape = match (replay, flag("--record").flatten()) {
+        (Some(recording), _) => InputTape::replay(recording),
+        (None, Some(path)) => InputTape::record_to(path.into()),
+        (None, None) => InputTape::Idle,
+    };
+
     let app_root = application_root_dir()?;
     let display_config_path = app_root.join("config").join("display.ron");
-    let binding_path = app_root.join("config").join("input.ron");
-    let input_bundle = InputBundle::<StringBindings>::new()
-        .with_bindings_from_file(binding_path)?;
+    let background_config = BackgroundConfig::load(app_root.join("config").join("background.ron"))?;
+    let radar_config = RadarConfig::load(app_root.join("config").join("radar.ron"))?;
+    let astroid_config = AstroidConfig::load(app_root.join("config").join("astroids.ron"))?;
+    let pickup_config = PickupConfig::load(app_root.join("config").join("pickups.ron"))?;
+    let scanner_config = ScannerConfig::load(app_root.join("config").join("scanner.ron"))?;
+    let power_config = PowerConfig::load(app_root.join("config").join("power.ron"))?;
+    let collision_config = CollisionConfig::load(app_root.join("config").join("collision.ron"))?;
+    let recipe_book = RecipeBook::load(app_root.join("config").join("recipes.ron"))?;
+    let fuel_config = FuelConfig::load(app_root.join("config").join("fuel.ron"))?;
+    let shipyard_config = ShipyardConfig::load(app_root.join("config").join("ships.ron"))?;
+    let module_config = ModuleConfig::load(app_root.join("config").join("modules.ron"))?;
+    let market_config = MarketConfig::load(app_root.join("config").join("market.ron"))?;
+    let npc_config = NpcConfig::load(app_root.join("config").join("npcs.ron"))?;
+    let faction_config = FactionConfig::load(app_root.join("config").join("factions.ron"))?;
+    let contract_config = ContractConfig::load(app_root.join("config").join("contracts.ron"))?;
+    let event_config = EventConfig::load(app_root.join("config").join("events.ron"))?;
+    let regen_config = RegenConfig::load(app_root.join("config").join("regen.ron"))?;
+    let input_bundle = InputBundle::<GameBindings>::new()
+        .with_bindings(input::load_bindings()?);
 
 
     let game_data = GameDataBuilder::default()
                     .with_bundle(TransformBundle::new())?
-                    /*
-                    .with_bundle(
-                        InputBundle::<StringBindings>::new().with_bindings_from_file(input_bundle)?,
-                    )?*/
                     .with_bundle(input_bundle)?
-                    .with(systems::BackgroundMovementSystem, "background_movement", &["input_system"])
-                    .with(systems::ZoomSystem, "camera_zoom_system", &["input_system"])
-                    .with(systems::PlayerControlSystem, "player_control", &["input_system"])
-                    .with(systems::MiningSystem, "mining", &["input_system"])
-                    .with(systems::MouseRaycastSystem::default(), "mouse_raycast", &["input_system"])
-                    .with_bundle(
-                        RenderingBundle::<DefaultBackend>::new()
-                            // The RenderToWindow plugin provides all the scaffolding for opening a window and drawing on it
-                            .with_plugin(
-                                RenderToWindow::from_config_path(display_config_path)?
-                                    .with_clear([0.0, 0.0, 0.0, 1.0]),
-                            )
-                            // RenderFlat2D plugin is used to render entities with a `SpriteRender` component.
-                            .with_plugin(RenderFlat2D::default()),
-                    )?;
+                    .with(systems::InputSampleSystem::default(), "input_sample", &["input_system"])
+                    .with(systems::MouseRaycastSystem::default().pausable(SimulationState::Running), "mouse_raycast", &["input_sample"])
+                    .with_bundle(SimulationBundle::after(&["mouse_raycast"]))?
+                    .with_bundle(PresentationBundle::new(display_config_path))?;
 
     let assets_dir = app_root.join("assets");
-    let mut game = Application::new(assets_dir, SectorState::default(), game_data)?;
+    let mut game = CoreApplication::<_, GameStateEvent, StateEventReader<GameBindings>>::build(
+        assets_dir,
+        MainMenuState::default(),
+    )?
+        .with_resource(background_config)
+        .with_resource(radar_config)
+        .with_resource(astroid_config)
+        .with_resource(pickup_config)
+        .with_resource(scanner_config)
+        .with_resource(power_config)
+        .with_resource(collision_config)
+        .with_resource(recipe_book)
+        .with_resource(fuel_config)
+        .with_resource(shipyard_config)
+        .with_resource(module_config)
+        .with_resource(market_config)
+        .with_resource(npc_config)
+        .with_resource(faction_config)
+        .with_resource(contract_config)
+        .with_resource(event_config)
+        .with_resource(regen_config)
+        .with_resource(tape)
+        .build(game_data)?;
     game.run();
 
     Ok(())
 }
 
-
+fn run_headless(ticks: u64, replay: Option<Recording>) -> amethyst::Result<()> {
+    let mut runner = HeadlessRunner::new()?;
+    match replay {
+        Some(recording) => {
+            runner.replay(&recording);
+        }
+        None => {
+            runner.spawn_sector(rand::random());
+            runner.run(ticks, |_, _| PlayerInput::default());
+        }
+    }
+    println!("Simulated {} ticks headless", runner.ticks);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use amethyst::ecs::prelude::Entity;
+
+use crate::solar_lords::{NpcGoal, NpcRole};
+
+//Score every NPC gets for doing nothing in particular, anything more useful beats it
+pub const WANDER_SCORE: f32 = 0.1;
+
+//What an NPC knows about its surroundings when it picks a goal.
+//Fractions run from 0 to 1, entities come with how far away they are.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Situation {
+    pub hull: f32,
+    pub cargo: f32,
+    //Closest astroid with rock left in it
+    pub astroid: Option<(Entity, f32)>,
+    pub station: Option<(Entity, f32)>,
+    //Closest armed enemy in sight, for ships that can't fight back
+    pub threat: Option<(Entity, f32)>,
+    //Closest enemy in sight, for armed ships
+    pub prey: Option<(Entity, f32)>,
+}
+
+//How much each goal open to the role appeals right now
+pub fn score_goals(role: NpcRole, sight: f32, situation: &Situation) -> Vec<(NpcGoal, f32)> {
+    let mut scores = vec![(NpcGoal::Wander, WANDER_SCORE)];
+    // Closer threats and a weaker hull make running away more urgent
+    let closeness = |distance: f32| if sight > 0.0 { (1.0 - distance / sight).max(0.0) } else { 0.0 };
+
+    match role {
+        NpcRole::Miner => {
+            if let Some((astroid, _)) = situation.astroid {
+                if situation.cargo < 1.0 {
+                    scores.push((NpcGoal::Mine(astroid), 0.8 * (1.0 - situation.cargo)));
+                }
+            }
+            if let Some((station, _)) = situation.station {
+                scores.push((NpcGoal::Sell(station), situation.cargo * situation.cargo));
+            }
+        }
+        NpcRole::Trader => {
+            if let Some((station, _)) = situation.station {
+                if situation.cargo < 0.5 {
+                    scores.push((NpcGoal::Trade(station), 0.6 * (1.0 - situation.cargo)));
+                }
+            }
+            if situation.cargo > 0.0 {
+                scores.push((NpcGoal::Haul, situation.cargo));
+            }
+        }
+        // Pirates live off what they can catch
+        NpcRole::Pirate => {}
+    }
+
+    if let Some((prey, distance)) = situation.prey {
+        scores.push((NpcGoal::Hunt(prey), situation.hull * (0.5 + 0.5 * closeness(distance))));
+    }
+    if let Some((threat, distance)) = situation.threat {
+        scores.push((NpcGoal::Flee(threat), closeness(distance) * (1.5 - situation.hull)));
+    }
+    scores
+}
+
+//The best scoring goal, earlier goals win ties
+pub fn choose_goal(role: NpcRole, sight: f32, situation: &Situation) -> NpcGoal {
+    score_goals(role, sight, situation)
+        .into_iter()
+        .fold((NpcGoal::Wander, std::f32::MIN), |best, (goal, score)| {
+            if score > best.1 {
+                (goal, score)
+            } else {
+                best
+            }
+        })
+        .0
+}
@@ -0,0 +1,90 @@
+use rand::{rngs::StdRng, Rng};
+
+use crate::config::RegenConfig;
+use crate::solar_lords::{Astroid, Ore, RegenPolicy, SectorRegen, ASTROID_FIELD};
+
+//Rolls a sector's regrowth. The ore mix starts out at each ore's abundance.
+pub fn roll_policy(rng: &mut StdRng, config: &RegenConfig) -> RegenPolicy {
+    let (fastest, slowest) = config.respawn_interval;
+    let (fewest, most) = config.max_astroids;
+    RegenPolicy {
+        respawn_interval: if slowest > fastest { rng.gen_range(fastest, slowest) } else { fastest },
+        max_astroids: if most > fewest { rng.gen_range(fewest, most + 1) } else { fewest },
+        ore_mix: Ore::ALL
+            .iter()
+            .map(|ore| (*ore, ore.abundance() as f32 / 100.0))
+            .collect(),
+    }
+}
+
+//Nudges every ore's share up or down by up to the drift and evens them out again
+pub fn drift_mix(rng: &mut StdRng, mix: &mut Vec<(Ore, f32)>, drift: f32) {
+    if drift > 0.0 {
+        for (_, share) in mix.iter_mut() {
+            // Never quite dies out, so an ore can always drift back
+            *share = (*share * (1.0 + rng.gen_range(-drift, drift))).max(0.001);
+        }
+    }
+    let total = mix.iter().map(|(_, share)| share).sum::<f32>();
+    if total > 0.0 {
+        for (_, share) in mix.iter_mut() {
+            *share /= total;
+        }
+    }
+}
+
+//A fresh large astroid, its main ore picked from the mix with a trace of anything
+pub fn regrow_astroid(rng: &mut StdRng, mix: &[(Ore, f32)]) -> Astroid {
+    let mut roll = rng.gen_range(0.0, 1.0);
+    let mut main = mix.last().map(|(ore, _)| *ore).unwrap_or(Ore::Ice);
+    for (ore, share) in mix.iter() {
+        if roll < *share {
+            main = *ore;
+            break;
+        }
+        roll -= share;
+    }
+    let share = rng.gen_range(0.6, 0.9);
+    let trace = Ore::roll(rng);
+    let composition = if trace == main {
+        vec![(main, 1.0)]
+    } else {
+        vec![(main, share), (trace, 1.0 - share)]
+    };
+    Astroid::with_composition(composition, rng.gen_range(800, 1200))
+}
+
+//Somewhere in the astroid field, kept clear of the ship where there is room
+pub fn regrow_position(rng: &mut StdRng, ship: (f32, f32), clearance: f32) -> (f32, f32) {
+    let mut position = (0.0, 0.0);
+    for _ in 0..10 {
+        position = (
+            rng.gen_range(-ASTROID_FIELD.0, ASTROID_FIELD.0),
+            rng.gen_range(-ASTROID_FIELD.1, ASTROID_FIELD.1),
+        );
+        let (dx, dy) = (position.0 - ship.0, position.1 - ship.1);
+        if dx * dx + dy * dy >= clearance * clearance {
+            break;
+        }
+    }
+    position
+}
+
+//Runs a sector's regrowth forward by some seconds. Every interval the ore mix drifts
+//and, while the sector has fewer astroids than its policy allows, one more is queued.
+pub fn advance(regen: &mut SectorRegen, config: &RegenConfig, astroids: usize, elapsed: f32, ship: (f32, f32)) {
+    regen.timer += elapsed.max(0.0);
+    let interval = regen.policy.respawn_interval.max(1.0);
+    let mut count = astroids + regen.pending.len();
+    while regen.timer >= interval {
+        regen.timer -= interval;
+        drift_mix(&mut regen.rng, &mut regen.policy.ore_mix, config.ore_drift);
+        if count < regen.policy.max_astroids as usize {
+            let astroid = regrow_astroid(&mut regen.rng, &regen.policy.ore_mix);
+            let position = regrow_position(&mut regen.rng, ship, config.clearance);
+            regen.pending.push((format!("astroid {}", regen.next_index), astroid, position));
+            regen.next_index += 1;
+            count += 1;
+        }
+    }
+}
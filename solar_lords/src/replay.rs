@@ -0,0 +1,161 @@
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::headless::TICK_SECONDS;
+use crate::input::PlayerInput;
+
+//Every tick's PlayerInput and length plus the seed the sector was generated from.
+//Identical consecutive inputs are stored once with a repeat count.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Recording {
+    pub seed: u64,
+    pub frames: Vec<(u32, PlayerInput)>,
+    //Seconds each tick lasted, ticks without one replay at the headless tick
+    pub deltas: Vec<f32>,
+}
+
+impl Recording {
+    pub fn new(seed: u64) -> Recording {
+        Recording {
+            seed,
+            frames: Vec::new(),
+            deltas: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, input: &PlayerInput, delta: f32) {
+        match self.frames.last_mut() {
+            Some((count, last)) if last == input => *count += 1,
+            _ => self.frames.push((1, input.clone())),
+        }
+        self.deltas.push(delta);
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.frames.iter().map(|(count, _)| *count as u64).sum()
+    }
+
+    //Each tick's input in order
+    pub fn inputs(&self) -> impl Iterator<Item = &PlayerInput> {
+        self.frames
+            .iter()
+            .flat_map(|(count, input)| std::iter::repeat(input).take(*count as usize))
+    }
+
+    //How long a tick lasted when it was recorded
+    pub fn delta(&self, tick: usize) -> f32 {
+        self.deltas.get(tick).cloned().unwrap_or(TICK_SECONDS)
+    }
+
+    //Each tick's input and length in order
+    pub fn ticks_with_deltas(&self) -> impl Iterator<Item = (&PlayerInput, f32)> {
+        self.inputs().enumerate().map(move |(tick, input)| (input, self.delta(tick)))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> amethyst::Result<()> {
+        self.write(path)?;
+        Ok(())
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> amethyst::Result<Recording> {
+        Ok(Recording::load(path)?)
+    }
+}
+
+//Whether PlayerInput is being recorded, replayed or left alone
+pub enum InputTape {
+    Idle,
+    Recording {
+        path: PathBuf,
+        recording: Recording,
+    },
+    Replaying {
+        recording: Recording,
+        frame: usize,
+        repeat: u32,
+        tick: usize,
+    },
+}
+
+impl Default for InputTape {
+    fn default() -> Self {
+        InputTape::Idle
+    }
+}
+
+impl InputTape {
+    pub fn record_to(path: PathBuf) -> InputTape {
+        InputTape::Recording {
+            path,
+            recording: Recording::default(),
+        }
+    }
+
+    pub fn replay(recording: Recording) -> InputTape {
+        InputTape::Replaying {
+            recording,
+            frame: 0,
+            repeat: 0,
+            tick: 0,
+        }
+    }
+
+    //Seed a replay has to use, if any
+    pub fn replay_seed(&self) -> Option<u64> {
+        match self {
+            InputTape::Replaying { recording, .. } => Some(recording.seed),
+            _ => None,
+        }
+    }
+
+    //Starts a fresh recording for a newly generated sector
+    pub fn begin(&mut self, seed: u64) {
+        match self {
+            InputTape::Recording { recording, .. } => *recording = Recording::new(seed),
+            InputTape::Replaying { frame, repeat, tick, .. } => {
+                *frame = 0;
+                *repeat = 0;
+                *tick = 0;
+            }
+            InputTape::Idle => {}
+        }
+    }
+
+    pub fn record(&mut self, input: &PlayerInput, delta: f32) {
+        if let InputTape::Recording { recording, .. } = self {
+            recording.push(input, delta);
+        }
+    }
+
+    //The next replayed input and how long its tick lasted, None once the replay has run out
+    pub fn next_input(&mut self) -> Option<(PlayerInput, f32)> {
+        if let InputTape::Replaying {
+            recording,
+            frame,
+            repeat,
+            tick,
+        } = self
+        {
+            let (count, input) = recording.frames.get(*frame)?;
+            let delta = recording.delta(*tick);
+            *repeat += 1;
+            *tick += 1;
+            if *repeat >= *count {
+                *frame += 1;
+                *repeat = 0;
+            }
+            return Some((input.clone(), delta));
+        }
+        None
+    }
+
+    //Writes out the recording, if one is running
+    pub fn finish(&mut self) -> amethyst::Result<()> {
+        if let InputTape::Recording { path, recording } = self {
+            recording.save(path.as_path())?;
+            log::info!("Saved {} recorded ticks to {:?}", recording.ticks(), path);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+use amethyst::{
+    assets::Handle,
+    config::Config,
+    core::transform::Transform,
+    ecs::prelude::{Entity, Join},
+    prelude::*,
+    renderer::SpriteSheet,
+    utils::application_root_dir,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::{ModuleConfig, ShipyardConfig};
+use crate::solar_lords::{
+    fit_ship, refit_ship, resume_galaxy, sector_astroids, Astroid, Cargo, Collider, Contract, Faction, Fitting, Fuel,
+    Galaxy, Good, MessageLog, Ore, Player, QuestLog, RegenPolicy, Reputation, SectorHistory, SectorRecord, SectorRegen,
+    Ship,
+};
+
+//Player progress written by the pause menu and read back by Load Game
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaveGame {
+    pub hull: u32,
+    pub sheild: u32,
+    pub wealth: f32,
+    pub position: (f32, f32),
+    pub cargo: Vec<(Ore, u32)>,
+    pub goods: Vec<(Good, u32)>,
+    pub fuel: Option<f32>,
+    pub ship: Option<String>,
+    pub hangar: Vec<String>,
+    pub modules: Option<Vec<String>>,
+    pub spare_modules: Vec<String>,
+    pub standing: Vec<(Faction, f32)>,
+    pub contracts: Vec<Contract>,
+    pub galaxy: Option<SavedGalaxy>,
+}
+
+//Where the player is and every sector they have been to, so regrowth carries on after loading
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedGalaxy {
+    pub seed: u64,
+    pub sector: (i32, i32),
+    pub clock: f32,
+    pub sectors: Vec<SavedSector>,
+}
+
+//A sector's astroids and regrowth, the one the player is in is saved as if they had just left it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedSector {
+    pub sector: (i32, i32),
+    pub policy: RegenPolicy,
+    pub timer: f32,
+    pub next_index: u32,
+    pub astroids: Vec<(String, Astroid, (f32, f32))>,
+    pub left_at: f32,
+}
+
+impl SavedSector {
+    fn new(sector: (i32, i32), regen: &SectorRegen, astroids: Vec<(String, Astroid, (f32, f32))>, left_at: f32) -> SavedSector {
+        SavedSector {
+            sector,
+            policy: regen.policy.clone(),
+            timer: regen.timer,
+            next_index: regen.next_index,
+            astroids,
+            left_at,
+        }
+    }
+}
+
+impl SavedGalaxy {
+    pub fn capture(world: &World) -> Option<SavedGalaxy> {
+        let galaxy = world.try_fetch::<Galaxy>()?;
+        let history = world.try_fetch::<SectorHistory>();
+        let clock = history.as_ref().map_or(0.0, |history| history.clock);
+        let mut sectors = history
+            .map(|history| {
+                history
+                    .sectors
+                    .iter()
+                    .map(|(sector, record)| SavedSector::new(*sector, &record.regen, record.astroids.clone(), record.left_at))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if let Some(regen) = world.try_fetch::<SectorRegen>() {
+            sectors.push(SavedSector::new(galaxy.sector, &regen, sector_astroids(world), clock));
+        }
+        sectors.sort_by_key(|saved| saved.sector);
+        Some(SavedGalaxy {
+            seed: galaxy.seed,
+            sector: galaxy.sector,
+            clock,
+            sectors,
+        })
+    }
+
+    //Puts the galaxy back and enters the sector the player was in
+    pub fn resume(self, world: &mut World, spritesheet_handle: Handle<SpriteSheet>) {
+        let mut galaxy = Galaxy::new(self.seed);
+        galaxy.sector = self.sector;
+        let mut history = SectorHistory {
+            clock: self.clock,
+            sectors: HashMap::new(),
+        };
+        for saved in self.sectors {
+            // Regrowth rolls differ from before the save, only replays would notice and they don't load saves
+            let rng = StdRng::seed_from_u64(galaxy.sector_seed(saved.sector) ^ saved.next_index as u64);
+            let regen = SectorRegen {
+                policy: saved.policy,
+                timer: saved.timer,
+                next_index: saved.next_index,
+                pending: Vec::new(),
+                rng,
+            };
+            let record = SectorRecord {
+                regen,
+                astroids: saved.astroids,
+                left_at: saved.left_at,
+            };
+            history.sectors.insert(saved.sector, record);
+        }
+        resume_galaxy(world, spritesheet_handle, galaxy, history);
+    }
+}
+
+impl SaveGame {
+    pub fn capture(world: &World) -> SaveGame {
+        let players = world.read_storage::<Player>();
+        let cargos = world.read_storage::<Cargo>();
+        let transforms = world.read_storage::<Transform>();
+
+        let mut save = SaveGame {
+            hull: 0,
+            sheild: 0,
+            wealth: 0.0,
+            position: (0.0, 0.0),
+            cargo: Vec::new(),
+            goods: Vec::new(),
+            fuel: None,
+            ship: None,
+            hangar: Vec::new(),
+            modules: None,
+            spare_modules: Vec::new(),
+            standing: Vec::new(),
+            contracts: Vec::new(),
+            galaxy: SavedGalaxy::capture(world),
+        };
+        let fuels = world.read_storage::<Fuel>();
+        let ships = world.read_storage::<Ship>();
+        let fittings = world.read_storage::<Fitting>();
+        let reputations = world.read_storage::<Reputation>();
+        let logs = world.read_storage::<QuestLog>();
+        for (player, cargo, transform, fuel, ship, fitting, reputation, log) in (
+            &players,
+            &cargos,
+            &transforms,
+            fuels.maybe(),
+            ships.maybe(),
+            fittings.maybe(),
+            reputations.maybe(),
+            logs.maybe(),
+        )
+            .join()
+        {
+            save.hull = player.hull;
+            save.sheild = player.sheild;
+            save.wealth = player.wealth;
+            save.position = (transform.translation().x, transform.translation().y);
+            save.cargo = cargo.contents.iter().map(|(ore, amount)| (*ore, *amount)).collect();
+            save.goods = cargo.goods.iter().map(|(good, amount)| (*good, *amount)).collect();
+            save.fuel = fuel.map(|fuel| fuel.amount);
+            save.ship = ship.map(|ship| ship.class.name.clone());
+            save.hangar = player.hangar.clone();
+            if let Some(fitting) = fitting {
+                save.modules = Some(fitting.modules.iter().map(|module| module.name.clone()).collect());
+                save.spare_modules = fitting.spares.iter().map(|module| module.name.clone()).collect();
+            }
+            if let Some(reputation) = reputation {
+                save.standing = Faction::ALL
+                    .iter()
+                    .map(|faction| (*faction, reputation.standing(*faction)))
+                    .collect();
+            }
+            if let Some(log) = log {
+                save.contracts = log.contracts.clone();
+            }
+        }
+        save
+    }
+
+    pub fn apply(&self, world: &mut World, player: Entity) {
+        // Hull first, it sets the capacities everything below is filled up to
+        let class = self.ship.as_ref().and_then(|name| {
+            world
+                .entry::<ShipyardConfig>()
+                .or_insert_with(ShipyardConfig::default)
+                .class(name)
+                .cloned()
+        });
+        if let Some(class) = class {
+            if let Err(err) = refit_ship(world, player, &class) {
+                world
+                    .entry::<MessageLog>()
+                    .or_insert_with(MessageLog::default)
+                    .push(format!("Could not restore the {}: {}", class.name, err));
+            }
+        }
+        // Then the modules on it, names no longer in the catalogue are dropped
+        let catalogue = world.entry::<ModuleConfig>().or_insert_with(ModuleConfig::default).clone();
+        let resolve = |names: &[String]| {
+            names
+                .iter()
+                .filter_map(|name| catalogue.module(name).cloned())
+                .collect::<Vec<_>>()
+        };
+        if let Some(modules) = self.modules.as_ref().map(|names| resolve(names)) {
+            let current = world.read_storage::<Ship>().get(player).map(|ship| ship.class.clone());
+            if let Some(current) = current {
+                if let Err(err) = fit_ship(world, player, &current, modules) {
+                    world
+                        .entry::<MessageLog>()
+                        .or_insert_with(MessageLog::default)
+                        .push(format!("Could not restore the fitting: {}", err));
+                }
+            }
+            if let Some(fitting) = world.write_storage::<Fitting>().get_mut(player) {
+                fitting.spares = resolve(&self.spare_modules);
+            }
+        }
+        if let Some(player) = world.write_storage::<Player>().get_mut(player) {
+            player.hull = self.hull;
+            player.sheild = self.sheild;
+            player.wealth = self.wealth;
+            for ship in self.hangar.iter() {
+                if !player.hangar.contains(ship) {
+                    player.hangar.push(ship.clone());
+                }
+            }
+        }
+        if let Some(cargo) = world.write_storage::<Cargo>().get_mut(player) {
+            for (ore, amount) in self.cargo.iter() {
+                cargo.add(*ore, *amount);
+            }
+            for (good, amount) in self.goods.iter() {
+                cargo.add_good(*good, *amount);
+            }
+        }
+        if let (Some(amount), Some(fuel)) = (self.fuel, world.write_storage::<Fuel>().get_mut(player)) {
+            fuel.amount = amount.min(fuel.capacity);
+        }
+        if let Some(reputation) = world.write_storage::<Reputation>().get_mut(player) {
+            for (faction, standing) in self.standing.iter() {
+                reputation.standing.insert(*faction, *standing);
+            }
+        }
+        if let Some(log) = world.write_storage::<QuestLog>().get_mut(player) {
+            log.contracts = self.contracts.clone();
+        }
+        if let Some(transform) = world.write_storage::<Transform>().get_mut(player) {
+            transform.set_translation_x(self.position.0);
+            transform.set_translation_y(self.position.1);
+        }
+        if let Some(collider) = world.write_storage::<Collider>().get_mut(player) {
+            collider.last_position = None;
+        }
+    }
+
+    pub fn save_to_disk(&self) -> amethyst::Result<()> {
+        let path = save_path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        self.write(&path)?;
+        Ok(())
+    }
+
+    //Nothing if there is no save yet, an error if there is one that can't be read
+    pub fn load_from_disk() -> amethyst::Result<Option<SaveGame>> {
+        let path = save_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(SaveGame::load(&path)?))
+    }
+}
+
+fn save_path() -> amethyst::Result<PathBuf> {
+    Ok(application_root_dir()?.join("saves").join("savegame.ron"))
+}
@@ -1,15 +1,37 @@
 use amethyst::{
     assets::{AssetStorage, Handle, Loader},
     core::{math::*, timing::Time, transform::Transform, Named, Parent, WithNamed},
-    ecs::prelude::{Component, DenseVecStorage, Entity},
+    ecs::prelude::{Component, DenseVecStorage, Entity, Join, NullStorage},
+    input::{is_key_down, InputEvent, VirtualKeyCode},
     prelude::*,
     renderer::{
         Camera, ImageFormat, SpriteRender, SpriteSheet, SpriteSheetFormat, Texture, Transparent,
     },
-    ui::{Anchor, LineMode, TtfFormat, UiText, UiTransform},
+    ui::{get_default_font, Anchor, FontAsset, FontHandle, LineMode, TtfFormat, UiImage, UiText, UiTransform},
     window::ScreenDimensions,
 };
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
+
+use crate::collision::Shape;
+use crate::config::{
+    AstroidConfig, BackgroundConfig, Blueprint, ContractConfig, EventConfig, FactionConfig, MarketConfig, ModuleConfig,
+    ModuleDef, NpcArchetype, NpcConfig, RadarConfig, RegenConfig, ShipClass, ShipyardConfig,
+};
+use crate::contracts::generate_contracts;
+use crate::director;
+use crate::fitting::{self, ShipStats};
+use crate::input::ActionBinding;
+use crate::regen;
+use crate::replay::InputTape;
+use crate::save::SaveGame;
+use crate::states::{
+    GameOverState, GameStateEvent, GameTrans, PauseState, SimulationState, StationState, WorkshopState,
+};
 
 //Public Constants
 
@@ -17,6 +39,33 @@ use rand::Rng;
 pub const CAMERA_WIDTH: f32 = 1000.0;
 pub const CAMERA_HEIGHT: f32 = 750.0;
 
+//Sprite sizes, used for collision shapes
+pub const SHIP_SPRITE_SIZE: (f32, f32) = (48.0, 96.0);
+pub const FIGHTER_SPRITE_SIZE: (f32, f32) = (32.0, 32.0);
+pub const ASTROID_SPRITE_SIZE: (f32, f32) = (48.0, 48.0);
+pub const STATION_SPRITE_SIZE: (f32, f32) = (32.0, 32.0);
+
+//Stations reuse the round sprite, scaled up
+pub const STATION_SPRITE: usize = 6;
+pub const STATION_SCALE: f32 = 3.0;
+
+//The small fighter sprites, the other ship sprites are full size hulls
+pub const FIGHTER_SPRITES: [usize; 3] = [6, 9, 10];
+
+//Astroid frames, from whole down to nearly mined out
+pub const ASTROID_SPRITES: [usize; 4] = [1, 2, 3, 4];
+
+//NPC ships all push about as hard as a light hull
+pub const NPC_MASS: f32 = 8.0;
+
+//Astroids a sector starts out with, spread across the field around the origin
+pub const SECTOR_ASTROIDS: u32 = 4;
+pub const ASTROID_FIELD: (f32, f32) = (500.0, 350.0);
+
+//Hud Constraints
+pub const HUD_BAR_WIDTH: f32 = 200.0;
+pub const HUD_BAR_HEIGHT: f32 = 14.0;
+
 //Arena Constraints
 //pub const ARENA_HEIGHT: f32 = 800.0;
 //pub const ARENA_WIDTH: f32 = 1600.0;
@@ -31,10 +80,20 @@ pub struct CameraDimensions{
 pub struct SectorState {
     pub background_sprite_handle: Option<Handle<SpriteSheet>>,
     pub spritesheet_handle: Option<Handle<SpriteSheet>>,
+    pub save: Option<SaveGame>,
+}
+
+impl SectorState {
+    pub fn from_save(save: SaveGame) -> SectorState {
+        SectorState {
+            save: Some(save),
+            ..Default::default()
+        }
+    }
 }
 
-impl SimpleState for SectorState {
-    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for SectorState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
         let world = data.world;
 
         self.background_sprite_handle
@@ -44,93 +103,977 @@ impl SimpleState for SectorState {
         world.register::<Astroid>();
 
         let _player = init_mining_ship(world, self.spritesheet_handle.clone().unwrap());
+        let saved_galaxy = self.save.as_mut().and_then(|save| save.galaxy.take());
         let _camera = init_camera(world, _player);
         init_background_sprites(
             world,
             self.background_sprite_handle.clone().unwrap(),
-            _camera,
+            self.spritesheet_handle.clone().unwrap(),
         );
-        for i in 0..4 {
-            init_astroids(world, self.spritesheet_handle.clone().unwrap(), i);
+
+        // A replay has to regenerate the sector it was recorded in, a loaded game carries on in its galaxy
+        world.entry::<InputTape>().or_insert_with(InputTape::default);
+        let replay_seed = world.read_resource::<InputTape>().replay_seed();
+        let seed = replay_seed
+            .or_else(|| saved_galaxy.as_ref().map(|galaxy| galaxy.seed))
+            .unwrap_or_else(|| rand::thread_rng().gen());
+        world.write_resource::<InputTape>().begin(seed);
+        match saved_galaxy {
+            Some(galaxy) if replay_seed.is_none() => galaxy.resume(world, self.spritesheet_handle.clone().unwrap()),
+            _ => init_sector(world, self.spritesheet_handle.clone().unwrap(), seed),
+        }
+        // Entering the sector puts the ship at the arrival point, so the save goes on after
+        if let Some(save) = self.save.take() {
+            save.apply(world, _player);
         }
+        init_minimap(world);
+        init_hud(world);
+
+        world.insert(SimulationState::Running);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        let world = data.world;
+        world.insert(SimulationState::Paused);
+        if let Err(err) = world.write_resource::<InputTape>().finish() {
+            log::warn!("Could not save recording: {}", err);
+        }
+        world.delete_all();
+        world.remove::<Hud>();
+        world.remove::<Minimap>();
+        world.remove::<FogOfWar>();
+    }
+
+    fn on_pause(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        data.world.insert(SimulationState::Paused);
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        data.world.insert(SimulationState::Running);
+    }
+
+    fn handle_event(&mut self, _: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        if let StateEvent::Window(event) = &event {
+            if is_key_down(event, VirtualKeyCode::Escape) {
+                return Trans::Push(Box::new(PauseState::default()));
+            }
+        }
+        if let StateEvent::Input(InputEvent::ActionPressed(ActionBinding::Workshop)) = &event {
+            return Trans::Push(Box::new(WorkshopState::default()));
+        }
+        Trans::None
     }
 
-    fn update(&mut self, _: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        _camera = init_camera(world, _player);
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        let jump = data.world.write_resource::<JumpRequest>().0.take();
+        if let Some(sector) = jump {
+            enter_sector(data.world, self.spritesheet_handle.clone().unwrap(), sector);
+        }
+        spawn_sector_events(data.world, self.spritesheet_handle.clone().unwrap());
+        spawn_regrown_astroids(data.world, self.spritesheet_handle.clone().unwrap());
+
+        let destroyed = (&data.world.read_storage::<Player>())
+            .join()
+            .any(|player| player.hull == 0);
+        if destroyed {
+            return Trans::Push(Box::new(GameOverState::default()));
+        }
+        let docked = (&data.world.read_storage::<Player>())
+            .join()
+            .find_map(|player| player.docked.clone());
+        if let Some(station) = docked {
+            return Trans::Push(Box::new(StationState::new(station)));
+        }
         Trans::None
     }
 }
 
 //Mining Sprite
-fn init_mining_ship(world: &mut World, spritesheet_handle: Handle<SpriteSheet>) -> Entity {
+//The player starts out in the shipyard's starting hull
+pub fn init_mining_ship(world: &mut World, spritesheet_handle: Handle<SpriteSheet>) -> Entity {
+    let class = world
+        .entry::<ShipyardConfig>()
+        .or_insert_with(ShipyardConfig::default)
+        .starting_class();
+    let loadout = world
+        .entry::<ModuleConfig>()
+        .or_insert_with(ModuleConfig::default)
+        .starting_loadout();
+    world.register::<Ship>();
+    world.register::<Fitting>();
+    world.register::<Reputation>();
+    world.register::<QuestLog>();
+    let reputation = Reputation::new(&world.entry::<FactionConfig>().or_insert_with(FactionConfig::default));
     let mut transform = Transform::default();
     transform.set_translation_xyz(CAMERA_WIDTH * 0.5, CAMERA_HEIGHT * 0.5, 0.1);
-    let sprite = SpriteRender::new(spritesheet_handle, 0);
+    transform.set_scale(Vector3::new(class.scale, class.scale, 1.0));
+    let sprite = SpriteRender::new(spritesheet_handle, class.sprite);
 
-    world
+    let ship = world
         .create_entity()
-        .with(Player::new())
+        .with(Player::from_class(&class))
+        .with(Cargo::new(class.cargo))
+        .with(Capacitor::new(class.capacitor, class.recharge))
+        .with(Heat::new(fitting::BASE_HEAT_CAPACITY))
+        .with(PowerGrid::default())
+        .with(Scanner::default())
+        .with(Refinery::default())
+        .with(Fuel::new(class.fuel))
+        .with(Collider::new(ship_shape(class.sprite), class.mass))
+        .with(Velocity::default())
+        .with(Ship::new(class.clone()))
+        .with(Fitting::default())
+        .with(reputation)
+        .with(QuestLog::default())
         .with(transform)
         .with(sprite)
         .named("player_ship")
-        .build()
+        .build();
+
+    if let Err(err) = fit_ship(world, ship, &class, loadout) {
+        world
+            .entry::<MessageLog>()
+            .or_insert_with(MessageLog::default)
+            .push(format!("Starting loadout doesn't fit the {}: {}", class.name, err));
+    }
+    // Start out with everything topped up to what the modules allow
+    if let Some(player) = world.write_storage::<Player>().get_mut(ship) {
+        player.hull = player.max_hull;
+        player.sheild = player.max_sheild;
+    }
+    if let Some(fuel) = world.write_storage::<Fuel>().get_mut(ship) {
+        fuel.amount = fuel.capacity;
+    }
+    if let Some(capacitor) = world.write_storage::<Capacitor>().get_mut(ship) {
+        capacitor.charge = capacitor.capacity;
+    }
+    ship
+}
+
+//Puts a hull and loadout on the ship, recalculating its stats from both.
+//Fails if the loadout breaks the fitting rules or the cargo wouldn't fit in the hold.
+pub fn fit_ship(world: &mut World, ship: Entity, class: &ShipClass, modules: Vec<ModuleDef>) -> Result<(), String> {
+    fitting::validate(class, &modules)?;
+    let stats = ShipStats::new(class, &modules);
+
+    if let Some(cargo) = world.write_storage::<Cargo>().get_mut(ship) {
+        if cargo.total() > stats.cargo {
+            return Err(format!("Too much cargo for a hold of {}", stats.cargo));
+        }
+        cargo.capacity = stats.cargo;
+    }
+    if let Some(player) = world.write_storage::<Player>().get_mut(ship) {
+        player.max_hull = stats.hull;
+        player.max_sheild = stats.shield;
+        player.hull = player.hull.min(stats.hull);
+        player.sheild = player.sheild.min(stats.shield);
+        player.speed = stats.speed;
+    }
+    if let Some(capacitor) = world.write_storage::<Capacitor>().get_mut(ship) {
+        capacitor.capacity = stats.capacitor;
+        capacitor.recharge = stats.recharge;
+        capacitor.charge = capacitor.charge.min(stats.capacitor);
+    }
+    if let Some(fuel) = world.write_storage::<Fuel>().get_mut(ship) {
+        fuel.capacity = stats.fuel;
+        fuel.amount = fuel.amount.min(stats.fuel);
+    }
+    if let Some(heat) = world.write_storage::<Heat>().get_mut(ship) {
+        heat.capacity = stats.heat_capacity;
+    }
+    if let Some(collider) = world.write_storage::<Collider>().get_mut(ship) {
+        collider.shape = ship_shape(class.sprite);
+        collider.mass = class.mass;
+    }
+    if let Some(transform) = world.write_storage::<Transform>().get_mut(ship) {
+        transform.set_scale(Vector3::new(class.scale, class.scale, 1.0));
+    }
+    if let Some(sprite) = world.write_storage::<SpriteRender>().get_mut(ship) {
+        sprite.sprite_number = class.sprite;
+    }
+    world
+        .write_storage::<Ship>()
+        .insert(ship, Ship::new(class.clone()))
+        .map_err(|err| err.to_string())?;
+    if let Some(fitting) = world.write_storage::<Fitting>().get_mut(ship) {
+        fitting.modules = modules;
+    }
+    Ok(())
+}
+
+//Outline of a hull drawn with this sprite
+pub fn ship_shape(sprite: usize) -> Shape {
+    let (width, height) = if FIGHTER_SPRITES.contains(&sprite) {
+        FIGHTER_SPRITE_SIZE
+    } else {
+        SHIP_SPRITE_SIZE
+    };
+    Shape::sprite_box(width, height)
+}
+
+//Moves the pilot into another hull, which takes on the same share of damage.
+//Modules the new hull can't take go to the spares.
+pub fn refit_ship(world: &mut World, ship: Entity, class: &ShipClass) -> Result<(), String> {
+    let condition = world.read_storage::<Player>().get(ship).map(|player| {
+        (
+            player.hull as f32 / player.max_hull.max(1) as f32,
+            player.sheild as f32 / player.max_sheild.max(1) as f32,
+        )
+    });
+    let current = world
+        .read_storage::<Fitting>()
+        .get(ship)
+        .map(|fitting| fitting.modules.clone())
+        .unwrap_or_default();
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    for module in current.into_iter() {
+        kept.push(module);
+        if fitting::validate(class, &kept).is_err() {
+            removed.push(kept.pop().unwrap());
+        }
+    }
+
+    fit_ship(world, ship, class, kept)?;
+    if let Some(fitting) = world.write_storage::<Fitting>().get_mut(ship) {
+        fitting.spares.extend(removed);
+    }
+    if let (Some(player), Some((hull, sheild))) = (world.write_storage::<Player>().get_mut(ship), condition) {
+        player.hull = (player.max_hull as f32 * hull).ceil() as u32;
+        player.sheild = (player.max_sheild as f32 * sheild).ceil() as u32;
+    }
+    Ok(())
+}
+
+//Fits a spare module to the current hull
+pub fn fit_module(world: &mut World, ship: Entity, spare: usize) -> Result<(), String> {
+    let (class, mut modules, spares) = loadout(world, ship)?;
+    let module = spares
+        .get(spare)
+        .cloned()
+        .ok_or_else(|| "No such spare module".to_string())?;
+    modules.push(module);
+    fit_ship(world, ship, &class, modules)?;
+    if let Some(fitting) = world.write_storage::<Fitting>().get_mut(ship) {
+        fitting.spares.remove(spare);
+    }
+    Ok(())
+}
+
+//Takes a fitted module off and stores it with the spares
+pub fn unfit_module(world: &mut World, ship: Entity, index: usize) -> Result<(), String> {
+    let (class, mut modules, _) = loadout(world, ship)?;
+    if index >= modules.len() {
+        return Err("No such fitted module".to_string());
+    }
+    let module = modules.remove(index);
+    fit_ship(world, ship, &class, modules)?;
+    if let Some(fitting) = world.write_storage::<Fitting>().get_mut(ship) {
+        fitting.spares.push(module);
+    }
+    Ok(())
+}
+
+//The ship's hull, fitted modules and spares
+fn loadout(world: &World, ship: Entity) -> Result<(ShipClass, Vec<ModuleDef>, Vec<ModuleDef>), String> {
+    let class = world
+        .read_storage::<Ship>()
+        .get(ship)
+        .map(|ship| ship.class.clone())
+        .ok_or_else(|| "No hull to fit".to_string())?;
+    let fittings = world.read_storage::<Fitting>();
+    let fitting = fittings.get(ship).ok_or_else(|| "No fitting on this ship".to_string())?;
+    Ok((class, fitting.modules.clone(), fitting.spares.clone()))
+}
+
+//Switches to a hull already in the hangar for free, otherwise buys it first
+pub fn buy_ship(world: &mut World, ship: Entity, class: &ShipClass) -> Result<(), String> {
+    let (owned, wealth) = match world.read_storage::<Player>().get(ship) {
+        Some(player) => (player.hangar.contains(&class.name), player.wealth),
+        None => return Err("No pilot to fly it".to_string()),
+    };
+    if !owned && wealth < class.price {
+        return Err(format!("A {} costs {:.0}", class.name, class.price));
+    }
+    refit_ship(world, ship, class)?;
+    if !owned {
+        if let Some(player) = world.write_storage::<Player>().get_mut(ship) {
+            player.wealth -= class.price;
+            player.hangar.push(class.name.clone());
+        }
+    }
+    Ok(())
+}
+
+//Builds a blueprint's module from refined goods, it goes to the spares until fitted at a station
+pub fn craft_module(world: &mut World, ship: Entity, blueprint: &Blueprint) -> Result<(), String> {
+    let module = world
+        .entry::<ModuleConfig>()
+        .or_insert_with(ModuleConfig::default)
+        .module(&blueprint.module)
+        .cloned()
+        .ok_or_else(|| format!("Unknown module {}", blueprint.module))?;
+    let paid = match world.write_storage::<Cargo>().get_mut(ship) {
+        Some(cargo) => cargo.take_goods(&blueprint.cost),
+        None => false,
+    };
+    if !paid {
+        return Err(format!("Not enough goods for {}", blueprint.name));
+    }
+    if let Some(fitting) = world.write_storage::<Fitting>().get_mut(ship) {
+        fitting.spares.push(module);
+    }
+    Ok(())
+}
+
+//Sells all of one ore in the hold to the market of the station the ship is docked at.
+//Returns the amount sold and the wealth it brought in.
+pub fn sell_ore(world: &mut World, ship: Entity, ore: Ore) -> Result<(u32, f32), String> {
+    let station = world
+        .read_storage::<Player>()
+        .get(ship)
+        .and_then(|player| player.docked.clone())
+        .ok_or_else(|| "Not docked at a station".to_string())?;
+    let amount = world.read_storage::<Cargo>().get(ship).map_or(0, |cargo| cargo.amount(ore));
+    if amount == 0 {
+        return Err(format!("No {:?} in the hold", ore));
+    }
+    let config = world.entry::<MarketConfig>().or_insert_with(MarketConfig::default).clone();
+    let factions = world.entry::<FactionConfig>().or_insert_with(FactionConfig::default).clone();
+    let paid = {
+        let stations = world.read_storage::<Station>();
+        let mut markets = world.write_storage::<Market>();
+        let market = (&stations, &mut markets)
+            .join()
+            .find(|(found, _)| found.name == station)
+            .map(|(_, market)| market)
+            .ok_or_else(|| format!("{} has no market", station))?;
+        market.sell(ore, amount, &config)
+    };
+    // Standing with the station authority moves the price, and the guild likes to see ore sold
+    let paid = match world.write_storage::<Reputation>().get_mut(ship) {
+        Some(reputation) => {
+            let paid = paid * reputation.price_factor(&factions);
+            reputation.change(Faction::MinersGuild, amount as f32 * factions.sale_bonus);
+            paid
+        }
+        None => paid,
+    };
+    if let Some(cargo) = world.write_storage::<Cargo>().get_mut(ship) {
+        cargo.take_ores(&[(ore, amount)]);
+    }
+    if let Some(player) = world.write_storage::<Player>().get_mut(ship) {
+        player.wealth += paid;
+    }
+    Ok((amount, paid))
+}
+
+//Starts a new galaxy in its home sector
+pub fn init_sector(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, galaxy_seed: u64) {
+    // Nothing carries over from an earlier game
+    resume_galaxy(world, spritesheet_handle, Galaxy::new(galaxy_seed), SectorHistory::default());
+}
+
+//Picks a galaxy up with the sectors already visited in it and enters the galaxy's sector
+pub fn resume_galaxy(
+    world: &mut World,
+    spritesheet_handle: Handle<SpriteSheet>,
+    galaxy: Galaxy,
+    history: SectorHistory,
+) {
+    let sector = galaxy.sector;
+    world.insert(Galaxy::new(galaxy.seed));
+    world.insert(history);
+    world.remove::<SectorRegen>();
+    enter_sector(world, spritesheet_handle, sector);
+}
+
+//Clears out the current sector and lays out the one at these coordinates.
+//The player arrives back at the starting point. Sectors visited before come back
+//as they were left, plus whatever grew back while the player was away.
+pub fn enter_sector(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, sector: (i32, i32)) {
+    let (left, seed) = {
+        let mut galaxy = world.entry::<Galaxy>().or_insert_with(Galaxy::default);
+        let left = galaxy.sector;
+        galaxy.sector = sector;
+        (left, galaxy.sector_seed(sector))
+    };
+    leave_sector(world, left);
+
+    let leftovers = {
+        let entities = world.entities();
+        let astroids = world.read_storage::<Astroid>();
+        let pickups = world.read_storage::<OrePickup>();
+        let stations = world.read_storage::<Station>();
+        let pulses = world.read_storage::<ScanPulse>();
+        let npcs = world.read_storage::<Npc>();
+        let mut leftovers = (&entities, &astroids).join().map(|(entity, _)| entity).collect::<Vec<Entity>>();
+        leftovers.extend((&entities, &pickups).join().map(|(entity, _)| entity));
+        leftovers.extend((&entities, &stations).join().map(|(entity, _)| entity));
+        leftovers.extend((&entities, &pulses).join().map(|(entity, _)| entity));
+        leftovers.extend((&entities, &npcs).join().map(|(entity, _)| entity));
+        leftovers
+    };
+    if let Err(err) = world.delete_entities(&leftovers) {
+        log::warn!("Could not clear the old sector: {}", err);
+    }
+
+    for (player, transform, velocity, collider) in (
+        &mut world.write_storage::<Player>(),
+        &mut world.write_storage::<Transform>(),
+        (&mut world.write_storage::<Velocity>()).maybe(),
+        (&mut world.write_storage::<Collider>()).maybe(),
+    )
+        .join()
+    {
+        player.target = None;
+        transform.set_translation_x(CAMERA_WIDTH * 0.5);
+        transform.set_translation_y(CAMERA_HEIGHT * 0.5);
+        if let Some(velocity) = velocity {
+            *velocity = Velocity::default();
+        }
+        // Arriving isn't moving, or the jump would hit like a collision
+        if let Some(collider) = collider {
+            collider.last_position = None;
+        }
+    }
+    if let Some(mut fog) = world.try_fetch_mut::<FogOfWar>() {
+        fog.explored.clear();
+    }
+
+    if !return_to_sector(world, spritesheet_handle.clone(), seed, sector) {
+        init_sector_astroids(world, spritesheet_handle.clone(), seed);
+        let config = world.entry::<RegenConfig>().or_insert_with(RegenConfig::default).clone();
+        world.insert(SectorRegen::new(seed, &config));
+    }
+    init_sector_station(world, spritesheet_handle.clone(), seed, sector);
+    init_sector_npcs(world, spritesheet_handle, seed);
+
+    let config = world.entry::<EventConfig>().or_insert_with(EventConfig::default).clone();
+    world.insert(SectorEvents::new(config.danger(sector), seed, &config));
+}
+
+//Remembers the astroids left in a sector and how far along its regrowth is
+fn leave_sector(world: &mut World, sector: (i32, i32)) {
+    let regen = match world.remove::<SectorRegen>() {
+        Some(regen) => regen,
+        None => return,
+    };
+    world.register::<Comet>();
+    world.register::<Meteor>();
+    let astroids = sector_astroids(world);
+    let mut history = world.entry::<SectorHistory>().or_insert_with(SectorHistory::default);
+    let left_at = history.clock;
+    history.sectors.insert(sector, SectorRecord { regen, astroids, left_at });
+}
+
+//The astroids that stay in a sector, comets and meteor showers are only passing through
+pub fn sector_astroids(world: &World) -> Vec<(String, Astroid, (f32, f32))> {
+    (
+        &world.read_storage::<Astroid>(),
+        &world.read_storage::<Named>(),
+        &world.read_storage::<Transform>(),
+        !&world.read_storage::<Comet>(),
+        !&world.read_storage::<Meteor>(),
+    )
+        .join()
+        .map(|(astroid, name, transform, _, _)| {
+            let position = (transform.translation().x, transform.translation().y);
+            (name.name.to_string(), astroid.clone(), position)
+        })
+        .collect()
+}
+
+//Puts back a sector visited before and catches its regrowth up on the time away.
+//Returns false for a sector the player has never been to.
+fn return_to_sector(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, seed: u64, sector: (i32, i32)) -> bool {
+    let (record, clock) = {
+        let mut history = world.entry::<SectorHistory>().or_insert_with(SectorHistory::default);
+        let clock = history.clock;
+        match history.sectors.remove(&sector) {
+            Some(record) => (record, clock),
+            None => return false,
+        }
+    };
+    world.insert(SectorSeed(seed));
+    let count = record.astroids.len();
+    for (name, astroid, position) in record.astroids {
+        spawn_named_astroid(world, spritesheet_handle.clone(), astroid, position.0, position.1, name);
+    }
+
+    let config = world.entry::<RegenConfig>().or_insert_with(RegenConfig::default).clone();
+    let mut regen = record.regen;
+    let arrival = (CAMERA_WIDTH * 0.5, CAMERA_HEIGHT * 0.5);
+    regen::advance(&mut regen, &config, count, clock - record.left_at, arrival);
+    world.insert(regen);
+    spawn_regrown_astroids(world, spritesheet_handle);
+    true
+}
+
+//Spawns the astroids a sector has grown back, once the dispatcher has run
+pub fn spawn_regrown_astroids(world: &mut World, spritesheet_handle: Handle<SpriteSheet>) {
+    let regrown = match world.try_fetch_mut::<SectorRegen>() {
+        Some(mut regen) => std::mem::take(&mut regen.pending),
+        None => return,
+    };
+    for (name, astroid, position) in regrown {
+        spawn_named_astroid(world, spritesheet_handle.clone(), astroid, position.0, position.1, name);
+    }
+}
+
+//Spawns whatever the sector event director has planned, once the dispatcher has run
+pub fn spawn_sector_events(world: &mut World, spritesheet_handle: Handle<SpriteSheet>) {
+    let (pending, first) = match world.try_fetch_mut::<SectorEvents>() {
+        Some(mut events) => {
+            let pending = std::mem::take(&mut events.pending);
+            let first = events.spawned;
+            events.spawned += pending.len() as u32;
+            (pending, first)
+        }
+        None => return,
+    };
+    if pending.is_empty() {
+        return;
+    }
+    world.register::<Comet>();
+    world.register::<Meteor>();
+    let raider = world
+        .entry::<NpcConfig>()
+        .or_insert_with(NpcConfig::default)
+        .archetype(NpcRole::Pirate)
+        .cloned();
+
+    for (index, spawn) in pending.into_iter().enumerate() {
+        let number = first + index as u32;
+        match spawn {
+            EventSpawn::Meteor { astroid, position, velocity } => {
+                let name = format!("meteor {}", number);
+                let meteor = spawn_named_astroid(world, spritesheet_handle.clone(), astroid, position.0, position.1, name);
+                world.write_storage::<Velocity>().insert(meteor, velocity).ok();
+                world.write_storage::<Meteor>().insert(meteor, Meteor).ok();
+            }
+            EventSpawn::Comet { astroid, position, velocity, lifetime } => {
+                let name = format!("comet {}", number);
+                let comet = spawn_named_astroid(world, spritesheet_handle.clone(), astroid, position.0, position.1, name);
+                world.write_storage::<Velocity>().insert(comet, velocity).ok();
+                world.write_storage::<Comet>().insert(comet, Comet { velocity, time_left: lifetime }).ok();
+            }
+            EventSpawn::Raider { position } => {
+                if let Some(archetype) = raider.clone() {
+                    let name = format!("{} raider {}", archetype.name, number);
+                    spawn_npc(world, spritesheet_handle.clone(), archetype, name, position.0, position.1, number as u64);
+                }
+            }
+        }
+    }
 }
 
 //Astroid Initialization
-fn init_astroids(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, i: u32) {
-    let mut rng = rand::thread_rng();
-    let x_pos = rng.gen_range(-500.0, 500.0);
-    let y_pos = rng.gen_range(-350.0, 350.0);
+//The same seed always lays the sector out the same way
+pub fn init_sector_astroids(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, seed: u64) {
+    world.insert(SectorSeed(seed));
+    let mut rng = StdRng::seed_from_u64(seed);
+    for i in 0..SECTOR_ASTROIDS {
+        let x_pos = rng.gen_range(-ASTROID_FIELD.0, ASTROID_FIELD.0);
+        let y_pos = rng.gen_range(-ASTROID_FIELD.1, ASTROID_FIELD.1);
+        let astroid = Astroid::random(&mut rng);
+        spawn_astroid(world, spritesheet_handle.clone(), astroid, x_pos, y_pos, i);
+    }
+}
+
+//The home sector always has a station, elsewhere it's a coin toss
+pub fn init_sector_station(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, seed: u64, sector: (i32, i32)) {
+    // A separate generator so the astroid layout doesn't depend on the station
+    let mut rng = StdRng::seed_from_u64(seed ^ 0x5747_1104);
+    if sector != (0, 0) && rng.gen_bool(0.5) {
+        return;
+    }
+    let angle = rng.gen_range(0.0, 2.0 * PI);
+    // Outside the astroid field so nothing starts out overlapping it
+    let distance = rng.gen_range(1000.0, 1200.0);
+    let name = format!("Station {}:{}", sector.0, sector.1);
+    spawn_station(world, spritesheet_handle, name, angle.cos() * distance, angle.sin() * distance);
+}
+
+pub fn spawn_station(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, name: String, x_pos: f32, y_pos: f32) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(x_pos, y_pos, 0.0);
+    transform.set_scale(Vector3::new(STATION_SCALE, STATION_SCALE, 1.0));
+    let shape = Shape::sprite_circle(STATION_SPRITE_SIZE.0, STATION_SPRITE_SIZE.1);
+    let market = Market::new(&world.entry::<MarketConfig>().or_insert_with(MarketConfig::default));
+    let board = init_contract_board(world, &name);
+
+    // No Velocity, nothing can push a station around
+    world
+        .create_entity()
+        .with(Station::new(name.clone()))
+        .with(market)
+        .with(board)
+        .with(Collider::new(shape, 0.0))
+        .with(RadarContact::new(ContactKind::Station))
+        .with(transform)
+        .with(SpriteRender::new(spritesheet_handle, STATION_SPRITE))
+        .named(name)
+        .build()
+}
+
+//Work on offer at a station. The board is rolled from the galaxy seed, so the same
+//visit always offers the same work, and rolls again once the player has taken some on.
+pub fn init_contract_board(world: &mut World, station: &str) -> ContractBoard {
+    world.register::<QuestLog>();
+    let galaxy = *world.entry::<Galaxy>().or_insert_with(Galaxy::default);
+    let taken = world
+        .read_storage::<QuestLog>()
+        .join()
+        .map(|log| log.contracts.len())
+        .next()
+        .unwrap_or(0);
+    let mut hasher = DefaultHasher::new();
+    (galaxy.seed, station, taken).hash(&mut hasher);
+    let mut rng = StdRng::seed_from_u64(hasher.finish());
+
+    let config = world.entry::<ContractConfig>().or_insert_with(ContractConfig::default).clone();
+    let market = world.entry::<MarketConfig>().or_insert_with(MarketConfig::default).clone();
+    let npcs = world.entry::<NpcConfig>().or_insert_with(NpcConfig::default).clone();
+    let factions = world.entry::<FactionConfig>().or_insert_with(FactionConfig::default).clone();
+    ContractBoard {
+        offers: generate_contracts(&mut rng, station, galaxy.sector, &config, &market, &npcs, &factions),
+    }
+}
+
+//Takes on a contract from the board of the station the ship is docked at
+pub fn accept_contract(world: &mut World, ship: Entity, offer: usize) -> Result<Contract, String> {
+    let station = world
+        .read_storage::<Player>()
+        .get(ship)
+        .and_then(|player| player.docked.clone())
+        .ok_or_else(|| "Not docked at a station".to_string())?;
+    let config = world.entry::<ContractConfig>().or_insert_with(ContractConfig::default).clone();
+    let active = world
+        .read_storage::<QuestLog>()
+        .get(ship)
+        .map(|log| log.active().count())
+        .ok_or_else(|| "No quest log".to_string())?;
+    if active >= config.max_active {
+        return Err(format!("Already working {} contracts", active));
+    }
 
+    let contract = {
+        let stations = world.read_storage::<Station>();
+        let mut boards = world.write_storage::<ContractBoard>();
+        let board = (&stations, &mut boards)
+            .join()
+            .find(|(found, _)| found.name == station)
+            .map(|(_, board)| board)
+            .ok_or_else(|| format!("{} has no contracts", station))?;
+        if offer >= board.offers.len() {
+            return Err("No such contract".to_string());
+        }
+        board.offers.remove(offer)
+    };
+    if let Some(log) = world.write_storage::<QuestLog>().get_mut(ship) {
+        log.contracts.push(contract.clone());
+    }
+    Ok(contract)
+}
+
+//A handful of miners, traders and pirates spread around the sector
+pub fn init_sector_npcs(world: &mut World, spritesheet_handle: Handle<SpriteSheet>, seed: u64) {
+    let config = world.entry::<NpcConfig>().or_insert_with(NpcConfig::default).clone();
+    let mut rng = StdRng::seed_from_u64(seed ^ 0x4e50_4353);
+    let mut index = 0;
+    for archetype in config.archetypes.iter() {
+        let (min, max) = archetype.count;
+        let count = if max > min { rng.gen_range(min, max + 1) } else { min };
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0, 2.0 * PI);
+            let distance = rng.gen_range(300.0, 900.0);
+            let name = format!("{} {}", archetype.name, index);
+            spawn_npc(
+                world,
+                spritesheet_handle.clone(),
+                archetype.clone(),
+                name,
+                angle.cos() * distance,
+                angle.sin() * distance,
+                rng.gen(),
+            );
+            index += 1;
+        }
+    }
+}
+
+//NPC ships reuse the player's sprite at the archetype's scale
+pub fn spawn_npc(
+    world: &mut World,
+    spritesheet_handle: Handle<SpriteSheet>,
+    archetype: NpcArchetype,
+    name: String,
+    x_pos: f32,
+    y_pos: f32,
+    seed: u64,
+) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(x_pos, y_pos, 0.05);
+    transform.set_scale(Vector3::new(archetype.scale, archetype.scale, 1.0));
+    let contact = RadarContact::new(archetype.role.contact_kind());
+    let cargo = Cargo::new(archetype.cargo);
+    let mut npc = Npc::new(archetype, seed);
+    npc.waypoint = (x_pos, y_pos);
+
+    world
+        .create_entity()
+        .with(npc)
+        .with(cargo)
+        .with(Collider::new(Shape::sprite_box(SHIP_SPRITE_SIZE.0, SHIP_SPRITE_SIZE.1), NPC_MASS))
+        .with(Velocity::default())
+        .with(contact)
+        .with(transform)
+        .with(SpriteRender::new(spritesheet_handle, 0))
+        .named(name)
+        .build()
+}
+
+pub fn spawn_astroid(
+    world: &mut World,
+    spritesheet_handle: Handle<SpriteSheet>,
+    astroid: Astroid,
+    x_pos: f32,
+    y_pos: f32,
+    i: u32,
+) -> Entity {
+    spawn_named_astroid(world, spritesheet_handle, astroid, x_pos, y_pos, format!("astroid {}", i))
+}
+
+//Astroids of any size, scaled to match
+pub fn spawn_named_astroid(
+    world: &mut World,
+    spritesheet_handle: Handle<SpriteSheet>,
+    astroid: Astroid,
+    x_pos: f32,
+    y_pos: f32,
+    name: String,
+) -> Entity {
+    let scale = world.entry::<AstroidConfig>().or_insert_with(AstroidConfig::default).size(astroid.size).scale;
     let mut transform = Transform::default();
     transform.set_translation_xyz(x_pos, y_pos, 0.0);
+    transform.set_scale(Vector3::new(scale, scale, 1.0));
 
-    let sprite = SpriteRender::new(spritesheet_handle, 1);
+    let sprite = SpriteRender::new(spritesheet_handle, astroid.sprite());
+    let collider = Collider::astroid(astroid.size);
 
     world
         .create_entity()
-        .with(Astroid::new(Ore::Ice))
+        .with(astroid)
+        .with(collider)
+        .with(Velocity::default())
+        .with(RadarContact::new(ContactKind::Asteroid))
         .with(transform)
         .with(sprite)
-        .named(format!("astroid {}", i))
-        .build();
+        .named(name)
+        .build()
 }
 
 //Background Sprites
-fn init_background_sprites(world: &mut World, sprite_sheet: Handle<SpriteSheet>, parent: Entity) {
-    let mut b1_transform = Transform::default();
-    b1_transform.set_translation_xyz(CAMERA_WIDTH * 0.5, CAMERA_HEIGHT * 0.5, -10.0);
-    let b1_scale = Vector3::new(0.5, 0.5, 1.0);
-    b1_transform.set_scale(b1_scale);
-    let b1_sprite = SpriteRender::new(sprite_sheet.clone(), 0);
-    world
+fn init_background_sprites(
+    world: &mut World,
+    background_sheet: Handle<SpriteSheet>,
+    star_sheet: Handle<SpriteSheet>,
+) {
+    let config = world.read_resource::<BackgroundConfig>().clone();
+
+    // Each layer is a 3x3 grid of tiles that wraps around the camera.
+    for (layer_index, layer) in config.layers.iter().enumerate() {
+        let tile_width = layer.tile_width * layer.scale;
+        let tile_height = layer.tile_height * layer.scale;
+        for i in -1..=1 {
+            for j in -1..=1 {
+                let mut transform = Transform::default();
+                transform.set_translation_xyz(0.0, 0.0, layer.z);
+                transform.set_scale(Vector3::new(layer.scale, layer.scale, 1.0));
+                let sprite = SpriteRender::new(background_sheet.clone(), layer.sprite);
+                world
+                    .create_entity()
+                    .with(Background::new(
+                        layer.depth,
+                        (i as f32 * tile_width, j as f32 * tile_height),
+                        (tile_width * 3.0, tile_height * 3.0),
+                    ))
+                    .with(transform)
+                    .with(sprite)
+                    .named(format!("background_{:02}_{}_{}", layer_index, i + 1, j + 1))
+                    .with(Transparent)
+                    .build();
+            }
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    for starfield in config.starfields.iter() {
+        for _ in 0..starfield.count {
+            let offset = (
+                rng.gen_range(0.0, starfield.field_width),
+                rng.gen_range(0.0, starfield.field_height),
+            );
+            let star_scale = rng.gen_range(0.5, 1.5);
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(0.0, 0.0, starfield.z);
+            transform.set_scale(Vector3::new(star_scale, star_scale, 1.0));
+            let sprite = SpriteRender::new(star_sheet.clone(), 5);
+            world
+                .create_entity()
+                .with(Background::new(
+                    starfield.depth,
+                    offset,
+                    (starfield.field_width, starfield.field_height),
+                ))
+                .with(transform)
+                .with(sprite)
+                .with(Transparent)
+                .build();
+        }
+    }
+}
+
+//Minimap panel in the top right corner, blips are added by the RadarSystem
+fn init_minimap(world: &mut World) {
+    let (size, cell_size) = {
+        let config = world.read_resource::<RadarConfig>();
+        (config.size, config.cell_size)
+    };
+
+    let panel = world
         .create_entity()
-        .with(Background::new(0.0))
-        .with(b1_transform)
-        .with(b1_sprite)
-        .with(Parent {
-            entity: parent.clone(),
-        })
-        .named("background_01")
-        .with(Transparent)
+        .with(UiTransform::new(
+            "minimap".to_string(),
+            Anchor::TopRight,
+            Anchor::TopRight,
+            -10.0,
+            -10.0,
+            0.0,
+            size,
+            size,
+        ))
+        .with(UiImage::SolidColor([0.0, 0.05, 0.1, 0.75]))
         .build();
 
-    let mut b2_transform = Transform::default();
-    b2_transform.set_translation_xyz(CAMERA_WIDTH * 0.5, CAMERA_HEIGHT * 0.5, -9.0);
-    let b2_scale = Vector3::new(1.0, 1.0, 1.0);
-    b2_transform.set_scale(b2_scale);
-    let b2_sprite = SpriteRender::new(sprite_sheet.clone(), 1);
+    world.insert(Minimap { panel });
+    world.insert(FogOfWar::new(cell_size));
+}
+
+//Hud with status bars top left and the target panel bottom left
+fn init_hud(world: &mut World) {
+    let font = {
+        let loader = world.read_resource::<Loader>();
+        let font_storage = world.read_resource::<AssetStorage<FontAsset>>();
+        get_default_font(&loader, &font_storage)
+    };
+
+    init_hud_label(world, &font, "hull_label", Anchor::TopLeft, 10.0, -10.0, "Hull");
+    let hull_bar = init_hud_bar(world, "hull", 80.0, -10.0, [0.8, 0.2, 0.2, 1.0]);
+    init_hud_label(world, &font, "shield_label", Anchor::TopLeft, 10.0, -30.0, "Shield");
+    let shield_bar = init_hud_bar(world, "shield", 80.0, -30.0, [0.2, 0.5, 1.0, 1.0]);
+    init_hud_label(world, &font, "energy_label", Anchor::TopLeft, 10.0, -50.0, "Energy");
+    let energy_bar = init_hud_bar(world, "energy", 80.0, -50.0, [1.0, 0.9, 0.3, 1.0]);
+    let heat_text = init_hud_label(world, &font, "heat_label", Anchor::TopLeft, 10.0, -70.0, "Heat");
+    let heat_bar = init_hud_bar(world, "heat", 80.0, -70.0, [1.0, 0.45, 0.1, 1.0]);
+
+    let fuel_text = init_hud_label(world, &font, "fuel_label", Anchor::TopLeft, 10.0, -90.0, "Fuel");
+    let fuel_bar = init_hud_bar(world, "fuel", 80.0, -90.0, [0.4, 0.9, 0.6, 1.0]);
+
+    let cargo_text = init_hud_label(world, &font, "cargo_label", Anchor::TopLeft, 10.0, -115.0, "Cargo");
+    let mut cargo_bars = Vec::new();
+    for (i, ore) in Ore::ALL.iter().enumerate() {
+        let y = -135.0 - i as f32 * 18.0;
+        init_hud_label(world, &font, "ore_label", Anchor::TopLeft, 10.0, y, &format!("{:?}", ore));
+        cargo_bars.push((*ore, init_hud_bar(world, "cargo", 80.0, y, ore.radar_color())));
+    }
+
+    let wealth_text = init_hud_label(world, &font, "wealth", Anchor::TopLeft, 10.0, -255.0, "");
+    let goods_text = init_hud_label(world, &font, "goods", Anchor::TopLeft, 10.0, -275.0, "");
+    let refinery_text = init_hud_label(world, &font, "refinery", Anchor::TopLeft, 10.0, -295.0, "");
+    let distress_text = init_hud_label(world, &font, "distress", Anchor::TopLeft, 10.0, -315.0, "");
+    let standing_text = init_hud_label(world, &font, "standing", Anchor::TopLeft, 10.0, -335.0, "");
+    let contracts_text = init_hud_label(world, &font, "contracts", Anchor::TopLeft, 10.0, -355.0, "");
+    let target_text = init_hud_label(world, &font, "target", Anchor::BottomLeft, 10.0, 70.0, "");
+    let message_text = init_hud_label(world, &font, "message", Anchor::TopMiddle, 0.0, -20.0, "");
+
+    world.insert(Hud {
+        hull_bar,
+        shield_bar,
+        energy_bar,
+        heat_bar,
+        heat_text,
+        fuel_bar,
+        fuel_text,
+        distress_text,
+        cargo_bars,
+        cargo_text,
+        wealth_text,
+        goods_text,
+        refinery_text,
+        standing_text,
+        contracts_text,
+        target_text,
+        message_text,
+    });
+}
+
+fn init_hud_label(
+    world: &mut World,
+    font: &FontHandle,
+    id: &str,
+    anchor: Anchor,
+    x: f32,
+    y: f32,
+    text: &str,
+) -> Entity {
     world
         .create_entity()
-        .with(Background::new(-0.2))
-        .with(b2_transform)
-        .with(b2_sprite)
-        .with(Parent {
-            entity: parent.clone(),
-        })
-        .named("background_02")
-        .with(Transparent)
+        .with(UiTransform::new(id.to_string(), anchor, anchor, x, y, 1.0, 300.0, 60.0))
+        .with(UiText::new(
+            font.clone(),
+            text.to_string(),
+            [1.0, 1.0, 1.0, 1.0],
+            14.0,
+            LineMode::Wrap,
+            Anchor::TopLeft,
+        ))
+        .build()
+}
+
+//Creates a bar backing and returns the fill entity, whose width is set by the HudSystem
+fn init_hud_bar(world: &mut World, id: &str, x: f32, y: f32, color: [f32; 4]) -> Entity {
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            format!("{}_back", id),
+            Anchor::TopLeft,
+            Anchor::TopLeft,
+            x,
+            y,
+            1.0,
+            HUD_BAR_WIDTH,
+            HUD_BAR_HEIGHT,
+        ))
+        .with(UiImage::SolidColor([0.1, 0.1, 0.1, 0.8]))
         .build();
+
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            format!("{}_fill", id),
+            Anchor::TopLeft,
+            Anchor::TopLeft,
+            x,
+            y,
+            2.0,
+            HUD_BAR_WIDTH,
+            HUD_BAR_HEIGHT,
+        ))
+        .with(UiImage::SolidColor(color))
+        .build()
 }
 
 fn load_background_sprite(world: &mut World) -> Handle<SpriteSheet> {
@@ -221,73 +1164,382 @@ pub fn init_mining_ray(world: &mut World, parent: Entity) -> Entity {
 }
 
 //Mining Tools
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum MiningTool {
     Laser,
     MiningLaser,
     ParticleDisruptor,
     MatterDisintegrator,
 }
-#[derive(PartialEq, Eq)]
+
+impl MiningTool {
+    //Better tools have higher tiers
+    pub fn tier(&self) -> u32 {
+        match self {
+            MiningTool::Laser => 1,
+            MiningTool::MiningLaser => 2,
+            MiningTool::ParticleDisruptor => 3,
+            MiningTool::MatterDisintegrator => 4,
+        }
+    }
+
+    //Fraction of an ore in the rock the tool recovers, the rest is lost.
+    //Better tools are needed to get anything out of the rare ores.
+    pub fn extraction(&self, ore: Ore) -> f32 {
+        let (ice, copper, iron, gold, diamond, plasma) = match self {
+            MiningTool::Laser => (1.0, 0.8, 0.8, 0.4, 0.2, 0.0),
+            MiningTool::MiningLaser => (1.0, 1.0, 1.0, 0.7, 0.4, 0.1),
+            MiningTool::ParticleDisruptor => (1.0, 1.0, 1.0, 0.9, 0.7, 0.4),
+            MiningTool::MatterDisintegrator => (1.0, 1.0, 1.0, 1.0, 0.9, 0.8),
+        };
+        match ore {
+            Ore::Ice => ice,
+            Ore::Copper => copper,
+            Ore::Iron => iron,
+            Ore::Gold => gold,
+            Ore::Diamond => diamond,
+            Ore::Plasma => plasma,
+        }
+    }
+
+    //Energy used per second while the beam is on
+    pub fn energy_draw(&self) -> f32 {
+        match self {
+            MiningTool::Laser => 5.0,
+            MiningTool::MiningLaser => 8.0,
+            MiningTool::ParticleDisruptor => 12.0,
+            MiningTool::MatterDisintegrator => 18.0,
+        }
+    }
+
+    //Heat built up per second while the beam is on
+    pub fn heat_rate(&self) -> f32 {
+        match self {
+            MiningTool::Laser => 6.0,
+            MiningTool::MiningLaser => 10.0,
+            MiningTool::ParticleDisruptor => 15.0,
+            MiningTool::MatterDisintegrator => 22.0,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Weapon {
     Laser,
     PlasmaLauncher,
     VoidBeam,
 }
 
+impl Weapon {
+    //Better weapons have higher tiers
+    pub fn tier(&self) -> u32 {
+        match self {
+            Weapon::Laser => 1,
+            Weapon::PlasmaLauncher => 2,
+            Weapon::VoidBeam => 3,
+        }
+    }
+
+    //Energy used per second while firing
+    pub fn energy_draw(&self) -> f32 {
+        match self {
+            Weapon::Laser => 10.0,
+            Weapon::PlasmaLauncher => 15.0,
+            Weapon::VoidBeam => 25.0,
+        }
+    }
+
+    //Damage per second to a target in range
+    pub fn damage(&self) -> f32 {
+        match self {
+            Weapon::Laser => 10.0,
+            Weapon::PlasmaLauncher => 18.0,
+            Weapon::VoidBeam => 30.0,
+        }
+    }
+
+    pub fn range(&self) -> f32 {
+        match self {
+            Weapon::Laser => 300.0,
+            Weapon::PlasmaLauncher => 350.0,
+            Weapon::VoidBeam => 400.0,
+        }
+    }
+}
+
 
 
 
 //Background Component
+//depth 0.0 stays fixed in the world, 1.0 follows the camera exactly.
+//offset is the position within the layer, period is the size it repeats at.
 pub struct Background {
-    pub movement_speed: f32,
+    pub depth: f32,
+    pub offset: (f32, f32),
+    pub period: (f32, f32),
 }
 
 impl Background {
-    pub fn new(speed: f32) -> Background {
+    pub fn new(depth: f32, offset: (f32, f32), period: (f32, f32)) -> Background {
         Background {
-            movement_speed: speed,
+            depth,
+            offset,
+            period,
         }
     }
+
+    //World position of this element, wrapped to the copy nearest the camera
+    pub fn position_for(&self, camera_x: f32, camera_y: f32) -> (f32, f32) {
+        let rel_x = wrap(self.offset.0 - camera_x * (1.0 - self.depth), self.period.0);
+        let rel_y = wrap(self.offset.1 - camera_y * (1.0 - self.depth), self.period.1);
+        (camera_x + rel_x, camera_y + rel_y)
+    }
+}
+
+//Wraps a value into [-period / 2, period / 2)
+fn wrap(value: f32, period: f32) -> f32 {
+    value - period * (value / period + 0.5).floor()
 }
 
 impl Component for Background {
     type Storage = DenseVecStorage<Self>;
 }
 
-//Player Component
-pub struct Player {
-    pub hull: u32,
-    pub sheild: u32,
-    pub mining_tool: MiningTool,
-    pub weapon: Weapon,
-    pub speed: f32,
-    pub wealth: f32,
-    pub position: (f32, f32),
-    pub zoom: f32,
-    pub target: Option<String>,
+//Ship component, the hull the player is flying
+pub struct Ship {
+    pub class: ShipClass,
 }
 
-impl Player {
+impl Ship {
+    pub fn new(class: ShipClass) -> Ship {
+        Ship { class }
+    }
+}
+
+impl Component for Ship {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Fitting component, the modules on the ship and the spares carried for later
+#[derive(Default)]
+pub struct Fitting {
+    pub modules: Vec<ModuleDef>,
+    pub spares: Vec<ModuleDef>,
+}
+
+impl Fitting {
+    pub fn mining_tool(&self) -> Option<MiningTool> {
+        fitting::mining_tool(&self.modules)
+    }
+
+    pub fn weapon(&self) -> Option<Weapon> {
+        fitting::weapon(&self.modules)
+    }
+}
+
+impl Component for Fitting {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Fuel component, burnt by thrust and sector jumps
+pub struct Fuel {
+    pub amount: f32,
+    pub capacity: f32,
+    //Seconds until help arrives after a distress call
+    pub distress: Option<f32>,
+}
+
+impl Fuel {
+    pub fn new(capacity: f32) -> Fuel {
+        Fuel {
+            amount: capacity,
+            capacity,
+            distress: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amount <= 0.0
+    }
+
+    //Burns the fuel only if there is enough of it
+    pub fn burn(&mut self, amount: f32) -> bool {
+        if self.amount < amount {
+            return false;
+        }
+        self.amount -= amount;
+        true
+    }
+
+    //Fills up as far as the tank allows and returns the amount added
+    pub fn refill(&mut self, amount: f32) -> f32 {
+        let added = amount.min(self.capacity - self.amount).max(0.0);
+        self.amount += added;
+        added
+    }
+
+    //Buys as much as the wealth covers at the given price per unit
+    pub fn buy(&mut self, wealth: &mut f32, price: f32) -> f32 {
+        let affordable = if price > 0.0 { *wealth / price } else { self.capacity };
+        let bought = self.refill(affordable);
+        *wealth -= bought * price;
+        bought
+    }
+}
+
+impl Component for Fuel {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Player Component
+pub struct Player {
+    pub hull: u32,
+    pub sheild: u32,
+    pub max_hull: u32,
+    pub max_sheild: u32,
+    pub speed: f32,
+    pub wealth: f32,
+    pub position: (f32, f32),
+    pub zoom: f32,
+    pub target: Option<String>,
+    //Name of the station the ship is docked at
+    pub docked: Option<String>,
+    //Hulls the player owns and can switch between at a shipyard
+    pub hangar: Vec<String>,
+}
+
+impl Player {
     pub fn new() -> Player {
+        Player::from_class(&ShipClass::default())
+    }
+
+    pub fn from_class(class: &ShipClass) -> Player {
         Player {
-            hull: 100,
-            sheild: 100,
-            mining_tool: MiningTool::Laser,
-            weapon: Weapon::Laser,
-            speed: 1.0,
+            hull: class.hull,
+            sheild: class.shield,
+            max_hull: class.hull,
+            max_sheild: class.shield,
+            speed: class.speed,
             wealth: 0.0,
             position: (0.0, 0.0),
             zoom: 2.0,
             target: None,
+            docked: None,
+            hangar: vec![class.name.clone()],
         }
     }
+
+    //Shields soak up damage before the hull does
+    pub fn take_damage(&mut self, amount: u32) {
+        let absorbed = amount.min(self.sheild);
+        self.sheild -= absorbed;
+        self.hull = self.hull.saturating_sub(amount - absorbed);
+    }
 }
 
 impl Component for Player {
     type Storage = DenseVecStorage<Self>;
 }
 
+//Ship energy, drawn by modules and recharged over time
+pub struct Capacitor {
+    pub charge: f32,
+    pub capacity: f32,
+    //Energy regained per second
+    pub recharge: f32,
+}
+
+impl Capacitor {
+    pub fn new(capacity: f32, recharge: f32) -> Capacitor {
+        Capacitor {
+            charge: capacity,
+            capacity,
+            recharge,
+        }
+    }
+
+    //Takes the energy if there is enough of it
+    pub fn draw(&mut self, amount: f32) -> bool {
+        if self.charge < amount {
+            return false;
+        }
+        self.charge -= amount;
+        true
+    }
+}
+
+impl Component for Capacitor {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Heat from running modules, an overheated ship can't mine until it cools down
+pub struct Heat {
+    pub heat: f32,
+    pub capacity: f32,
+    pub overheated: bool,
+}
+
+impl Heat {
+    pub fn new(capacity: f32) -> Heat {
+        Heat {
+            heat: 0.0,
+            capacity,
+            overheated: false,
+        }
+    }
+}
+
+impl Component for Heat {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Which modules got the power they asked for this tick
+#[derive(Default)]
+pub struct PowerGrid {
+    pub thrust: bool,
+    pub mining: bool,
+    pub weapon: bool,
+    //Shield regenerated so far towards the next whole point
+    pub shield_progress: f32,
+    //Weapon damage dealt so far towards the next whole point
+    pub weapon_progress: f32,
+}
+
+impl Component for PowerGrid {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Prospecting scanner fitted to a ship
+#[derive(Default)]
+pub struct Scanner {
+    //Seconds until the next pulse can be sent
+    pub cooldown: f32,
+}
+
+impl Component for Scanner {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Expanding ring that reveals every astroid it passes over
+pub struct ScanPulse {
+    pub origin: (f32, f32),
+    pub radius: f32,
+    pub max_radius: f32,
+}
+
+impl ScanPulse {
+    pub fn new(origin: (f32, f32), max_radius: f32) -> ScanPulse {
+        ScanPulse {
+            origin,
+            radius: 0.0,
+            max_radius,
+        }
+    }
+}
+
+impl Component for ScanPulse {
+    type Storage = DenseVecStorage<Self>;
+}
+
 //Camera Zoom componant
 pub struct PlayerCamera {
     pub width: f32,
@@ -309,12 +1561,54 @@ impl Component for PlayerCamera {
 
 //Astroid components
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Astroid {
-    pub ore: Ore,
+    //Share of the rock each ore makes up, adding up to 1
+    pub composition: Vec<(Ore, f32)>,
+    pub size: AstroidSize,
     pub resources: u32,
+    //Rock it started out with, how much of it is gone picks the sprite
+    pub starting: u32,
+    //Rock mined since the last chunk broke off
+    pub loose: u32,
+    //Ore recovered from the loose rock, whole units break off as pickups
+    pub extracted: HashMap<Ore, f32>,
+    //Tool that last worked the rock, the rubble it ends up as is broken down with it too
+    pub mined_with: Option<MiningTool>,
+    //Composition and resources stay hidden until scanned
+    pub scanned: bool,
+    //A close up scan also shows what the equipped tool will get out of it
+    pub detailed: bool,
 }
 
-#[derive(PartialEq, Eq)]
+//Astroids break down a size at a time as they are mined
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AstroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AstroidSize {
+    pub fn mass(&self) -> f32 {
+        match self {
+            AstroidSize::Large => 50.0,
+            AstroidSize::Medium => 15.0,
+            AstroidSize::Small => 5.0,
+        }
+    }
+
+    //Size of the pieces this breaks into, None once only pickups are left
+    pub fn smaller(&self) -> Option<AstroidSize> {
+        match self {
+            AstroidSize::Large => Some(AstroidSize::Medium),
+            AstroidSize::Medium => Some(AstroidSize::Small),
+            AstroidSize::Small => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum Ore {
     Ice,
     Copper,
@@ -326,17 +1620,1026 @@ pub enum Ore {
 
 impl Astroid {
     pub fn new(ore: Ore) -> Astroid {
+        Astroid::with_composition(vec![(ore, 1.0)], 1000)
+    }
+
+    pub fn with_composition(composition: Vec<(Ore, f32)>, resources: u32) -> Astroid {
         Astroid {
-            ore,
-            resources: 1000,
+            composition,
+            size: AstroidSize::Large,
+            resources,
+            starting: resources,
+            loose: 0,
+            extracted: HashMap::new(),
+            mined_with: None,
+            scanned: false,
+            detailed: false,
         }
     }
+
+    //A broken off piece of this astroid
+    pub fn fragment(&self, size: AstroidSize, resources: u32) -> Astroid {
+        Astroid {
+            size,
+            mined_with: self.mined_with,
+            scanned: self.scanned,
+            detailed: self.detailed,
+            ..Astroid::with_composition(self.composition.clone(), resources)
+        }
+    }
+
+    //Rolls a mix of one main ore and up to two traces, rarer ores turn up less often
+    pub fn random<R: Rng>(rng: &mut R) -> Astroid {
+        let mut composition = Vec::new();
+        let mut left = 1.0;
+        for index in 0..3 {
+            let ore = Ore::roll(rng);
+            let share = match index {
+                0 => rng.gen_range(0.6, 0.9),
+                1 => left * rng.gen_range(0.5, 1.0),
+                _ => left,
+            };
+            if share <= 0.0 {
+                break;
+            }
+            match composition.iter_mut().find(|(existing, _)| *existing == ore) {
+                Some((_, existing_share)) => *existing_share += share,
+                None => composition.push((ore, share)),
+            }
+            left -= share;
+        }
+        Astroid::with_composition(composition, rng.gen_range(800, 1200))
+    }
+
+    pub fn share(&self, ore: Ore) -> f32 {
+        self.composition
+            .iter()
+            .filter(|(existing, _)| *existing == ore)
+            .map(|(_, share)| share)
+            .sum()
+    }
+
+    //The ore making up most of the rock
+    pub fn main_ore(&self) -> Ore {
+        self.composition
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(ore, _)| *ore)
+            .unwrap_or(Ore::Ice)
+    }
+
+    //Raw ore in an amount of rock, anything left from rounding counts as the main ore
+    pub fn ore_in(&self, amount: u32) -> Vec<(Ore, u32)> {
+        let main_ore = self.main_ore();
+        let mut split = self
+            .composition
+            .iter()
+            .map(|(ore, share)| (*ore, (amount as f32 * share).floor() as u32))
+            .collect::<Vec<_>>();
+        let rounded = split.iter().map(|(_, part)| part).sum::<u32>();
+        if let Some((_, part)) = split.iter_mut().find(|(ore, _)| *ore == main_ore) {
+            *part += amount.saturating_sub(rounded);
+        }
+        split.retain(|(_, part)| *part > 0);
+        split
+    }
+
+    //The frame for how much of the rock has been mined out
+    pub fn sprite(&self) -> usize {
+        let mined = self.starting.saturating_sub(self.resources) as f32 / self.starting.max(1) as f32;
+        let stage = (mined * ASTROID_SPRITES.len() as f32) as usize;
+        ASTROID_SPRITES[stage.min(ASTROID_SPRITES.len() - 1)]
+    }
+
+    //Mines one unit of rock with the tool, returns true once a chunk is ready to break off
+    pub fn mine(&mut self, tool: MiningTool, chunk_size: u32) -> bool {
+        self.resources -= 1;
+        self.loose += 1;
+        self.mined_with = Some(tool);
+        for (ore, share) in self.composition.iter() {
+            *self.extracted.entry(*ore).or_insert(0.0) += share * tool.extraction(*ore);
+        }
+        self.loose >= chunk_size || self.resources == 0
+    }
+
+    //Whole units of each recovered ore, the fractions carry over to the next chunk
+    pub fn break_off(&mut self) -> Vec<(Ore, u32)> {
+        self.loose = 0;
+        let mut chunks = Vec::new();
+        for ore in Ore::ALL.iter() {
+            if let Some(extracted) = self.extracted.get_mut(ore) {
+                // Nudged so shares like 0.7 * 10 don't round down to 6
+                let whole = (*extracted + 0.0001).floor();
+                if whole >= 1.0 {
+                    *extracted -= whole;
+                    chunks.push((*ore, whole as u32));
+                }
+            }
+        }
+        chunks
+    }
+
+    //The rest of the rock worked down with the tool that mined it, rock nobody has mined counts as the starting laser
+    pub fn shatter(&self) -> Vec<(Ore, u32)> {
+        let mut rubble = self.clone();
+        let tool = self.mined_with.unwrap_or(MiningTool::Laser);
+        while rubble.resources > 0 {
+            rubble.mine(tool, u32::MAX);
+        }
+        rubble.break_off()
+    }
 }
 
 impl Component for Astroid {
     type Storage = DenseVecStorage<Self>;
 }
 
+//Drifting movement in units per second
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Velocity {
+    pub fn new(x: f32, y: f32) -> Velocity {
+        Velocity { x, y }
+    }
+}
+
+impl Component for Velocity {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Solid body that bounces off other colliders
+pub struct Collider {
+    pub shape: Shape,
+    pub mass: f32,
+    //Where it was last tick, to work out how fast it was moving
+    pub last_position: Option<(f32, f32)>,
+}
+
+impl Collider {
+    pub fn new(shape: Shape, mass: f32) -> Collider {
+        Collider {
+            shape,
+            mass,
+            last_position: None,
+        }
+    }
+
+    pub fn astroid(size: AstroidSize) -> Collider {
+        Collider::new(
+            Shape::sprite_circle(ASTROID_SPRITE_SIZE.0, ASTROID_SPRITE_SIZE.1),
+            size.mass(),
+        )
+    }
+}
+
+impl Component for Collider {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Loose ore floating in space, picked up by flying over it or pulled in by the tractor beam
+pub struct OrePickup {
+    pub ore: Ore,
+    pub amount: u32,
+    //Seconds since it was dropped
+    pub age: f32,
+}
+
+impl OrePickup {
+    pub fn new(ore: Ore, amount: u32) -> OrePickup {
+        OrePickup { ore, amount, age: 0.0 }
+    }
+}
+
+impl Component for OrePickup {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Ore {
+    pub const ALL: [Ore; 6] = [
+        Ore::Ice,
+        Ore::Copper,
+        Ore::Iron,
+        Ore::Gold,
+        Ore::Diamond,
+        Ore::Plasma,
+    ];
+
+    //How often the ore turns up in a sector, out of 100
+    pub fn abundance(&self) -> u32 {
+        match self {
+            Ore::Ice => 30,
+            Ore::Copper => 25,
+            Ore::Iron => 25,
+            Ore::Gold => 10,
+            Ore::Diamond => 6,
+            Ore::Plasma => 4,
+        }
+    }
+
+    pub fn roll<R: Rng>(rng: &mut R) -> Ore {
+        let mut roll = rng.gen_range(0, 100);
+        for ore in Ore::ALL.iter() {
+            if roll < ore.abundance() {
+                return *ore;
+            }
+            roll -= ore.abundance();
+        }
+        Ore::Ice
+    }
+
+    //Blip colour used on the minimap
+    pub fn radar_color(&self) -> [f32; 4] {
+        match self {
+            Ore::Ice => [0.7, 0.9, 1.0, 1.0],
+            Ore::Copper => [0.85, 0.5, 0.2, 1.0],
+            Ore::Iron => [0.6, 0.6, 0.6, 1.0],
+            Ore::Gold => [1.0, 0.85, 0.1, 1.0],
+            Ore::Diamond => [0.6, 1.0, 1.0, 1.0],
+            Ore::Plasma => [0.9, 0.3, 1.0, 1.0],
+        }
+    }
+}
+
+//Refined goods, made from ore in the ship's refinery
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Good {
+    Water,
+    Fuel,
+    Alloy,
+    Lenses,
+    EnergyCells,
+}
+
+impl Good {
+    pub const ALL: [Good; 5] = [
+        Good::Water,
+        Good::Fuel,
+        Good::Alloy,
+        Good::Lenses,
+        Good::EnergyCells,
+    ];
+}
+
+//Cargo component, ore and refined goods share the hold
+pub struct Cargo {
+    pub capacity: u32,
+    pub contents: HashMap<Ore, u32>,
+    pub goods: HashMap<Good, u32>,
+}
+
+impl Cargo {
+    pub fn new(capacity: u32) -> Cargo {
+        Cargo {
+            capacity,
+            contents: HashMap::new(),
+            goods: HashMap::new(),
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.contents.values().sum::<u32>() + self.goods.values().sum::<u32>()
+    }
+
+    pub fn amount(&self, ore: Ore) -> u32 {
+        *self.contents.get(&ore).unwrap_or(&0)
+    }
+
+    pub fn good_amount(&self, good: Good) -> u32 {
+        *self.goods.get(&good).unwrap_or(&0)
+    }
+
+    //Stores as much as fits and returns the amount actually stored
+    pub fn add(&mut self, ore: Ore, amount: u32) -> u32 {
+        let stored = amount.min(self.capacity.saturating_sub(self.total()));
+        *self.contents.entry(ore).or_insert(0) += stored;
+        stored
+    }
+
+    pub fn add_good(&mut self, good: Good, amount: u32) -> u32 {
+        let stored = amount.min(self.capacity.saturating_sub(self.total()));
+        *self.goods.entry(good).or_insert(0) += stored;
+        stored
+    }
+
+    //Removes every listed ore, or nothing if any of it is missing
+    pub fn take_ores(&mut self, ores: &[(Ore, u32)]) -> bool {
+        if ores.iter().any(|(ore, amount)| self.amount(*ore) < *amount) {
+            return false;
+        }
+        for (ore, amount) in ores.iter() {
+            *self.contents.entry(*ore).or_insert(0) -= amount;
+        }
+        true
+    }
+
+    pub fn take_goods(&mut self, goods: &[(Good, u32)]) -> bool {
+        if goods.iter().any(|(good, amount)| self.good_amount(*good) < *amount) {
+            return false;
+        }
+        for (good, amount) in goods.iter() {
+            *self.goods.entry(*good).or_insert(0) -= amount;
+        }
+        true
+    }
+}
+
+impl Component for Cargo {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Ship refinery, works through the enabled recipes one batch at a time
+#[derive(Default)]
+pub struct Refinery {
+    pub enabled: HashSet<String>,
+    pub job: Option<RefineJob>,
+}
+
+//A batch in progress, its ore has already been taken from the hold
+pub struct RefineJob {
+    pub recipe: String,
+    pub remaining: f32,
+}
+
+impl Refinery {
+    pub fn toggle(&mut self, recipe: &str) {
+        if !self.enabled.remove(recipe) {
+            self.enabled.insert(recipe.to_string());
+        }
+    }
+}
+
+impl Component for Refinery {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Hud resource
+pub struct Hud {
+    pub hull_bar: Entity,
+    pub shield_bar: Entity,
+    pub energy_bar: Entity,
+    pub heat_bar: Entity,
+    pub heat_text: Entity,
+    pub fuel_bar: Entity,
+    pub fuel_text: Entity,
+    pub distress_text: Entity,
+    pub cargo_bars: Vec<(Ore, Entity)>,
+    pub cargo_text: Entity,
+    pub wealth_text: Entity,
+    pub goods_text: Entity,
+    pub refinery_text: Entity,
+    pub standing_text: Entity,
+    pub contracts_text: Entity,
+    pub target_text: Entity,
+    //Warnings from the sector event director and the message log
+    pub message_text: Entity,
+}
+
+//Seconds a message stays on the hud
+pub const MESSAGE_SECONDS: f32 = 5.0;
+//Messages shown at once, older ones give way
+pub const MESSAGE_LINES: usize = 3;
+
+//Feedback for the player, e.g. why a jump or docking didn't happen.
+//Newest last, each with the seconds it has been up.
+#[derive(Default)]
+pub struct MessageLog {
+    pub messages: Vec<(String, f32)>,
+}
+
+impl MessageLog {
+    pub fn push(&mut self, message: String) {
+        self.messages.push((message, 0.0));
+        if self.messages.len() > MESSAGE_LINES {
+            self.messages.remove(0);
+        }
+    }
+
+    //Ages every message and drops the ones that have been up long enough
+    pub fn tick(&mut self, delta: f32) {
+        for (_, age) in self.messages.iter_mut() {
+            *age += delta;
+        }
+        self.messages.retain(|(_, age)| *age < MESSAGE_SECONDS);
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.messages.iter().map(|(message, _)| message.as_str())
+    }
+}
+
+//Radar components
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ContactKind {
+    Asteroid,
+    Station,
+    Hostile,
+    Neutral,
+}
+
+impl ContactKind {
+    //Static contacts stay on the minimap once their region is explored
+    pub fn is_static(&self) -> bool {
+        match self {
+            ContactKind::Asteroid | ContactKind::Station => true,
+            ContactKind::Hostile | ContactKind::Neutral => false,
+        }
+    }
+
+    pub fn radar_color(&self) -> [f32; 4] {
+        match self {
+            ContactKind::Asteroid => [0.7, 0.7, 0.7, 1.0],
+            ContactKind::Station => [0.2, 0.6, 1.0, 1.0],
+            ContactKind::Hostile => [1.0, 0.15, 0.15, 1.0],
+            ContactKind::Neutral => [0.2, 1.0, 0.3, 1.0],
+        }
+    }
+}
+
+pub struct RadarContact {
+    pub kind: ContactKind,
+}
+
+impl RadarContact {
+    pub fn new(kind: ContactKind) -> RadarContact {
+        RadarContact { kind }
+    }
+}
+
+impl Component for RadarContact {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Seed the current sector was generated from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SectorSeed(pub u64);
+
+//Sectors sit on a grid, each one generated from the galaxy seed and its coordinates
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Galaxy {
+    pub seed: u64,
+    pub sector: (i32, i32),
+}
+
+impl Galaxy {
+    pub fn new(seed: u64) -> Galaxy {
+        Galaxy { seed, sector: (0, 0) }
+    }
+
+    //The home sector uses the galaxy seed itself so replays and old saves lay out the same
+    pub fn sector_seed(&self, sector: (i32, i32)) -> u64 {
+        if sector == (0, 0) {
+            return self.seed;
+        }
+        let mut hasher = DefaultHasher::new();
+        (self.seed, sector).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+//Sector a jump is heading for, carried out once the dispatcher has run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JumpRequest(pub Option<(i32, i32)>);
+
+//Timed events the director can throw at a sector
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SectorEventKind {
+    MeteorShower,
+    SolarFlare,
+    PirateRaid,
+    CometFlyby,
+}
+
+impl SectorEventKind {
+    //Shown on the HUD when the event starts
+    pub fn warning(&self) -> &'static str {
+        match self {
+            SectorEventKind::MeteorShower => "Meteor shower incoming",
+            SectorEventKind::SolarFlare => "Solar flare, shields draining",
+            SectorEventKind::PirateRaid => "Pirate raid, hostile ships inbound",
+            SectorEventKind::CometFlyby => "A plasma rich comet is passing through",
+        }
+    }
+}
+
+//Something an event brings into the sector, spawned once the dispatcher has run
+pub enum EventSpawn {
+    Meteor { astroid: Astroid, position: (f32, f32), velocity: Velocity },
+    //Comets keep their speed and leave once their time is up
+    Comet { astroid: Astroid, position: (f32, f32), velocity: Velocity, lifetime: f32 },
+    Raider { position: (f32, f32) },
+}
+
+//Sector event director resource, replaced on entering a sector
+pub struct SectorEvents {
+    pub danger: f32,
+    //Seconds until the next event
+    pub next_event: f32,
+    //Seconds left on a solar flare, and drained shield that doesn't add up to a whole point yet
+    pub flare: f32,
+    pub flare_progress: f32,
+    //Latest event and the seconds since, for the HUD
+    pub last_event: Option<(SectorEventKind, f32)>,
+    pub pending: Vec<EventSpawn>,
+    //Spawned ships and astroids so far, to give each a name of its own
+    pub spawned: u32,
+    //Seeded from the sector so replays get the same events
+    pub rng: StdRng,
+}
+
+impl SectorEvents {
+    pub fn new(danger: f32, seed: u64, config: &EventConfig) -> SectorEvents {
+        let mut rng = StdRng::seed_from_u64(seed ^ 0x4556_4e54);
+        SectorEvents {
+            danger,
+            next_event: director::next_wait(&mut rng, config, danger),
+            flare: 0.0,
+            flare_progress: 0.0,
+            last_event: None,
+            pending: Vec::new(),
+            spawned: 0,
+            rng,
+        }
+    }
+}
+
+//Comet component, holds its course across the sector until it moves on
+pub struct Comet {
+    pub velocity: Velocity,
+    pub time_left: f32,
+}
+
+impl Component for Comet {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Meteor component, marks the rock a meteor shower brought in
+#[derive(Default)]
+pub struct Meteor;
+
+impl Component for Meteor {
+    type Storage = NullStorage<Self>;
+}
+
+//How a sector grows back, rolled once per sector
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RegenPolicy {
+    //Seconds between new astroids while there is room for more
+    pub respawn_interval: f32,
+    pub max_astroids: u32,
+    //Chance of each ore being a new astroid's main ore, adding up to 1
+    pub ore_mix: Vec<(Ore, f32)>,
+}
+
+//Regrowth of the sector the player is in, resource replaced on entering a sector
+pub struct SectorRegen {
+    pub policy: RegenPolicy,
+    //Seconds into the current interval
+    pub timer: f32,
+    //Number in the next regrown astroid's name
+    pub next_index: u32,
+    //Named astroids to spawn once the dispatcher has run
+    pub pending: Vec<(String, Astroid, (f32, f32))>,
+    pub rng: StdRng,
+}
+
+impl SectorRegen {
+    pub fn new(seed: u64, config: &RegenConfig) -> SectorRegen {
+        let mut rng = StdRng::seed_from_u64(seed ^ 0x5245_4745);
+        SectorRegen {
+            policy: regen::roll_policy(&mut rng, config),
+            timer: 0.0,
+            next_index: SECTOR_ASTROIDS,
+            pending: Vec::new(),
+            rng,
+        }
+    }
+}
+
+//A sector the player has left, the astroids still in it and when
+pub struct SectorRecord {
+    pub regen: SectorRegen,
+    pub astroids: Vec<(String, Astroid, (f32, f32))>,
+    pub left_at: f32,
+}
+
+//Every sector left behind this game. The clock counts simulated seconds so a
+//sector catches up on its regrowth by how long the player was away.
+#[derive(Default)]
+pub struct SectorHistory {
+    pub clock: f32,
+    pub sectors: HashMap<(i32, i32), SectorRecord>,
+}
+
+//Station component, ships dock here to refuel
+pub struct Station {
+    pub name: String,
+}
+
+impl Station {
+    pub fn new(name: String) -> Station {
+        Station { name }
+    }
+}
+
+impl Component for Station {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Station market, the ore it has in stock sets what it pays
+pub struct Market {
+    pub stock: HashMap<Ore, f32>,
+}
+
+impl Market {
+    pub fn new(config: &MarketConfig) -> Market {
+        Market {
+            stock: Ore::ALL.iter().map(|ore| (*ore, config.stock_target)).collect(),
+        }
+    }
+
+    pub fn stock(&self, ore: Ore) -> f32 {
+        *self.stock.get(&ore).unwrap_or(&0.0)
+    }
+
+    //Twice the base price with nothing in stock, nothing extra at the target and less above it
+    pub fn price(&self, ore: Ore, config: &MarketConfig) -> f32 {
+        let factor = if config.stock_target > 0.0 {
+            2.0 - self.stock(ore) / config.stock_target
+        } else {
+            1.0
+        };
+        config.base_price(ore) * factor.max(config.price_floor).min(config.price_ceiling)
+    }
+
+    //Sells ore a unit at a time so big loads push the price down as they go, returns the wealth paid
+    pub fn sell(&mut self, ore: Ore, amount: u32, config: &MarketConfig) -> f32 {
+        let mut paid = 0.0;
+        for _ in 0..amount {
+            paid += self.price(ore, config);
+            *self.stock.entry(ore).or_insert(0.0) += 1.0;
+        }
+        paid
+    }
+
+    //Buys up to an amount out of the stock, returns the units bought and what they cost
+    pub fn buy(&mut self, ore: Ore, amount: u32, config: &MarketConfig) -> (u32, f32) {
+        let mut bought = 0;
+        let mut cost = 0.0;
+        while bought < amount && self.stock(ore) >= 1.0 {
+            *self.stock.entry(ore).or_insert(0.0) -= 1.0;
+            cost += self.price(ore, config);
+            bought += 1;
+        }
+        (bought, cost)
+    }
+
+    //Stations use up or ship out their ore, the stock drifts back to the target
+    pub fn restock(&mut self, config: &MarketConfig, delta: f32) {
+        let step = config.restock_rate * delta;
+        for stock in self.stock.values_mut() {
+            if *stock < config.stock_target {
+                *stock = (*stock + step).min(config.stock_target);
+            } else {
+                *stock = (*stock - step).max(config.stock_target);
+            }
+        }
+    }
+}
+
+impl Component for Market {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum NpcRole {
+    Miner,
+    Trader,
+    Pirate,
+}
+
+impl NpcRole {
+    pub fn contact_kind(&self) -> ContactKind {
+        match self {
+            NpcRole::Pirate => ContactKind::Hostile,
+            NpcRole::Miner | NpcRole::Trader => ContactKind::Neutral,
+        }
+    }
+}
+
+//What an NPC is currently trying to do
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NpcGoal {
+    Wander,
+    Mine(Entity),
+    //Miners sell their ore, traders buy some up
+    Sell(Entity),
+    Trade(Entity),
+    //Traders take their cargo to another station's market, or out of the sector and back
+    Haul,
+    Hunt(Entity),
+    Flee(Entity),
+}
+
+//NPC ship Component
+pub struct Npc {
+    pub archetype: NpcArchetype,
+    pub hull: u32,
+    pub goal: NpcGoal,
+    //Seconds until the goal is weighed up again
+    pub think: f32,
+    //Point a wandering or hauling ship is heading for
+    pub waypoint: (f32, f32),
+    //Station a trader last bought from and the one it is hauling the cargo to
+    pub bought_at: Option<Entity>,
+    pub delivery: Option<Entity>,
+    //Rock mined and damage dealt that don't add up to a whole unit yet
+    pub mining_progress: f32,
+    pub damage_progress: f32,
+    //Whoever damaged it last, wrecking it only counts against the player if that was them
+    pub last_attacker: Option<Entity>,
+    //Each NPC rolls its own waypoints so replays play out the same
+    pub rng: StdRng,
+}
+
+impl Npc {
+    pub fn new(archetype: NpcArchetype, seed: u64) -> Npc {
+        Npc {
+            hull: archetype.hull,
+            archetype,
+            goal: NpcGoal::Wander,
+            think: 0.0,
+            waypoint: (0.0, 0.0),
+            bought_at: None,
+            delivery: None,
+            mining_progress: 0.0,
+            damage_progress: 0.0,
+            last_attacker: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn role(&self) -> NpcRole {
+        self.archetype.role
+    }
+
+    pub fn faction(&self) -> Faction {
+        self.archetype.faction
+    }
+
+    pub fn take_damage(&mut self, amount: u32) {
+        self.hull = self.hull.saturating_sub(amount);
+    }
+}
+
+impl Component for Npc {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Groups the NPC ships and stations belong to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Faction {
+    MinersGuild,
+    Pirates,
+    StationAuthority,
+}
+
+impl Faction {
+    pub const ALL: [Faction; 3] = [Faction::MinersGuild, Faction::Pirates, Faction::StationAuthority];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Faction::MinersGuild => "Miners' Guild",
+            Faction::Pirates => "Pirates",
+            Faction::StationAuthority => "Station Authority",
+        }
+    }
+}
+
+//Standing can't go past this either way
+pub const MAX_STANDING: f32 = 100.0;
+
+//Player standing with each faction Component
+#[derive(Clone, Debug, Default)]
+pub struct Reputation {
+    pub standing: HashMap<Faction, f32>,
+}
+
+impl Reputation {
+    pub fn new(config: &FactionConfig) -> Reputation {
+        let mut reputation = Reputation::default();
+        for (faction, standing) in config.starting.iter() {
+            reputation.change(*faction, *standing);
+        }
+        reputation
+    }
+
+    pub fn standing(&self, faction: Faction) -> f32 {
+        self.standing.get(&faction).cloned().unwrap_or(0.0)
+    }
+
+    pub fn change(&mut self, faction: Faction, amount: f32) {
+        let standing = (self.standing(faction) + amount).max(-MAX_STANDING).min(MAX_STANDING);
+        self.standing.insert(faction, standing);
+    }
+
+    pub fn is_hostile(&self, faction: Faction, config: &FactionConfig) -> bool {
+        self.standing(faction) < config.hostile_below
+    }
+
+    pub fn can_dock(&self, config: &FactionConfig) -> bool {
+        self.standing(Faction::StationAuthority) >= config.dock_below
+    }
+
+    //Multiplier on what stations pay for ore
+    pub fn price_factor(&self, config: &FactionConfig) -> f32 {
+        1.0 + self.standing(Faction::StationAuthority) / MAX_STANDING * config.price_bonus
+    }
+
+    //Damage done to one of the faction's ships
+    pub fn attacked(&mut self, faction: Faction, damage: u32, config: &FactionConfig) {
+        self.change(faction, -(damage as f32) * config.attack_penalty);
+    }
+
+    //One of the faction's ships wrecked, its enemies are grateful
+    pub fn wrecked(&mut self, faction: Faction, config: &FactionConfig) {
+        self.change(faction, -config.kill_penalty);
+        for rival in config.rivals_of(faction) {
+            self.change(rival, config.kill_bonus);
+        }
+    }
+}
+
+impl Component for Reputation {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Things that happen in the simulation, published on an EventChannel for whoever listens
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    //The player broke ore off an astroid
+    OreMined { ore: Ore, amount: u32, sector: (i32, i32) },
+    ShipDestroyed { role: NpcRole, faction: Faction, by_player: bool },
+    Docked { station: String },
+}
+
+//The work a contract asks for
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum ContractKind {
+    //Bring ore to the station that offered the contract
+    Deliver { ore: Ore, amount: u32 },
+    Destroy { role: NpcRole, count: u32 },
+    //Mine anything in the sector to bring back samples
+    Survey { sector: (i32, i32) },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ContractStatus {
+    Active,
+    Completed,
+    Failed,
+}
+
+//A job offered on a station's board, and kept in the quest log once taken
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Contract {
+    pub kind: ContractKind,
+    pub issuer: Faction,
+    //Station that offered it, deliveries go back there
+    pub station: String,
+    pub reward: f32,
+    //Standing gained with the issuer on completion
+    pub standing: f32,
+    //Seconds allowed, and left once taken
+    pub time_limit: f32,
+    pub time_left: f32,
+    pub progress: u32,
+    pub status: ContractStatus,
+}
+
+impl Contract {
+    pub fn new(kind: ContractKind, issuer: Faction, station: String, reward: f32, standing: f32, time_limit: f32) -> Contract {
+        Contract {
+            kind,
+            issuer,
+            station,
+            reward,
+            standing,
+            time_limit,
+            time_left: time_limit,
+            progress: 0,
+            status: ContractStatus::Active,
+        }
+    }
+
+    //What progress has to reach
+    pub fn goal(&self) -> u32 {
+        match self.kind {
+            ContractKind::Deliver { amount, .. } => amount,
+            ContractKind::Destroy { count, .. } => count,
+            ContractKind::Survey { .. } => 1,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress >= self.goal()
+    }
+
+    //e.g. "Deliver 300 Copper to Station 0:0"
+    pub fn describe(&self) -> String {
+        match self.kind {
+            ContractKind::Deliver { ore, amount } => format!("Deliver {} {:?} to {}", amount, ore, self.station),
+            ContractKind::Destroy { role, count } => format!("Destroy {} {:?} ships", count, role),
+            ContractKind::Survey { sector } => format!("Survey sector {}:{} by mining there", sector.0, sector.1),
+        }
+    }
+
+    //A contract underway, e.g. "Deliver 300 Copper to Station 0:0 (120/300, 9:05 left)"
+    pub fn describe_progress(&self) -> String {
+        let seconds = self.time_left.max(0.0) as u32;
+        format!(
+            "{} ({}/{}, {}:{:02} left)",
+            self.describe(),
+            self.progress,
+            self.goal(),
+            seconds / 60,
+            seconds % 60
+        )
+    }
+}
+
+//Contracts the player has taken on Component, finished ones stay in the log
+#[derive(Clone, Debug, Default)]
+pub struct QuestLog {
+    pub contracts: Vec<Contract>,
+}
+
+impl QuestLog {
+    pub fn active(&self) -> impl Iterator<Item = &Contract> {
+        self.contracts.iter().filter(|contract| contract.status == ContractStatus::Active)
+    }
+}
+
+impl Component for QuestLog {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Contracts a station has on offer Component
+#[derive(Clone, Debug, Default)]
+pub struct ContractBoard {
+    pub offers: Vec<Contract>,
+}
+
+impl Component for ContractBoard {
+    type Storage = DenseVecStorage<Self>;
+}
+
+//Minimap resource
+pub struct Minimap {
+    pub panel: Entity,
+}
+
+//Explored regions of the sector, tracked on a grid
+pub struct FogOfWar {
+    pub cell_size: f32,
+    pub explored: HashSet<(i32, i32)>,
+}
+
+impl FogOfWar {
+    pub fn new(cell_size: f32) -> FogOfWar {
+        FogOfWar {
+            cell_size,
+            explored: HashSet::new(),
+        }
+    }
+
+    pub fn cell_at(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn is_explored(&self, x: f32, y: f32) -> bool {
+        self.explored.contains(&self.cell_at(x, y))
+    }
+
+    //Marks every cell touching the circle as explored
+    pub fn reveal(&mut self, x: f32, y: f32, radius: f32) {
+        let (min_x, min_y) = self.cell_at(x - radius, y - radius);
+        let (max_x, max_y) = self.cell_at(x + radius, y + radius);
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                self.explored.insert((cell_x, cell_y));
+            }
+        }
+    }
+}
+
 
 
 /* LEIGHTWEIGHT ITEMS */
@@ -0,0 +1,159 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::solar_lords::{accept_contract, Contract, ContractBoard, ContractStatus, Player, QuestLog, Station};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    GameStateEvent, GameTrans,
+};
+
+//Finished contracts the log still shows
+const LOG_HISTORY: usize = 3;
+
+//Mission board pushed from a station, click an offer to take it on.
+//The quest log underneath shows the contracts underway and the last few finished.
+pub struct ContractsState {
+    station: String,
+    back: Option<Entity>,
+    offer_rows: Vec<(Entity, usize)>,
+    ui: Vec<Entity>,
+}
+
+impl ContractsState {
+    pub fn new(station: String) -> ContractsState {
+        ContractsState {
+            station,
+            back: None,
+            offer_rows: Vec::new(),
+            ui: Vec::new(),
+        }
+    }
+
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.offer_rows.clear();
+
+        let offers = {
+            let stations = world.read_storage::<Station>();
+            let boards = world.read_storage::<ContractBoard>();
+            (&stations, &boards)
+                .join()
+                .find(|(station, _)| station.name == self.station)
+                .map(|(_, board)| board.offers.clone())
+                .unwrap_or_default()
+        };
+        let contracts = {
+            let players = world.read_storage::<Player>();
+            let logs = world.read_storage::<QuestLog>();
+            (&players, &logs)
+                .join()
+                .map(|(_, log)| log.contracts.clone())
+                .next()
+                .unwrap_or_default()
+        };
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Contracts", 300.0, 40.0);
+        let status = create_menu_text(world, &font, status, 260.0, 18.0);
+        let offered = create_menu_text(world, &font, "On offer", 220.0, 24.0);
+        self.ui.extend(vec![title, status, offered]);
+
+        let mut y = 180.0;
+        for (index, offer) in offers.iter().enumerate() {
+            let row = create_menu_button_sized(world, &font, &describe_offer(offer), y, 760.0);
+            self.offer_rows.push((row, index));
+            self.ui.push(row);
+            y -= 42.0;
+        }
+
+        let heading = create_menu_text(world, &font, "Quest log", y - 10.0, 24.0);
+        self.ui.push(heading);
+        y -= 50.0;
+        let finished = contracts
+            .iter()
+            .filter(|contract| contract.status != ContractStatus::Active)
+            .collect::<Vec<_>>();
+        let recent = &finished[finished.len().saturating_sub(LOG_HISTORY)..];
+        let active = contracts.iter().filter(|contract| contract.status == ContractStatus::Active);
+        for contract in active.chain(recent.iter().cloned()) {
+            let line = match contract.status {
+                ContractStatus::Active => contract.describe_progress(),
+                ContractStatus::Completed => format!("Completed: {}", contract.describe()),
+                ContractStatus::Failed => format!("Failed: {}", contract.describe()),
+            };
+            let text = create_menu_text(world, &font, &line, y, 18.0);
+            self.ui.push(text);
+            y -= 28.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y - 20.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn accept(&mut self, world: &mut World, offer: usize) {
+        let ship = (&world.entities(), &world.read_storage::<Player>())
+            .join()
+            .map(|(entity, _)| entity)
+            .next();
+        let status = match ship.map(|ship| accept_contract(world, ship, offer)) {
+            Some(Ok(contract)) => format!("Took on: {}", contract.describe()),
+            Some(Err(err)) => err,
+            None => return,
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for ContractsState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Deliveries are handed over when you dock here");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, index)) = self.offer_rows.iter().find(|(row, _)| *row == target) {
+                    let index = *index;
+                    self.accept(data.world, index);
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
+
+//e.g. "Destroy 2 Pirate ships: 600 wealth, +10 Station Authority, 15:00"
+fn describe_offer(contract: &Contract) -> String {
+    let seconds = contract.time_limit.max(0.0) as u32;
+    format!(
+        "{}: {:.0} wealth, +{:.0} {}, {}:{:02}",
+        contract.describe(),
+        contract.reward,
+        contract.standing,
+        contract.issuer.label(),
+        seconds / 60,
+        seconds % 60
+    )
+}
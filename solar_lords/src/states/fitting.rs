@@ -0,0 +1,166 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::config::{ModuleDef, ShipClass, SlotKind};
+use crate::fitting;
+use crate::solar_lords::{fit_module, unfit_module, Fitting, Player, Ship};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    GameStateEvent, GameTrans,
+};
+
+//Module fitting pushed from a station, click a fitted module to take it off
+//or a spare one to fit it
+#[derive(Default)]
+pub struct FittingState {
+    back: Option<Entity>,
+    fitted_rows: Vec<(Entity, usize)>,
+    spare_rows: Vec<(Entity, usize)>,
+    ui: Vec<Entity>,
+}
+
+impl FittingState {
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.fitted_rows.clear();
+        self.spare_rows.clear();
+
+        let (class, modules, spares) = {
+            let players = world.read_storage::<Player>();
+            let ships = world.read_storage::<Ship>();
+            let fittings = world.read_storage::<Fitting>();
+            (&players, &ships, &fittings)
+                .join()
+                .map(|(_, ship, fitting)| (ship.class.clone(), fitting.modules.clone(), fitting.spares.clone()))
+                .next()
+                .unwrap_or_else(|| (ShipClass::default(), Vec::new(), Vec::new()))
+        };
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Fitting", 300.0, 40.0);
+        let status = create_menu_text(world, &font, status, 260.0, 18.0);
+        let budget = create_menu_text(
+            world,
+            &font,
+            &format!(
+                "{}: power {:.0}/{:.0}, CPU {:.0}/{:.0}, module size {}",
+                class.name,
+                fitting::power_used(&modules),
+                class.power,
+                fitting::cpu_used(&modules),
+                class.cpu,
+                class.module_size
+            ),
+            230.0,
+            18.0,
+        );
+        let slots = create_menu_text(world, &font, &describe_slots(&class, &modules), 205.0, 18.0);
+        let fitted = create_menu_text(world, &font, "Fitted", 170.0, 24.0);
+        self.ui.extend(vec![title, status, budget, slots, fitted]);
+
+        let mut y = 130.0;
+        for (index, module) in modules.iter().enumerate() {
+            let row = create_menu_button_sized(world, &font, &describe_module(module), y, 640.0);
+            self.fitted_rows.push((row, index));
+            self.ui.push(row);
+            y -= 42.0;
+        }
+
+        let spare = create_menu_text(world, &font, "Spares", y - 10.0, 24.0);
+        self.ui.push(spare);
+        y -= 50.0;
+        for (index, module) in spares.iter().enumerate() {
+            let row = create_menu_button_sized(world, &font, &describe_module(module), y, 640.0);
+            self.spare_rows.push((row, index));
+            self.ui.push(row);
+            y -= 42.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y - 10.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn change(&mut self, world: &mut World, fit: bool, index: usize) {
+        let ship = (&world.entities(), &world.read_storage::<Player>())
+            .join()
+            .map(|(entity, _)| entity)
+            .next();
+        let ship = match ship {
+            Some(ship) => ship,
+            None => return,
+        };
+        let result = if fit {
+            fit_module(world, ship, index)
+        } else {
+            unfit_module(world, ship, index)
+        };
+        let status = match result {
+            Ok(()) if fit => "Module fitted".to_string(),
+            Ok(()) => "Module moved to the spares".to_string(),
+            Err(err) => err,
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for FittingState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Modules have to fit the hull's slots, power and CPU");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, index)) = self.fitted_rows.iter().find(|(row, _)| *row == target) {
+                    let index = *index;
+                    self.change(data.world, false, index);
+                } else if let Some((_, index)) = self.spare_rows.iter().find(|(row, _)| *row == target) {
+                    let index = *index;
+                    self.change(data.world, true, index);
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
+
+//e.g. "Mining 1/1, Weapon 1/2, Utility 0/1, Defense 0/1"
+fn describe_slots(class: &ShipClass, modules: &[ModuleDef]) -> String {
+    SlotKind::ALL
+        .iter()
+        .map(|kind| {
+            let used = modules.iter().filter(|module| module.slot == *kind).count();
+            format!("{:?} {}/{}", kind, used, fitting::slot_count(class, *kind))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_module(module: &ModuleDef) -> String {
+    format!(
+        "{} ({:?}, size {}): power {:.0}, CPU {:.0}",
+        module.name, module.slot, module.size, module.power, module.cpu
+    )
+}
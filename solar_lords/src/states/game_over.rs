@@ -0,0 +1,63 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::solar_lords::Player;
+use crate::states::{
+    create_menu_button, create_menu_text, delete_menu, menu_font, GameStateEvent, GameTrans,
+};
+
+//Pushed over the SectorState when the player's hull reaches zero
+#[derive(Default)]
+pub struct GameOverState {
+    main_menu: Option<Entity>,
+    quit: Option<Entity>,
+    ui: Vec<Entity>,
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        let world = data.world;
+        let wealth = {
+            let players = world.read_storage::<Player>();
+            players.join().map(|player| player.wealth).next().unwrap_or(0.0)
+        };
+
+        let font = menu_font(world);
+        let title = create_menu_text(world, &font, "Ship Destroyed", 120.0, 40.0);
+        let summary = create_menu_text(world, &font, &format!("Final wealth: {:.0}", wealth), 70.0, 22.0);
+        let main_menu = create_menu_button(world, &font, "Main Menu", 0.0);
+        let quit = create_menu_button(world, &font, "Quit", -50.0);
+
+        self.main_menu = Some(main_menu);
+        self.quit = Some(quit);
+        self.ui = vec![title, summary, main_menu, quit];
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, _: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        if let StateEvent::Ui(UiEvent {
+            event_type: UiEventType::Click,
+            target,
+        }) = event
+        {
+            if Some(target) == self.main_menu {
+                return Trans::Sequence(vec![Trans::Pop, Trans::Pop]);
+            }
+            if Some(target) == self.quit {
+                return Trans::Quit;
+            }
+        }
+        Trans::None
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
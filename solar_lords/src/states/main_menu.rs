@@ -0,0 +1,94 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::save::SaveGame;
+use crate::solar_lords::SectorState;
+use crate::states::{
+    create_menu_button, create_menu_text, delete_menu, menu_font, set_menu_text, GameStateEvent, GameTrans,
+    SettingsState,
+};
+
+//Main menu, the bottom of the state stack
+#[derive(Default)]
+pub struct MainMenuState {
+    new_game: Option<Entity>,
+    load: Option<Entity>,
+    settings: Option<Entity>,
+    quit: Option<Entity>,
+    status: Option<Entity>,
+    ui: Vec<Entity>,
+}
+
+impl MainMenuState {
+    fn create_ui(&mut self, world: &mut World) {
+        let font = menu_font(world);
+        let title = create_menu_text(world, &font, "Solar Lords", 180.0, 48.0);
+        let status = create_menu_text(world, &font, "", 120.0, 18.0);
+        let new_game = create_menu_button(world, &font, "New Game", 60.0);
+        let load = create_menu_button(world, &font, "Load Game", 10.0);
+        let settings = create_menu_button(world, &font, "Settings", -40.0);
+        let quit = create_menu_button(world, &font, "Quit", -90.0);
+
+        self.new_game = Some(new_game);
+        self.load = Some(load);
+        self.settings = Some(settings);
+        self.quit = Some(quit);
+        self.status = Some(status);
+        self.ui = vec![title, status, new_game, load, settings, quit];
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for MainMenuState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world);
+    }
+
+    fn on_pause(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        if let StateEvent::Ui(UiEvent {
+            event_type: UiEventType::Click,
+            target,
+        }) = event
+        {
+            if Some(target) == self.new_game {
+                return Trans::Push(Box::new(SectorState::default()));
+            }
+            if Some(target) == self.load {
+                let status = match SaveGame::load_from_disk() {
+                    Ok(Some(save)) => return Trans::Push(Box::new(SectorState::from_save(save))),
+                    Ok(None) => "No saved game found".to_string(),
+                    Err(err) => format!("Could not load the saved game: {}", err),
+                };
+                if let Some(entity) = self.status {
+                    set_menu_text(data.world, entity, &status);
+                }
+            }
+            if Some(target) == self.settings {
+                return Trans::Push(Box::new(SettingsState::default()));
+            }
+            if Some(target) == self.quit {
+                return Trans::Quit;
+            }
+        }
+        Trans::None
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
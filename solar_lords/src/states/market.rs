@@ -0,0 +1,147 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::config::{FactionConfig, MarketConfig};
+use crate::solar_lords::{sell_ore, Cargo, Faction, Market, Ore, Player, Reputation, Station};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    GameStateEvent, GameTrans,
+};
+
+//Ore market pushed from a station, click an ore to sell all of it
+pub struct MarketState {
+    station: String,
+    back: Option<Entity>,
+    ore_rows: Vec<(Entity, Ore)>,
+    ui: Vec<Entity>,
+}
+
+impl MarketState {
+    pub fn new(station: String) -> MarketState {
+        MarketState {
+            station,
+            back: None,
+            ore_rows: Vec::new(),
+            ui: Vec::new(),
+        }
+    }
+
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.ore_rows.clear();
+
+        let config = world.read_resource::<MarketConfig>().clone();
+        let prices = {
+            let stations = world.read_storage::<Station>();
+            let markets = world.read_storage::<Market>();
+            (&stations, &markets)
+                .join()
+                .find(|(station, _)| station.name == self.station)
+                .map(|(_, market)| Ore::ALL.iter().map(|ore| (*ore, market.price(*ore, &config))).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+        let (hold, wealth) = {
+            let players = world.read_storage::<Player>();
+            let cargos = world.read_storage::<Cargo>();
+            (&players, &cargos)
+                .join()
+                .map(|(player, cargo)| (Ore::ALL.iter().map(|ore| cargo.amount(*ore)).collect::<Vec<_>>(), player.wealth))
+                .next()
+                .unwrap_or_default()
+        };
+        // What the player is actually paid depends on their standing with the station authority
+        let factions = world.entry::<FactionConfig>().or_insert_with(FactionConfig::default).clone();
+        let reputation = {
+            let players = world.read_storage::<Player>();
+            let reputations = world.read_storage::<Reputation>();
+            (&players, &reputations)
+                .join()
+                .map(|(_, reputation)| reputation.clone())
+                .next()
+                .unwrap_or_else(|| Reputation::new(&factions))
+        };
+        let factor = reputation.price_factor(&factions);
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Market", 220.0, 40.0);
+        let status = create_menu_text(world, &font, status, 175.0, 18.0);
+        let summary = create_menu_text(
+            world,
+            &font,
+            &format!(
+                "Wealth {:.0}, Station Authority standing {:.0}",
+                wealth,
+                reputation.standing(Faction::StationAuthority)
+            ),
+            145.0,
+            18.0,
+        );
+        self.ui.extend(vec![title, status, summary]);
+
+        let mut y = 95.0;
+        for (index, (ore, price)) in prices.into_iter().enumerate() {
+            let held = hold.get(index).cloned().unwrap_or(0);
+            let label = format!("{:?}: {:.1} each, {} in the hold", ore, price * factor, held);
+            let row = create_menu_button_sized(world, &font, &label, y, 640.0);
+            self.ore_rows.push((row, ore));
+            self.ui.push(row);
+            y -= 50.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y - 10.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn sell(&mut self, world: &mut World, ore: Ore) {
+        let ship = (&world.entities(), &world.read_storage::<Player>())
+            .join()
+            .map(|(entity, _)| entity)
+            .next();
+        let status = match ship.map(|ship| sell_ore(world, ship, ore)) {
+            Some(Ok((amount, paid))) => format!("Sold {} {:?} for {:.0}", amount, ore, paid),
+            Some(Err(err)) => err,
+            None => return,
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for MarketState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Prices drop as the station fills up on an ore");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, ore)) = self.ore_rows.iter().find(|(row, _)| *row == target) {
+                    let ore = *ore;
+                    self.sell(data.world, ore);
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
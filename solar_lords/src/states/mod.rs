@@ -0,0 +1,131 @@
+use amethyst::{
+    assets::{AssetStorage, Loader},
+    ecs::prelude::Entity,
+    prelude::*,
+    StateEvent,
+    ui::{get_default_font, Anchor, FontAsset, FontHandle, Interactable, LineMode, UiImage, UiText, UiTransform},
+};
+
+use crate::input::GameBindings;
+
+mod contracts;
+mod fitting;
+mod game_over;
+mod main_menu;
+mod market;
+mod pause;
+mod settings;
+mod shipyard;
+mod station;
+mod workshop;
+
+pub use self::{
+    contracts::ContractsState,
+    fitting::FittingState,
+    game_over::GameOverState,
+    main_menu::MainMenuState,
+    market::MarketState,
+    pause::PauseState,
+    settings::SettingsState,
+    shipyard::ShipyardState,
+    station::StationState,
+    workshop::WorkshopState,
+};
+
+//States receive input events using the game's typed bindings
+pub type GameStateEvent = StateEvent<GameBindings>;
+pub type GameTrans<'a, 'b> = Trans<GameData<'a, 'b>, GameStateEvent>;
+
+//Simulation systems only run while this resource is Running
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulationState {
+    Running,
+    Paused,
+}
+
+impl Default for SimulationState {
+    fn default() -> Self {
+        SimulationState::Paused
+    }
+}
+
+pub fn menu_font(world: &World) -> FontHandle {
+    let loader = world.read_resource::<Loader>();
+    let font_storage = world.read_resource::<AssetStorage<FontAsset>>();
+    get_default_font(&loader, &font_storage)
+}
+
+//Centered line of text, used for menu titles
+pub fn create_menu_text(world: &mut World, font: &FontHandle, text: &str, y: f32, size: f32) -> Entity {
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            "menu_text".to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.0,
+            y,
+            10.0,
+            600.0,
+            size * 1.5,
+        ))
+        .with(UiText::new(
+            font.clone(),
+            text.to_string(),
+            [1.0, 1.0, 1.0, 1.0],
+            size,
+            LineMode::Single,
+            Anchor::Middle,
+        ))
+        .build()
+}
+
+//Clickable centered button, clicks arrive as UiEvents targeting the returned entity
+pub fn create_menu_button(world: &mut World, font: &FontHandle, label: &str, y: f32) -> Entity {
+    create_menu_button_sized(world, font, label, y, 260.0)
+}
+
+pub fn create_menu_button_sized(
+    world: &mut World,
+    font: &FontHandle,
+    label: &str,
+    y: f32,
+    width: f32,
+) -> Entity {
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            format!("button_{}", label),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.0,
+            y,
+            10.0,
+            width,
+            40.0,
+        ))
+        .with(UiImage::SolidColor([0.1, 0.15, 0.25, 0.9]))
+        .with(UiText::new(
+            font.clone(),
+            label.to_string(),
+            [1.0, 1.0, 1.0, 1.0],
+            22.0,
+            LineMode::Single,
+            Anchor::Middle,
+        ))
+        .with(Interactable)
+        .build()
+}
+
+//Replaces the text of a menu line, used for status lines
+pub fn set_menu_text(world: &mut World, entity: Entity, text: &str) {
+    if let Some(ui_text) = world.write_storage::<UiText>().get_mut(entity) {
+        ui_text.text = text.to_string();
+    }
+}
+
+pub fn delete_menu(world: &mut World, entities: &mut Vec<Entity>) {
+    for entity in entities.drain(..) {
+        let _ = world.delete_entity(entity);
+    }
+}
@@ -0,0 +1,77 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::save::SaveGame;
+use crate::states::{
+    create_menu_button, create_menu_text, delete_menu, menu_font, set_menu_text, GameStateEvent, GameTrans,
+};
+
+//Pause overlay pushed on top of the SectorState, the sector keeps rendering underneath
+#[derive(Default)]
+pub struct PauseState {
+    resume: Option<Entity>,
+    save: Option<Entity>,
+    main_menu: Option<Entity>,
+    status: Option<Entity>,
+    ui: Vec<Entity>,
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for PauseState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        let world = data.world;
+        let font = menu_font(world);
+        let title = create_menu_text(world, &font, "Paused", 120.0, 40.0);
+        let resume = create_menu_button(world, &font, "Resume", 30.0);
+        let save = create_menu_button(world, &font, "Save Game", -20.0);
+        let main_menu = create_menu_button(world, &font, "Main Menu", -70.0);
+        let status = create_menu_text(world, &font, "", -120.0, 18.0);
+
+        self.resume = Some(resume);
+        self.save = Some(save);
+        self.main_menu = Some(main_menu);
+        self.status = Some(status);
+        self.ui = vec![title, resume, save, main_menu, status];
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.resume {
+                    Trans::Pop
+                } else if Some(target) == self.save {
+                    let status = match SaveGame::capture(data.world).save_to_disk() {
+                        Ok(()) => "Game saved".to_string(),
+                        Err(err) => format!("Failed to save game: {}", err),
+                    };
+                    if let Some(entity) = self.status {
+                        set_menu_text(data.world, entity, &status);
+                    }
+                    Trans::None
+                } else if Some(target) == self.main_menu {
+                    // Pop the pause overlay and then the sector itself
+                    Trans::Sequence(vec![Trans::Pop, Trans::Pop])
+                } else {
+                    Trans::None
+                }
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
@@ -0,0 +1,275 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    input::{
+        is_key_down, Axis, BindingError, Bindings, Button, InputEvent, InputHandler, ScrollDirection,
+        VirtualKeyCode,
+    },
+    prelude::*,
+    ui::{FontHandle, UiEvent, UiEventType, UiTransform},
+};
+
+use crate::input::{
+    clear_action, describe_axis, describe_button, save_user_bindings, ActionBinding, AxisBinding,
+    GameBindings,
+};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    set_menu_text, GameStateEvent, GameTrans,
+};
+
+//Which binding is waiting for the next input
+enum Capture {
+    Action(ActionBinding),
+    AxisPositive(AxisBinding),
+    AxisNegative(AxisBinding, Button),
+}
+
+//Settings screen pushed from the main menu, click a binding then press the new input
+#[derive(Default)]
+pub struct SettingsState {
+    back: Option<Entity>,
+    status: Option<Entity>,
+    axis_rows: Vec<(Entity, AxisBinding)>,
+    action_rows: Vec<(Entity, ActionBinding)>,
+    capture: Option<Capture>,
+    ui: Vec<Entity>,
+}
+
+impl SettingsState {
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.axis_rows.clear();
+        self.action_rows.clear();
+
+        let bindings = world
+            .read_resource::<InputHandler<GameBindings>>()
+            .bindings
+            .clone();
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Settings", 240.0, 40.0);
+        let status = create_menu_text(world, &font, status, 195.0, 18.0);
+        self.ui.push(title);
+        self.ui.push(status);
+        self.status = Some(status);
+
+        // Every variant is listed so unbound inputs can be fixed from here,
+        // axes on the left and actions on the right
+        let mut y = 140.0;
+        for id in AxisBinding::ALL.iter().cloned() {
+            let label = match bindings.axis(&id) {
+                Some(axis) => format!("{:?}: {}", id, describe_axis(axis)),
+                None => format!("{:?}: unbound", id),
+            };
+            let row = create_row(world, &font, &label, -COLUMN_OFFSET, y);
+            self.axis_rows.push((row, id));
+            self.ui.push(row);
+            y -= 45.0;
+        }
+        let axes_end = y;
+
+        let mut y = 140.0;
+        for id in ActionBinding::ALL.iter().cloned() {
+            let buttons = bindings
+                .action_bindings(&id)
+                .map(|combo| {
+                    combo
+                        .iter()
+                        .map(describe_button)
+                        .collect::<Vec<String>>()
+                        .join(" + ")
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            let buttons = if buttons.is_empty() {
+                "unbound".to_string()
+            } else {
+                buttons
+            };
+            let row = create_row(world, &font, &format!("{:?}: {}", id, buttons), COLUMN_OFFSET, y);
+            self.action_rows.push((row, id));
+            self.ui.push(row);
+            y -= 45.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y.min(axes_end) - 20.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn set_status(&self, world: &mut World, text: &str) {
+        if let Some(status) = self.status {
+            set_menu_text(world, status, text);
+        }
+    }
+
+    //Feeds a captured input into the pending binding, saving it once complete
+    fn capture_input(&mut self, world: &mut World, event: &InputEvent<GameBindings>) {
+        let capture = match self.capture.take() {
+            Some(capture) => capture,
+            None => return,
+        };
+        let mut bindings = world
+            .read_resource::<InputHandler<GameBindings>>()
+            .bindings
+            .clone();
+
+        let result = match (capture, captured_button(event), captured_axis(event)) {
+            (Capture::Action(id), Some(button), _) => {
+                clear_action(&mut bindings, &id);
+                bindings.insert_action_binding(id, vec![button])
+            }
+            (Capture::AxisPositive(id), _, Some(axis)) | (Capture::AxisNegative(id, _), _, Some(axis)) => {
+                replace_axis(&mut bindings, id, axis)
+            }
+            (Capture::AxisPositive(id), Some(pos), None) => {
+                self.set_status(world, &format!("Press the negative input for {:?}", id));
+                self.capture = Some(Capture::AxisNegative(id, pos));
+                return;
+            }
+            (Capture::AxisNegative(id, pos), Some(neg), None) => {
+                replace_axis(&mut bindings, id, Axis::Emulated { pos, neg })
+            }
+            // Not an input we can bind, keep waiting
+            (capture, None, None) => {
+                self.capture = Some(capture);
+                return;
+            }
+            (Capture::Action(id), None, Some(_)) => {
+                self.set_status(world, &format!("{:?} needs a button, not an axis", id));
+                self.capture = Some(Capture::Action(id));
+                return;
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                let status = match save_user_bindings(&bindings) {
+                    Ok(()) => "Binding saved".to_string(),
+                    Err(err) => format!("Binding applied but not saved: {}", err),
+                };
+                world
+                    .write_resource::<InputHandler<GameBindings>>()
+                    .bindings = bindings;
+                self.create_ui(world, &status);
+            }
+            Err(err) => self.set_status(world, &format!("Conflict: {}", err)),
+        }
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for SettingsState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Click a binding to change it");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => {
+                if self.capture.take().is_some() {
+                    self.set_status(data.world, "Cancelled");
+                    Trans::None
+                } else {
+                    Trans::Pop
+                }
+            }
+            StateEvent::Input(InputEvent::KeyPressed {
+                key_code: VirtualKeyCode::Escape,
+                ..
+            }) => Trans::None,
+            StateEvent::Input(event) => {
+                self.capture_input(data.world, &event);
+                Trans::None
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) if self.capture.is_none() => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, id)) = self.axis_rows.iter().find(|(row, _)| *row == target) {
+                    let id = *id;
+                    self.set_status(data.world, &format!("Press the positive input for {:?}", id));
+                    self.capture = Some(Capture::AxisPositive(id));
+                } else if let Some((_, id)) = self.action_rows.iter().find(|(row, _)| *row == target) {
+                    let id = *id;
+                    self.set_status(data.world, &format!("Press the new input for {:?}", id));
+                    self.capture = Some(Capture::Action(id));
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
+
+//Horizontal distance of each column of rows from the middle of the screen
+const COLUMN_OFFSET: f32 = 245.0;
+
+fn create_row(world: &mut World, font: &FontHandle, label: &str, x: f32, y: f32) -> Entity {
+    let row = create_menu_button_sized(world, font, label, y, 470.0);
+    if let Some(ui_transform) = world.write_storage::<UiTransform>().get_mut(row) {
+        ui_transform.local_x = x;
+    }
+    row
+}
+
+//Swaps in a new axis, restoring the old one if the new binding conflicts
+fn replace_axis(
+    bindings: &mut Bindings<GameBindings>,
+    id: AxisBinding,
+    axis: Axis,
+) -> Result<(), BindingError> {
+    let previous = bindings.remove_axis(&id);
+    match bindings.insert_axis(id, axis) {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            if let Some(previous) = previous {
+                let _ = bindings.insert_axis(id, previous);
+            }
+            Err(err)
+        }
+    }
+}
+
+fn captured_button(event: &InputEvent<GameBindings>) -> Option<Button> {
+    match event {
+        InputEvent::KeyPressed { key_code, .. } => Some(Button::Key(*key_code)),
+        InputEvent::MouseButtonPressed(mouse_button) => Some(Button::Mouse(*mouse_button)),
+        InputEvent::ControllerButtonPressed { which, button } => {
+            Some(Button::Controller(*which, *button))
+        }
+        _ => None,
+    }
+}
+
+//Inputs that can only be bound as a whole axis
+fn captured_axis(event: &InputEvent<GameBindings>) -> Option<Axis> {
+    match event {
+        InputEvent::MouseWheelMoved(direction) => Some(Axis::MouseWheel {
+            horizontal: match direction {
+                ScrollDirection::ScrollLeft | ScrollDirection::ScrollRight => true,
+                _ => false,
+            },
+        }),
+        InputEvent::ControllerAxisMoved { which, axis, value } if value.abs() > 0.5 => {
+            Some(Axis::Controller {
+                controller_id: *which,
+                axis: *axis,
+                invert: *value < 0.0,
+                dead_zone: 0.1,
+            })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,116 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::config::{ShipClass, ShipyardConfig};
+use crate::solar_lords::{buy_ship, Player, Ship};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    GameStateEvent, GameTrans,
+};
+
+//Hull list pushed from a station, click a hull to buy it or switch to it
+#[derive(Default)]
+pub struct ShipyardState {
+    back: Option<Entity>,
+    ship_rows: Vec<(Entity, ShipClass)>,
+    ui: Vec<Entity>,
+}
+
+impl ShipyardState {
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.ship_rows.clear();
+
+        let ships = world.read_resource::<ShipyardConfig>().ships.clone();
+        let (current, hangar, wealth) = {
+            let players = world.read_storage::<Player>();
+            let hulls = world.read_storage::<Ship>();
+            (&players, &hulls)
+                .join()
+                .map(|(player, ship)| (ship.class.name.clone(), player.hangar.clone(), player.wealth))
+                .next()
+                .unwrap_or_default()
+        };
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Shipyard", 220.0, 40.0);
+        let status = create_menu_text(world, &font, status, 175.0, 18.0);
+        let summary = create_menu_text(world, &font, &format!("Flying the {}, wealth {:.0}", current, wealth), 145.0, 18.0);
+        self.ui.extend(vec![title, status, summary]);
+
+        let mut y = 95.0;
+        for class in ships.into_iter() {
+            let state = if class.name == current {
+                "flying".to_string()
+            } else if hangar.contains(&class.name) {
+                "owned".to_string()
+            } else {
+                format!("{:.0}", class.price)
+            };
+            let label = format!(
+                "{} ({}): hull {}, shield {}, cargo {}, speed {:.1}",
+                class.name, state, class.hull, class.shield, class.cargo, class.speed
+            );
+            let row = create_menu_button_sized(world, &font, &label, y, 760.0);
+            self.ship_rows.push((row, class));
+            self.ui.push(row);
+            y -= 50.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y - 10.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn choose(&mut self, world: &mut World, class: &ShipClass) {
+        let ship = (&world.entities(), &world.read_storage::<Player>())
+            .join()
+            .map(|(entity, _)| entity)
+            .next();
+        let status = match ship.map(|ship| buy_ship(world, ship, class)) {
+            Some(Ok(())) => format!("Now flying the {}", class.name),
+            Some(Err(err)) => err,
+            None => return,
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for ShipyardState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Hulls you own can be switched to for free");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, class)) = self.ship_rows.iter().find(|(row, _)| *row == target) {
+                    let class = class.clone();
+                    self.choose(data.world, &class);
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
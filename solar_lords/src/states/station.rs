@@ -0,0 +1,145 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+
+use crate::config::FuelConfig;
+use crate::solar_lords::{Fuel, Player};
+use crate::states::{
+    create_menu_button, create_menu_text, delete_menu, menu_font, ContractsState, FittingState, GameStateEvent, GameTrans,
+    MarketState, ShipyardState,
+};
+
+//Station services, pushed by the SectorState while the ship is docked.
+//Leaving the screen undocks the ship.
+pub struct StationState {
+    station: String,
+    refuel: Option<Entity>,
+    market: Option<Entity>,
+    contracts: Option<Entity>,
+    shipyard: Option<Entity>,
+    fitting: Option<Entity>,
+    undock: Option<Entity>,
+    ui: Vec<Entity>,
+}
+
+impl StationState {
+    pub fn new(station: String) -> StationState {
+        StationState {
+            station,
+            refuel: None,
+            market: None,
+            contracts: None,
+            shipyard: None,
+            fitting: None,
+            undock: None,
+            ui: Vec::new(),
+        }
+    }
+
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        let price = world.read_resource::<FuelConfig>().station_price;
+        let (fuel, capacity, wealth) = {
+            let players = world.read_storage::<Player>();
+            let fuels = world.read_storage::<Fuel>();
+            (&players, &fuels)
+                .join()
+                .map(|(player, fuel)| (fuel.amount, fuel.capacity, player.wealth))
+                .next()
+                .unwrap_or((0.0, 0.0, 0.0))
+        };
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, &self.station, 120.0, 40.0);
+        let status = create_menu_text(world, &font, status, 75.0, 18.0);
+        let summary = create_menu_text(
+            world,
+            &font,
+            &format!("Fuel {:.0}/{:.0}  Wealth {:.0}  Price {:.1} per unit", fuel, capacity, wealth, price),
+            45.0,
+            18.0,
+        );
+        let refuel = create_menu_button(world, &font, "Refuel", -10.0);
+        let market = create_menu_button(world, &font, "Market", -60.0);
+        let contracts = create_menu_button(world, &font, "Contracts", -110.0);
+        let shipyard = create_menu_button(world, &font, "Shipyard", -160.0);
+        let fitting = create_menu_button(world, &font, "Fitting", -210.0);
+        let undock = create_menu_button(world, &font, "Undock", -260.0);
+
+        self.refuel = Some(refuel);
+        self.market = Some(market);
+        self.contracts = Some(contracts);
+        self.shipyard = Some(shipyard);
+        self.fitting = Some(fitting);
+        self.undock = Some(undock);
+        self.ui = vec![title, status, summary, refuel, market, contracts, shipyard, fitting, undock];
+    }
+
+    fn refuel(&mut self, world: &mut World) {
+        let price = world.read_resource::<FuelConfig>().station_price;
+        let mut bought = 0.0;
+        for (player, fuel) in (&mut world.write_storage::<Player>(), &mut world.write_storage::<Fuel>()).join() {
+            bought += fuel.buy(&mut player.wealth, price);
+        }
+        let status = if bought > 0.0 {
+            format!("Bought {:.0} fuel", bought)
+        } else {
+            "Tank full or nothing to pay with".to_string()
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for StationState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Docked");
+    }
+
+    //Back from another screen, the fuel tank or wealth may have changed
+    fn on_resume(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Docked");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+        for player in (&mut data.world.write_storage::<Player>()).join() {
+            player.docked = None;
+        }
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.undock {
+                    Trans::Pop
+                } else if Some(target) == self.refuel {
+                    self.refuel(data.world);
+                    Trans::None
+                } else if Some(target) == self.market {
+                    Trans::Push(Box::new(MarketState::new(self.station.clone())))
+                } else if Some(target) == self.contracts {
+                    Trans::Push(Box::new(ContractsState::new(self.station.clone())))
+                } else if Some(target) == self.shipyard {
+                    Trans::Push(Box::new(ShipyardState::default()))
+                } else if Some(target) == self.fitting {
+                    Trans::Push(Box::new(FittingState::default()))
+                } else {
+                    Trans::None
+                }
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
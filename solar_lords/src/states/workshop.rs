@@ -0,0 +1,153 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join},
+    input::{is_key_down, InputEvent, VirtualKeyCode},
+    prelude::*,
+    ui::{UiEvent, UiEventType},
+};
+use std::fmt::Debug;
+
+use crate::config::RecipeBook;
+use crate::input::ActionBinding;
+use crate::solar_lords::{craft_module, Player, Refinery};
+use crate::states::{
+    create_menu_button, create_menu_button_sized, create_menu_text, delete_menu, menu_font,
+    GameStateEvent, GameTrans,
+};
+
+//Refinery and crafting screen pushed on top of the SectorState, the simulation is paused underneath
+#[derive(Default)]
+pub struct WorkshopState {
+    back: Option<Entity>,
+    recipe_rows: Vec<(Entity, String)>,
+    blueprint_rows: Vec<(Entity, usize)>,
+    ui: Vec<Entity>,
+}
+
+impl WorkshopState {
+    fn create_ui(&mut self, world: &mut World, status: &str) {
+        delete_menu(world, &mut self.ui);
+        self.recipe_rows.clear();
+        self.blueprint_rows.clear();
+
+        let book = world.read_resource::<RecipeBook>().clone();
+        let enabled = {
+            let refineries = world.read_storage::<Refinery>();
+            let players = world.read_storage::<Player>();
+            (&players, &refineries)
+                .join()
+                .map(|(_, refinery)| refinery.enabled.clone())
+                .next()
+                .unwrap_or_default()
+        };
+        let font = menu_font(world);
+
+        let title = create_menu_text(world, &font, "Workshop", 300.0, 40.0);
+        let status = create_menu_text(world, &font, status, 260.0, 18.0);
+        let refinery = create_menu_text(world, &font, "Refinery", 220.0, 24.0);
+        self.ui.extend(vec![title, status, refinery]);
+
+        // Clicking a recipe switches it on or off
+        let mut y = 180.0;
+        for recipe in book.refining.iter() {
+            let label = format!(
+                "[{}] {}: {} -> {}, {:.0}s",
+                if enabled.contains(&recipe.name) { "on" } else { "off" },
+                recipe.name,
+                describe_amounts(&recipe.inputs),
+                describe_amounts(&recipe.outputs),
+                recipe.seconds
+            );
+            let row = create_menu_button_sized(world, &font, &label, y, 640.0);
+            self.recipe_rows.push((row, recipe.name.clone()));
+            self.ui.push(row);
+            y -= 42.0;
+        }
+
+        let crafting = create_menu_text(world, &font, "Crafting", y - 10.0, 24.0);
+        self.ui.push(crafting);
+        y -= 50.0;
+        for (index, blueprint) in book.crafting.iter().enumerate() {
+            let label = format!("{}: {}", blueprint.name, describe_amounts(&blueprint.cost));
+            let row = create_menu_button_sized(world, &font, &label, y, 640.0);
+            self.blueprint_rows.push((row, index));
+            self.ui.push(row);
+            y -= 42.0;
+        }
+
+        let back = create_menu_button(world, &font, "Back", y - 10.0);
+        self.back = Some(back);
+        self.ui.push(back);
+    }
+
+    fn toggle_recipe(&mut self, world: &mut World, recipe: &str) {
+        for (_, refinery) in (&world.read_storage::<Player>(), &mut world.write_storage::<Refinery>()).join() {
+            refinery.toggle(recipe);
+        }
+        self.create_ui(world, &format!("Toggled {}", recipe));
+    }
+
+    fn craft(&mut self, world: &mut World, index: usize) {
+        let blueprint = match world.read_resource::<RecipeBook>().crafting.get(index) {
+            Some(blueprint) => blueprint.clone(),
+            None => return,
+        };
+        let ship = (&world.entities(), &world.read_storage::<Player>())
+            .join()
+            .map(|(entity, _)| entity)
+            .next();
+        let status = match ship.map(|ship| craft_module(world, ship, &blueprint)) {
+            Some(Ok(())) => format!("Built {}, fit it at a station", blueprint.name),
+            Some(Err(err)) => err,
+            None => return,
+        };
+        self.create_ui(world, &status);
+    }
+}
+
+impl<'a, 'b> State<GameData<'a, 'b>, GameStateEvent> for WorkshopState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        self.create_ui(data.world, "Click a recipe to switch it on or off");
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'a, 'b>>) {
+        delete_menu(data.world, &mut self.ui);
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'a, 'b>>, event: GameStateEvent) -> GameTrans<'a, 'b> {
+        match event {
+            StateEvent::Window(event) if is_key_down(&event, VirtualKeyCode::Escape) => Trans::Pop,
+            StateEvent::Input(InputEvent::ActionPressed(ActionBinding::Workshop)) => Trans::Pop,
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.back {
+                    return Trans::Pop;
+                }
+                if let Some((_, recipe)) = self.recipe_rows.iter().find(|(row, _)| *row == target) {
+                    let recipe = recipe.clone();
+                    self.toggle_recipe(data.world, &recipe);
+                } else if let Some((_, index)) = self.blueprint_rows.iter().find(|(row, _)| *row == target) {
+                    let index = *index;
+                    self.craft(data.world, index);
+                }
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn update(&mut self, data: StateData<'_, GameData<'a, 'b>>) -> GameTrans<'a, 'b> {
+        data.data.update(&data.world);
+        Trans::None
+    }
+}
+
+//e.g. "10 Iron, 5 Copper"
+fn describe_amounts<T: Debug>(amounts: &[(T, u32)]) -> String {
+    amounts
+        .iter()
+        .map(|(item, amount)| format!("{} {:?}", amount, item))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -2,10 +2,12 @@ use amethyst::{
     core::{timing::Time, transform::Transform, math::{Vector2, Vector3, Matrix4}},
     derive::SystemDesc,
     ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
-    input::{InputHandler, StringBindings, VirtualKeyCode},
+    input::VirtualKeyCode,
 };
 
+use crate::input::PlayerInput;
 use crate::solar_lords::{Background, PlayerCamera};
+use crate::systems::player_control::MOVE_DEAD_ZONE;
 
 #[derive(SystemDesc)]
 pub struct BackgroundMovementSystem;
@@ -15,33 +17,32 @@ impl<'s> System<'s> for BackgroundMovementSystem {
         ReadStorage<'s, PlayerCamera>,
         ReadStorage<'s, Background>,
         WriteStorage<'s, Transform>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, PlayerInput>,
     );
 
     fn run(&mut self, (cameras, backgrounds, mut transforms, input): Self::SystemData){
-        let x_move = input.axis_value("entity_x").unwrap();
-        let y_move = input.axis_value("entity_y").unwrap();
-        let rotation = (x_move as i32, y_move as i32);
-        
-        for (background, transform) in (&backgrounds, &mut transforms).join() {
-            transform.prepend_translation_x(background.movement_speed * x_move);
-            transform.prepend_translation_y(background.movement_speed * y_move);
-        }
+        let x_move = input.move_x;
+        let y_move = input.move_y;
 
-        for (camera, transform) in (&cameras, &mut transforms).join() {
-            transform.set_rotation_2d(match rotation {
-                (1,0) => -std::f32::consts::PI*3.0/2.0,
-                (1,1) => std::f32::consts::PI/4.0,
-                (0,1) => 0.0,
-                (-1,1) => std::f32::consts::PI*7.0/4.0,
-                (-1,0) => std::f32::consts::PI*3.0/2.0,
-                (-1,-1) => std::f32::consts::PI*5.0/4.0,
-                (0,-1) => std::f32::consts::PI,
-                (1,-1) => std::f32::consts::PI*3.0/4.0,
-                (_,_) => continue,
-            });
+        // Layers are placed from the camera's world position so they tile forever
+        let camera_position = (&cameras, &transforms).join().next().map(|(_, transform)| {
+            let global = transform.global_matrix();
+            (global[(0, 3)], global[(1, 3)])
+        });
 
+        if let Some((camera_x, camera_y)) = camera_position {
+            for (background, transform) in (&backgrounds, &mut transforms).join() {
+                let (x, y) = background.position_for(camera_x, camera_y);
+                transform.set_translation_x(x);
+                transform.set_translation_y(y);
+            }
+        }
 
+        for (camera, transform) in (&cameras, &mut transforms).join() {
+            // Counter the ship's rotation so the view stays upright
+            if x_move.abs() > MOVE_DEAD_ZONE || y_move.abs() > MOVE_DEAD_ZONE {
+                transform.set_rotation_2d(std::f32::consts::FRAC_PI_2 - y_move.atan2(x_move));
+            }
         }
     }
 }
\ No newline at end of file
@@ -1,9 +1,10 @@
+use crate::input::{AxisBinding, GameBindings};
 use crate::solar_lords::PlayerCamera;
 use amethyst::{
     core::{timing::Time, transform::Transform, math::{Vector2, Vector3, Matrix4}},
     derive::SystemDesc,
     ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
-    input::{InputHandler, StringBindings},
+    input::InputHandler,
 };
 
 
@@ -15,11 +16,11 @@ impl<'s> System<'s> for ZoomSystem {
     type SystemData = (
         ReadStorage<'s, PlayerCamera>,
         WriteStorage<'s, Transform>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, InputHandler<GameBindings>>,
     );
 
     fn run(&mut self, (cameras, mut transforms, input): Self::SystemData){
-        let zoom_level = input.axis_value("mouse_scroll").unwrap();
+        let zoom_level = input.axis_value(&AxisBinding::Zoom).unwrap_or(0.0);
 
         for (camera, transform) in (&cameras, &mut transforms).join() {
             
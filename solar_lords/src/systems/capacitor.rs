@@ -0,0 +1,21 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::solar_lords::Capacitor;
+
+//Recharges every ship's capacitor
+#[derive(SystemDesc)]
+pub struct CapacitorSystem;
+
+impl<'s> System<'s> for CapacitorSystem {
+    type SystemData = (WriteStorage<'s, Capacitor>, Read<'s, Time>);
+
+    fn run(&mut self, (mut capacitors, time): Self::SystemData) {
+        for capacitor in (&mut capacitors).join() {
+            capacitor.charge = (capacitor.charge + capacitor.recharge * time.delta_seconds()).min(capacitor.capacity);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Entities, Entity, Join, Read, System, SystemData, WriteStorage},
+};
+use std::collections::HashMap;
+
+use crate::collision::{dot, length, WorldShape};
+use crate::config::CollisionConfig;
+use crate::solar_lords::{Collider, Npc, Player, Velocity};
+
+//A collider placed in the world for this tick
+struct Body {
+    entity: Entity,
+    shape: WorldShape,
+    velocity: (f32, f32),
+    inverse_mass: f32,
+}
+
+//Finds overlapping colliders on a grid, pushes them apart, bounces them
+//off each other and damages ships that hit too hard. Weapons hit instantly
+//(see CombatSystem), so there are no projectiles to collide.
+#[derive(SystemDesc)]
+pub struct CollisionSystem;
+
+impl<'s> System<'s> for CollisionSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Collider>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Velocity>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Npc>,
+        Read<'s, CollisionConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, mut colliders, mut transforms, mut velocities, mut players, mut npcs, config, time): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        let mut bodies = Vec::new();
+        for (entity, collider, transform) in (&entities, &colliders, &transforms).join() {
+            let shape = collider.shape.placed(transform);
+            let position = (transform.translation().x, transform.translation().y);
+            let velocity = match collider.last_position {
+                Some(last) if delta > 0.0 => ((position.0 - last.0) / delta, (position.1 - last.1) / delta),
+                _ => (0.0, 0.0),
+            };
+            // Anything that can't be moved acts as if it had infinite mass
+            let inverse_mass = if velocities.contains(entity) && collider.mass > 0.0 {
+                1.0 / collider.mass
+            } else {
+                0.0
+            };
+            bodies.push(Body {
+                entity,
+                shape,
+                velocity,
+                inverse_mass,
+            });
+        }
+
+        // Broadphase, only colliders sharing a grid cell are tested
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (index, body) in bodies.iter().enumerate() {
+            let (x, y) = body.shape.center();
+            let radius = body.shape.bounding_radius();
+            let cell = |value: f32| (value / config.cell_size).floor() as i32;
+            for cell_x in cell(x - radius)..=cell(x + radius) {
+                for cell_y in cell(y - radius)..=cell(y + radius) {
+                    cells.entry((cell_x, cell_y)).or_insert_with(Vec::new).push(index);
+                }
+            }
+        }
+        let mut pairs = Vec::new();
+        for indices in cells.values() {
+            for (position, first) in indices.iter().enumerate() {
+                for second in indices[position + 1..].iter() {
+                    pairs.push((*first.min(second), *first.max(second)));
+                }
+            }
+        }
+        // Sorted so the same pairs resolve in the same order every run
+        pairs.sort();
+        pairs.dedup();
+
+        for (first, second) in pairs {
+            let total_inverse_mass = bodies[first].inverse_mass + bodies[second].inverse_mass;
+            if total_inverse_mass <= 0.0 {
+                continue;
+            }
+            let (a, b) = (&bodies[first], &bodies[second]);
+            let reach = a.shape.bounding_radius() + b.shape.bounding_radius();
+            let (center_a, center_b) = (a.shape.center(), b.shape.center());
+            if length((center_b.0 - center_a.0, center_b.1 - center_a.1)) > reach {
+                continue;
+            }
+            let contact = match a.shape.contact(&b.shape) {
+                Some(contact) => contact,
+                None => continue,
+            };
+            let normal = contact.normal;
+
+            // Push them apart, the lighter one moves further
+            for (index, direction) in [(first, -1.0), (second, 1.0)].iter() {
+                let share = bodies[*index].inverse_mass / total_inverse_mass * contact.depth * direction;
+                if let Some(transform) = transforms.get_mut(bodies[*index].entity) {
+                    transform.prepend_translation_x(normal.0 * share);
+                    transform.prepend_translation_y(normal.1 * share);
+                }
+            }
+
+            // Only bounce if they are still moving into each other
+            let (velocity_a, velocity_b) = (bodies[first].velocity, bodies[second].velocity);
+            let closing = dot((velocity_a.0 - velocity_b.0, velocity_a.1 - velocity_b.1), normal);
+            if closing <= 0.0 {
+                continue;
+            }
+            let impulse = (1.0 + config.restitution) * closing / total_inverse_mass;
+            for (index, direction) in [(first, -1.0), (second, 1.0)].iter() {
+                let body = &mut bodies[*index];
+                let change = impulse * body.inverse_mass * direction;
+                body.velocity.0 += normal.0 * change;
+                body.velocity.1 += normal.1 * change;
+                if let Some(velocity) = velocities.get_mut(body.entity) {
+                    velocity.x += normal.0 * change;
+                    velocity.y += normal.1 * change;
+                }
+            }
+
+            let damage = ((closing - config.damage_threshold).max(0.0) * config.damage_per_speed) as u32;
+            if damage > 0 {
+                for index in [first, second].iter() {
+                    if let Some(player) = players.get_mut(bodies[*index].entity) {
+                        player.take_damage(damage);
+                    } else if let Some(npc) = npcs.get_mut(bodies[*index].entity) {
+                        npc.take_damage(damage);
+                    }
+                }
+            }
+        }
+
+        for (collider, transform) in (&mut colliders, &transforms).join() {
+            collider.last_position = Some((transform.translation().x, transform.translation().y));
+        }
+    }
+}
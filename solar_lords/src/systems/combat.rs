@@ -0,0 +1,173 @@
+use amethyst::{
+    core::{math::Vector3, timing::Time, transform::Transform, Named},
+    derive::SystemDesc,
+    ecs::prelude::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::SpriteRender,
+    shrev::EventChannel,
+};
+
+use crate::config::FactionConfig;
+use crate::solar_lords::{
+    Cargo, Fitting, GameEvent, MessageLog, Npc, NpcGoal, Ore, Player, PowerGrid, Reputation, Velocity,
+};
+use super::pickup::eject_pickup;
+
+//Player weapon fire at the targeted ship, armed NPCs shooting at whatever they hunt,
+//and wrecking ships that run out of hull. Shooting at a faction's ships costs standing with it.
+#[derive(SystemDesc)]
+pub struct CombatSystem;
+
+impl<'s> System<'s> for CombatSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Npc>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, PowerGrid>,
+        WriteStorage<'s, Reputation>,
+        ReadStorage<'s, Fitting>,
+        ReadStorage<'s, Cargo>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Named>,
+        ReadStorage<'s, SpriteRender>,
+        Read<'s, FactionConfig>,
+        Read<'s, Time>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<GameEvent>>,
+        Write<'s, MessageLog>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut npcs,
+            mut players,
+            mut grids,
+            mut reputations,
+            fittings,
+            cargos,
+            transforms,
+            names,
+            sprites,
+            faction_config,
+            time,
+            lazy,
+            mut events,
+            mut messages,
+        ): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+
+        // The player's weapon hits the targeted ship while it is in range
+        for (shooter, player, grid, fitting, transform, reputation) in
+            (&entities, &players, &mut grids, &fittings, &transforms, (&mut reputations).maybe()).join()
+        {
+            let weapon = match fitting.weapon() {
+                Some(weapon) if grid.weapon => weapon,
+                _ => {
+                    grid.weapon_progress = 0.0;
+                    continue;
+                }
+            };
+            let target = match player.target.as_ref() {
+                Some(target) => target,
+                None => continue,
+            };
+            let here = *transform.translation();
+            let hit = (&mut npcs, &names, &transforms)
+                .join()
+                .find(|(_, name, _)| name.name == *target);
+            if let Some((npc, _, npc_transform)) = hit {
+                if distance(here, *npc_transform.translation()) <= weapon.range() {
+                    grid.weapon_progress += weapon.damage() * delta;
+                    let whole = grid.weapon_progress.floor();
+                    grid.weapon_progress -= whole;
+                    if whole > 0.0 {
+                        npc.take_damage(whole as u32);
+                        npc.last_attacker = Some(shooter);
+                        if let Some(reputation) = reputation {
+                            reputation.attacked(npc.faction(), whole as u32, &faction_config);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Armed ships open fire once their prey is in range
+        let mut hits = Vec::new();
+        for (shooter, npc, transform) in (&entities, &mut npcs, &transforms).join() {
+            let prey = match npc.goal {
+                NpcGoal::Hunt(prey) if npc.archetype.damage > 0.0 => prey,
+                _ => continue,
+            };
+            let in_range = transforms.get(prey).map_or(false, |prey_transform| {
+                distance(*transform.translation(), *prey_transform.translation()) <= npc.archetype.weapon_range
+            });
+            if !in_range {
+                npc.damage_progress = 0.0;
+                continue;
+            }
+            npc.damage_progress += npc.archetype.damage * delta;
+            let whole = npc.damage_progress.floor();
+            npc.damage_progress -= whole;
+            if whole > 0.0 {
+                hits.push((shooter, prey, whole as u32));
+            }
+        }
+        for (shooter, prey, amount) in hits {
+            if let Some(npc) = npcs.get_mut(prey) {
+                npc.take_damage(amount);
+                npc.last_attacker = Some(shooter);
+            } else if let Some(player) = players.get_mut(prey) {
+                if player.docked.is_none() {
+                    player.take_damage(amount);
+                }
+            }
+        }
+
+        // Wrecks spill their hold as pickups
+        for (entity, npc, transform, name, cargo, sprite) in
+            (&entities, &npcs, &transforms, &names, cargos.maybe(), sprites.maybe()).join()
+        {
+            if npc.hull > 0 {
+                continue;
+            }
+            entities.delete(entity).expect("Failed to delete wrecked ship");
+            messages.push(format!("{} destroyed", name.name));
+            let by_player = npc.last_attacker.map_or(false, |attacker| players.contains(attacker));
+            events.single_write(GameEvent::ShipDestroyed {
+                role: npc.role(),
+                faction: npc.faction(),
+                by_player,
+            });
+            if by_player {
+                for reputation in (&mut reputations).join() {
+                    reputation.wrecked(npc.faction(), &faction_config);
+                }
+            }
+            if let (Some(cargo), Some(sprite)) = (cargo, sprite) {
+                // In a fixed order so replays create the same pickups
+                for ore in Ore::ALL.iter().filter(|ore| cargo.amount(**ore) > 0) {
+                    eject_pickup(
+                        &lazy,
+                        &entities,
+                        sprite.sprite_sheet.clone(),
+                        *ore,
+                        cargo.amount(*ore),
+                        *transform.translation(),
+                        Velocity::default(),
+                    );
+                }
+            }
+            for player in (&mut players).join() {
+                if player.target.as_deref() == Some(name.name.as_ref()) {
+                    player.target = None;
+                }
+            }
+        }
+    }
+}
+
+fn distance(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    ((a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)).sqrt()
+}
@@ -0,0 +1,96 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, World, Write, WriteStorage},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::config::ContractConfig;
+use crate::solar_lords::{
+    Cargo, ContractKind, ContractStatus, GameEvent, MessageLog, Player, QuestLog, Reputation,
+};
+
+//Moves the player's contracts along from mining, combat and docking events,
+//pays out the finished ones and fails the ones that run out of time
+#[derive(SystemDesc, Default)]
+pub struct ContractSystem {
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'s> System<'s> for ContractSystem {
+    type SystemData = (
+        WriteStorage<'s, QuestLog>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Cargo>,
+        WriteStorage<'s, Reputation>,
+        Read<'s, EventChannel<GameEvent>>,
+        Read<'s, ContractConfig>,
+        Read<'s, Time>,
+        Write<'s, MessageLog>,
+    );
+
+    fn run(
+        &mut self,
+        (mut logs, mut players, mut cargos, mut reputations, channel, config, time, mut messages): Self::SystemData,
+    ) {
+        let events = match self.reader.as_mut() {
+            Some(reader) => channel.read(reader).cloned().collect::<Vec<_>>(),
+            None => return,
+        };
+        let delta = time.delta_seconds();
+
+        for (log, player, cargo, mut reputation) in
+            (&mut logs, &mut players, &mut cargos, (&mut reputations).maybe()).join()
+        {
+            for contract in log.contracts.iter_mut() {
+                if contract.status != ContractStatus::Active {
+                    continue;
+                }
+                for event in events.iter() {
+                    match (&contract.kind, event) {
+                        (ContractKind::Deliver { ore, amount }, GameEvent::Docked { station })
+                            if *station == contract.station =>
+                        {
+                            // Hands over whatever is in the hold, up to what is still owed
+                            let handed = cargo.amount(*ore).min(amount.saturating_sub(contract.progress));
+                            cargo.take_ores(&[(*ore, handed)]);
+                            contract.progress += handed;
+                        }
+                        (ContractKind::Destroy { role, .. }, GameEvent::ShipDestroyed { role: destroyed, by_player: true, .. })
+                            if role == destroyed =>
+                        {
+                            contract.progress += 1;
+                        }
+                        (ContractKind::Survey { sector }, GameEvent::OreMined { sector: mined, .. }) if sector == mined => {
+                            contract.progress = 1;
+                        }
+                        _ => {}
+                    }
+                }
+
+                if contract.is_done() {
+                    contract.status = ContractStatus::Completed;
+                    player.wealth += contract.reward;
+                    if let Some(reputation) = reputation.as_mut() {
+                        reputation.change(contract.issuer, contract.standing);
+                    }
+                    messages.push(format!("Contract complete: {}, paid {:.0}", contract.describe(), contract.reward));
+                    continue;
+                }
+                contract.time_left -= delta;
+                if contract.time_left <= 0.0 {
+                    contract.status = ContractStatus::Failed;
+                    if let Some(reputation) = reputation.as_mut() {
+                        reputation.change(contract.issuer, -config.failure_penalty);
+                    }
+                    messages.push(format!("Contract failed: {}", contract.describe()));
+                }
+            }
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<EventChannel<GameEvent>>().register_reader());
+    }
+}
@@ -0,0 +1,86 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::config::EventConfig;
+use crate::director::{choose_event, next_wait, plan_event};
+use crate::solar_lords::{Comet, Player, SectorEventKind, SectorEvents, Velocity};
+
+//Counts down to the sector's next event and plans what it brings in, drains
+//shields while a solar flare lasts and sees comets across the sector
+#[derive(SystemDesc)]
+pub struct EventDirectorSystem;
+
+impl<'s> System<'s> for EventDirectorSystem {
+    type SystemData = (
+        Entities<'s>,
+        Option<Write<'s, SectorEvents>>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Comet>,
+        WriteStorage<'s, Velocity>,
+        ReadStorage<'s, Transform>,
+        Read<'s, EventConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, events, mut players, mut comets, mut velocities, transforms, config, time): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+
+        // Comets hold their course against drag until they have crossed the sector
+        for (entity, comet, velocity) in (&entities, &mut comets, &mut velocities).join() {
+            *velocity = comet.velocity;
+            comet.time_left -= delta;
+            if comet.time_left <= 0.0 {
+                entities.delete(entity).ok();
+            }
+        }
+
+        let mut events = match events {
+            Some(events) => events,
+            None => return,
+        };
+        if let Some((_, since)) = events.last_event.as_mut() {
+            *since += delta;
+        }
+
+        if events.flare > 0.0 {
+            events.flare = (events.flare - delta).max(0.0);
+            events.flare_progress += config.flare_drain * delta;
+            let drained = events.flare_progress.floor();
+            events.flare_progress -= drained;
+            // Docked ships are shielded by the station
+            for player in (&mut players).join().filter(|player| player.docked.is_none()) {
+                player.sheild = player.sheild.saturating_sub(drained as u32);
+            }
+        }
+
+        events.next_event -= delta;
+        if events.next_event > 0.0 {
+            return;
+        }
+        let danger = events.danger;
+        events.next_event = next_wait(&mut events.rng, &config, danger);
+        let kind = match choose_event(&mut events.rng, &config, danger) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let center = (&players, &transforms)
+            .join()
+            .map(|(_, transform)| (transform.translation().x, transform.translation().y))
+            .next()
+            .unwrap_or((0.0, 0.0));
+
+        let spawns = plan_event(&mut events.rng, &config, kind, center);
+        events.pending.extend(spawns);
+        if kind == SectorEventKind::SolarFlare {
+            events.flare = config.flare_duration;
+        }
+        // The hud shows the warning for a few seconds from here
+        events.last_event = Some((kind, 0.0));
+    }
+}
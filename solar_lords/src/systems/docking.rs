@@ -0,0 +1,71 @@
+use amethyst::{
+    core::transform::Transform,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    shrev::EventChannel,
+};
+
+use crate::config::{FactionConfig, FuelConfig};
+use crate::input::PlayerInput;
+use crate::solar_lords::{GameEvent, MessageLog, Player, Reputation, Station, Velocity};
+
+//Docks the ship at the closest station in range when Dock is pressed,
+//unless the station authority has turned against the player
+#[derive(SystemDesc)]
+pub struct DockingSystem;
+
+impl<'s> System<'s> for DockingSystem {
+    type SystemData = (
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Velocity>,
+        ReadStorage<'s, Station>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Reputation>,
+        Read<'s, PlayerInput>,
+        Read<'s, FuelConfig>,
+        Read<'s, FactionConfig>,
+        Write<'s, EventChannel<GameEvent>>,
+        Write<'s, MessageLog>,
+    );
+
+    fn run(
+        &mut self,
+        (mut players, mut velocities, stations, transforms, reputations, input, config, faction_config, mut events, mut messages): Self::SystemData,
+    ) {
+        if !input.dock {
+            return;
+        }
+        for (player, transform, velocity, reputation) in
+            (&mut players, &transforms, (&mut velocities).maybe(), reputations.maybe()).join()
+        {
+            let position = transform.translation();
+            let closest = (&stations, &transforms)
+                .join()
+                .map(|(station, station_transform)| {
+                    let offset = station_transform.translation() - position;
+                    (station, (offset.x * offset.x + offset.y * offset.y).sqrt())
+                })
+                .filter(|(_, distance)| *distance <= config.dock_range)
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            match closest {
+                Some((station, _)) if reputation.map_or(false, |reputation| !reputation.can_dock(&faction_config)) => {
+                    messages.push(format!(
+                        "{} refused docking, standing with the Station Authority is too low",
+                        station.name
+                    ));
+                }
+                Some((station, _)) => {
+                    player.docked = Some(station.name.clone());
+                    events.single_write(GameEvent::Docked {
+                        station: station.name.clone(),
+                    });
+                    if let Some(velocity) = velocity {
+                        *velocity = Velocity::default();
+                    }
+                }
+                None => messages.push("No station within docking range".to_string()),
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::config::AstroidConfig;
+use crate::solar_lords::Velocity;
+
+//Moves anything with a Velocity and slows it down over time
+#[derive(SystemDesc)]
+pub struct DriftSystem;
+
+impl<'s> System<'s> for DriftSystem {
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Velocity>,
+        Read<'s, Time>,
+        Read<'s, AstroidConfig>,
+    );
+
+    fn run(&mut self, (mut transforms, mut velocities, time, config): Self::SystemData) {
+        let delta = time.delta_seconds();
+        let damping = (1.0 - config.drag * delta).max(0.0);
+        for (transform, velocity) in (&mut transforms, &mut velocities).join() {
+            transform.prepend_translation_x(velocity.x * delta);
+            transform.prepend_translation_y(velocity.y * delta);
+            velocity.x *= damping;
+            velocity.y *= damping;
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use amethyst::{
+    core::{math::Vector3, transform::Transform, Named, WithNamed},
+    derive::SystemDesc,
+    ecs::prelude::{Builder, Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
+    renderer::SpriteRender,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::hash_map::DefaultHasher;
+use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
+
+use crate::config::AstroidConfig;
+use crate::solar_lords::{Astroid, Collider, ContactKind, Player, RadarContact, SectorSeed, Velocity};
+use super::pickup::eject_pickup;
+
+//Distance from the old centre each piece starts at
+const FRAGMENT_SPREAD: f32 = 10.0;
+
+//Breaks mined down astroids into smaller ones, and the smallest into ore pickups
+#[derive(SystemDesc)]
+pub struct FragmentationSystem;
+
+impl<'s> System<'s> for FragmentationSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, SpriteRender>,
+        ReadStorage<'s, Named>,
+        WriteStorage<'s, Player>,
+        Read<'s, AstroidConfig>,
+        Option<Read<'s, SectorSeed>>,
+        Read<'s, LazyUpdate>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, astroids, transforms, sprites, names, mut players, config, sector_seed, lazy): Self::SystemData,
+    ) {
+        for (entity, astroid, transform, sprite, name) in (&entities, &astroids, &transforms, &sprites, &names).join() {
+            let split_at = config.size(astroid.size).split_at;
+            if astroid.resources == 0 || astroid.resources > split_at {
+                continue;
+            }
+            entities.delete(entity).expect("Failed to delete split astroid");
+
+            // Seeded from the sector and the astroid so replays split the same way
+            let mut hasher = DefaultHasher::new();
+            name.name.hash(&mut hasher);
+            let seed = sector_seed.as_ref().map_or(0, |seed| seed.0) ^ hasher.finish();
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            // Rock mined but not yet broken off goes with the pieces
+            let remaining = astroid.resources + astroid.loose;
+            let pieces = config.size(astroid.size).pieces.max(1).min(remaining);
+            let first_angle = rng.gen_range(0.0, 2.0 * PI);
+            // Rubble yields what mining the rest of it would have, not its raw ore
+            let rubble = match astroid.size.smaller() {
+                Some(_) => Vec::new(),
+                None => astroid.shatter(),
+            };
+            for piece in 0..pieces {
+                // Share out what is left, the first pieces take the remainder
+                let share = remaining / pieces + if piece < remaining % pieces { 1 } else { 0 };
+                let angle = first_angle + 2.0 * PI * piece as f32 / pieces as f32;
+                let speed = config.scatter_speed * rng.gen_range(0.5, 1.0);
+
+                let mut piece_transform = transform.clone();
+                piece_transform.prepend_translation_x(angle.cos() * FRAGMENT_SPREAD);
+                piece_transform.prepend_translation_y(angle.sin() * FRAGMENT_SPREAD);
+
+                match astroid.size.smaller() {
+                    Some(size) => {
+                        let scale = config.size(size).scale;
+                        piece_transform.set_scale(Vector3::new(scale, scale, 1.0));
+                        let piece_name = format!("{}-{}", name.name, piece);
+
+                        // Keep mining without having to aim again
+                        if piece == 0 {
+                            for player in (&mut players).join() {
+                                if player.target.as_deref() == Some(name.name.as_ref()) {
+                                    player.target = Some(piece_name.clone());
+                                }
+                            }
+                        }
+
+                        let fragment = astroid.fragment(size, share);
+                        let piece_sprite = SpriteRender::new(sprite.sprite_sheet.clone(), fragment.sprite());
+                        lazy.create_entity(&entities)
+                            .with(fragment)
+                            .with(Collider::astroid(size))
+                            .with(RadarContact::new(ContactKind::Asteroid))
+                            .with(Velocity::new(angle.cos() * speed, angle.sin() * speed))
+                            .with(piece_transform)
+                            .with(piece_sprite)
+                            .named(piece_name)
+                            .build();
+                    }
+                    None => {
+                        // Each piece of rubble holds its share of every ore
+                        for (ore, total) in rubble.iter() {
+                            let amount = total / pieces + if piece < total % pieces { 1 } else { 0 };
+                            if amount == 0 {
+                                continue;
+                            }
+                            eject_pickup(
+                                &lazy,
+                                &entities,
+                                sprite.sprite_sheet.clone(),
+                                *ore,
+                                amount,
+                                *piece_transform.translation(),
+                                Velocity::new(angle.cos() * speed, angle.sin() * speed),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::config::FuelConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Cargo, Fuel, Good, Player};
+
+//Tops the tank up from refined fuel in the hold and answers distress calls
+#[derive(SystemDesc)]
+pub struct FuelSystem;
+
+impl<'s> System<'s> for FuelSystem {
+    type SystemData = (
+        WriteStorage<'s, Fuel>,
+        WriteStorage<'s, Cargo>,
+        WriteStorage<'s, Player>,
+        Read<'s, PlayerInput>,
+        Read<'s, FuelConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (mut fuels, mut cargos, mut players, input, config, time): Self::SystemData) {
+        for (fuel, cargo, player) in (&mut fuels, (&mut cargos).maybe(), (&mut players).maybe()).join() {
+            // Only whole units are used, and only once they fit in the tank
+            if let Some(cargo) = cargo {
+                let room = ((fuel.capacity - fuel.amount) / config.per_refined_unit).floor() as u32;
+                let units = room.min(cargo.good_amount(Good::Fuel));
+                if units > 0 && cargo.take_goods(&[(Good::Fuel, units)]) {
+                    fuel.refill(units as f32 * config.per_refined_unit);
+                }
+            }
+
+            if let Some(player) = player {
+                if input.distress && fuel.is_empty() && fuel.distress.is_none() {
+                    fuel.distress = Some(config.distress_delay);
+                }
+                if let Some(remaining) = fuel.distress {
+                    let remaining = remaining - time.delta_seconds();
+                    if remaining > 0.0 {
+                        fuel.distress = Some(remaining);
+                    } else {
+                        // The rescue takes what it is owed, or whatever the pilot has
+                        fuel.refill(config.distress_fuel);
+                        player.wealth = (player.wealth - config.distress_fee).max(0.0);
+                        fuel.distress = None;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use amethyst::{
+    core::{timing::Time, Named},
+    derive::SystemDesc,
+    ecs::prelude::{Entity, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    ui::{UiText, UiTransform},
+};
+use std::cmp::Ordering;
+
+use crate::solar_lords::{
+    Astroid, Capacitor, Cargo, Contract, Faction, Fitting, Fuel, Good, Heat, Hud, MessageLog, MiningTool, Npc, Player,
+    QuestLog, Refinery, Reputation, SectorEvents, HUD_BAR_WIDTH,
+};
+
+//Seconds an event's warning stays up
+const EVENT_WARNING_SECONDS: f32 = 5.0;
+
+#[derive(SystemDesc)]
+pub struct HudSystem;
+
+impl<'s> System<'s> for HudSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Cargo>,
+        ReadStorage<'s, Capacitor>,
+        ReadStorage<'s, Heat>,
+        ReadStorage<'s, Refinery>,
+        ReadStorage<'s, Fuel>,
+        ReadStorage<'s, Fitting>,
+        ReadStorage<'s, Reputation>,
+        ReadStorage<'s, QuestLog>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, Npc>,
+        ReadStorage<'s, Named>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiText>,
+        Option<Read<'s, Hud>>,
+        Option<Read<'s, SectorEvents>>,
+        Write<'s, MessageLog>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            players,
+            cargos,
+            capacitors,
+            heats,
+            refineries,
+            fuels,
+            fittings,
+            reputations,
+            logs,
+            astroids,
+            npcs,
+            names,
+            mut ui_transforms,
+            mut ui_texts,
+            hud,
+            events,
+            mut messages,
+            time,
+        ): Self::SystemData,
+    ) {
+        messages.tick(time.delta_seconds());
+        let hud = match hud {
+            Some(hud) => hud,
+            None => return,
+        };
+        // The event warning on top, then whatever the player has been told lately
+        let event = events.map(|events| describe_event(&events)).unwrap_or_default();
+        let lines = Some(event.as_str())
+            .filter(|event| !event.is_empty())
+            .into_iter()
+            .chain(messages.lines())
+            .collect::<Vec<_>>();
+        set_text(&mut ui_texts, hud.message_text, lines.join("\n"));
+
+        for (player, cargo, capacitor, heat, refinery, fuel, fitting, reputation, log) in (
+            &players,
+            &cargos,
+            capacitors.maybe(),
+            heats.maybe(),
+            refineries.maybe(),
+            fuels.maybe(),
+            fittings.maybe(),
+            reputations.maybe(),
+            logs.maybe(),
+        )
+            .join()
+        {
+            set_bar(&mut ui_transforms, hud.hull_bar, player.hull as f32, player.max_hull as f32);
+            set_bar(&mut ui_transforms, hud.shield_bar, player.sheild as f32, player.max_sheild as f32);
+            if let Some(capacitor) = capacitor {
+                set_bar(&mut ui_transforms, hud.energy_bar, capacitor.charge, capacitor.capacity);
+            }
+            if let Some(heat) = heat {
+                set_bar(&mut ui_transforms, hud.heat_bar, heat.heat, heat.capacity);
+                let label = if heat.overheated { "OVERHEAT" } else { "Heat" };
+                set_text(&mut ui_texts, hud.heat_text, label.to_string());
+            }
+            if let Some(fuel) = fuel {
+                set_bar(&mut ui_transforms, hud.fuel_bar, fuel.amount, fuel.capacity);
+                let label = if fuel.is_empty() { "EMPTY" } else { "Fuel" };
+                set_text(&mut ui_texts, hud.fuel_text, label.to_string());
+                let distress = match fuel.distress {
+                    Some(remaining) => format!("Distress call sent, help in {:.0}s", remaining),
+                    None if fuel.is_empty() => "Out of fuel, send a distress call".to_string(),
+                    None => String::new(),
+                };
+                set_text(&mut ui_texts, hud.distress_text, distress);
+            }
+
+            for (ore, bar) in hud.cargo_bars.iter() {
+                set_bar(&mut ui_transforms, *bar, cargo.amount(*ore) as f32, cargo.capacity as f32);
+            }
+            set_text(
+                &mut ui_texts,
+                hud.cargo_text,
+                format!("Cargo {}/{}", cargo.total(), cargo.capacity),
+            );
+            set_text(&mut ui_texts, hud.wealth_text, format!("Wealth: {:.0}", player.wealth));
+            set_text(&mut ui_texts, hud.goods_text, describe_goods(cargo));
+            if let Some(refinery) = refinery {
+                let status = match &refinery.job {
+                    Some(job) => format!("Refining {} ({:.0}s)", job.recipe, job.remaining.max(0.0)),
+                    None => "Refinery idle".to_string(),
+                };
+                set_text(&mut ui_texts, hud.refinery_text, status);
+            }
+            if let Some(reputation) = reputation {
+                set_text(&mut ui_texts, hud.standing_text, describe_standing(reputation));
+            }
+            if let Some(log) = log {
+                let active = log.active().map(Contract::describe_progress).collect::<Vec<_>>();
+                set_text(&mut ui_texts, hud.contracts_text, active.join("\n"));
+            }
+
+            // Look up the targeted astroid by name
+            let target = player.target.as_ref().and_then(|target| {
+                (&astroids, &names)
+                    .join()
+                    .find(|(_, name)| name.name == *target)
+            });
+            let target_text = match target {
+                Some((astroid, name)) if astroid.detailed => format!(
+                    "Target: {}\nOre: {}\nResources: {}\nYield: {}",
+                    name.name,
+                    describe_composition(astroid),
+                    astroid.resources,
+                    match fitting.and_then(|fitting| fitting.mining_tool()) {
+                        Some(tool) => describe_yield(astroid, tool),
+                        None => "no mining module".to_string(),
+                    }
+                ),
+                Some((astroid, name)) if astroid.scanned => format!(
+                    "Target: {}\nOre: {}\nResources: {}",
+                    name.name,
+                    describe_composition(astroid),
+                    astroid.resources
+                ),
+                Some((_, name)) => format!("Target: {}\nUnscanned", name.name),
+                None => {
+                    let ship = player.target.as_ref().and_then(|target| {
+                        (&npcs, &names).join().find(|(_, name)| name.name == *target)
+                    });
+                    match ship {
+                        Some((npc, name)) => format!(
+                            "Target: {}\n{} {:?}, hull {}/{}",
+                            name.name,
+                            npc.faction().label(),
+                            npc.role(),
+                            npc.hull,
+                            npc.archetype.hull
+                        ),
+                        None => "Target: none".to_string(),
+                    }
+                }
+            };
+            set_text(&mut ui_texts, hud.target_text, target_text);
+        }
+    }
+}
+
+fn set_bar(ui_transforms: &mut WriteStorage<UiTransform>, bar: Entity, value: f32, max: f32) {
+    if let Some(ui_transform) = ui_transforms.get_mut(bar) {
+        let fill = if max > 0.0 { (value / max).min(1.0) } else { 0.0 };
+        ui_transform.width = HUD_BAR_WIDTH * fill;
+    }
+}
+
+fn set_text(ui_texts: &mut WriteStorage<UiText>, entity: Entity, text: String) {
+    if let Some(ui_text) = ui_texts.get_mut(entity) {
+        ui_text.text = text;
+    }
+}
+
+//Refined goods in the hold, e.g. "Goods: 8 Water, 5 Alloy"
+pub fn describe_goods(cargo: &Cargo) -> String {
+    let goods = Good::ALL
+        .iter()
+        .filter(|good| cargo.good_amount(**good) > 0)
+        .map(|good| format!("{} {:?}", cargo.good_amount(*good), good))
+        .collect::<Vec<_>>();
+    if goods.is_empty() {
+        "Goods: none".to_string()
+    } else {
+        format!("Goods: {}", goods.join(", "))
+    }
+}
+
+//Standing with every faction, e.g. "Standing: Miners' Guild 12, Pirates -50, Station Authority 3"
+pub fn describe_standing(reputation: &Reputation) -> String {
+    let standings = Faction::ALL
+        .iter()
+        .map(|faction| format!("{} {:.0}", faction.label(), reputation.standing(*faction)))
+        .collect::<Vec<_>>();
+    format!("Standing: {}", standings.join(", "))
+}
+
+//The flare countdown while one lasts, otherwise the latest event's warning for a few seconds
+pub fn describe_event(events: &SectorEvents) -> String {
+    if events.flare > 0.0 {
+        return format!("Solar flare, shields draining ({:.0}s)", events.flare.ceil());
+    }
+    match events.last_event {
+        Some((kind, since)) if since < EVENT_WARNING_SECONDS => kind.warning().to_string(),
+        _ => String::new(),
+    }
+}
+
+//Ores from most to least common, e.g. "70% Iron, 25% Copper, 5% Gold"
+pub fn describe_composition(astroid: &Astroid) -> String {
+    let mut composition = astroid.composition.clone();
+    composition.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    composition
+        .iter()
+        .map(|(ore, share)| format!("{:.0}% {:?}", share * 100.0, ore))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+//How much of each ore the tool will recover from the rock
+pub fn describe_yield(astroid: &Astroid, tool: MiningTool) -> String {
+    astroid
+        .composition
+        .iter()
+        .map(|(ore, share)| format!("{:.0}% {:?}", share * tool.extraction(*ore) * 100.0, ore))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,47 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::prelude::{Read, System, SystemData, Write},
+    input::InputHandler,
+};
+
+use crate::input::{fire_pressed, mine_pressed, ActionBinding, AxisBinding, GameBindings, PlayerInput};
+
+//Copies the bound inputs into PlayerInput so the simulation never reads devices directly
+#[derive(Default, SystemDesc)]
+pub struct InputSampleSystem {
+    //Buttons that were down last tick, so one press only counts once
+    held: Vec<ActionBinding>,
+}
+
+impl InputSampleSystem {
+    fn pressed(&mut self, input: &InputHandler<GameBindings>, action: ActionBinding) -> bool {
+        let down = input.action_is_down(&action).unwrap_or(false);
+        let was_down = self.held.contains(&action);
+        if down && !was_down {
+            self.held.push(action);
+        } else if !down {
+            self.held.retain(|held| *held != action);
+        }
+        down && !was_down
+    }
+}
+
+impl<'s> System<'s> for InputSampleSystem {
+    type SystemData = (
+        Read<'s, InputHandler<GameBindings>>,
+        Write<'s, PlayerInput>,
+    );
+
+    fn run(&mut self, (input, mut player_input): Self::SystemData) {
+        // Aim and targeting are filled in by the MouseRaycastSystem
+        player_input.move_x = input.axis_value(&AxisBinding::MoveX).unwrap_or(0.0);
+        player_input.move_y = input.axis_value(&AxisBinding::MoveY).unwrap_or(0.0);
+        player_input.mine = mine_pressed(&input);
+        player_input.fire = fire_pressed(&input);
+        player_input.tractor = input.action_is_down(&ActionBinding::Tractor).unwrap_or(false);
+        player_input.scan = input.action_is_down(&ActionBinding::Scan).unwrap_or(false);
+        player_input.dock = self.pressed(&input, ActionBinding::Dock);
+        player_input.jump = self.pressed(&input, ActionBinding::Jump);
+        player_input.distress = self.pressed(&input, ActionBinding::Distress);
+    }
+}
@@ -0,0 +1,61 @@
+use amethyst::{
+    core::transform::Transform,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+use std::f32::consts::FRAC_PI_2;
+
+use crate::config::FuelConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Fuel, Galaxy, JumpRequest, MessageLog, Player};
+
+//Spends jump fuel to head for the neighbouring sector the ship is facing.
+//The sector itself is swapped out after the dispatcher has run.
+#[derive(SystemDesc)]
+pub struct JumpSystem;
+
+impl<'s> System<'s> for JumpSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Fuel>,
+        Read<'s, PlayerInput>,
+        Read<'s, FuelConfig>,
+        Read<'s, Galaxy>,
+        Write<'s, JumpRequest>,
+        Write<'s, MessageLog>,
+    );
+
+    fn run(
+        &mut self,
+        (players, transforms, mut fuels, input, config, galaxy, mut jump, mut messages): Self::SystemData,
+    ) {
+        if !input.jump || jump.0.is_some() {
+            return;
+        }
+        for (player, transform, fuel) in (&players, &transforms, &mut fuels).join() {
+            if player.docked.is_some() {
+                continue;
+            }
+            if !fuel.burn(config.jump_cost) {
+                messages.push(format!("Not enough fuel to jump, {:.0} needed", config.jump_cost));
+                continue;
+            }
+            let (x, y) = galaxy.sector;
+            let (dx, dy) = heading(transform);
+            jump.0 = Some((x + dx, y + dy));
+        }
+    }
+}
+
+//The ship's facing rounded to one of the four neighbouring sectors.
+//The sprite points up when the rotation is zero.
+fn heading(transform: &Transform) -> (i32, i32) {
+    let angle = transform.euler_angles().2 + FRAC_PI_2;
+    let (x, y) = (angle.cos(), angle.sin());
+    if x.abs() > y.abs() {
+        (x.signum() as i32, 0)
+    } else {
+        (0, y.signum() as i32)
+    }
+}
@@ -0,0 +1,23 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::config::MarketConfig;
+use crate::solar_lords::Market;
+
+//Station stock drifts back towards the target so prices recover over time
+#[derive(SystemDesc)]
+pub struct MarketSystem;
+
+impl<'s> System<'s> for MarketSystem {
+    type SystemData = (WriteStorage<'s, Market>, Read<'s, MarketConfig>, Read<'s, Time>);
+
+    fn run(&mut self, (mut markets, config, time): Self::SystemData) {
+        let delta = time.delta_seconds();
+        for market in (&mut markets).join() {
+            market.restock(&config, delta);
+        }
+    }
+}
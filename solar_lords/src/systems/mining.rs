@@ -2,12 +2,16 @@ use amethyst::{
     assets::{AssetStorage, Handle, Loader, Progress, ProgressCounter},
     core::{timing::Time, transform::Transform, math::*, Named, WithNamed,},
     derive::SystemDesc,
-    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage, Entities},
-    input::{InputHandler, StringBindings, VirtualKeyCode},
+    ecs::prelude::{Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage, Entities},
+    input::VirtualKeyCode,
     renderer::{Camera, ImageFormat, SpriteRender, SpriteSheet, SpriteSheetFormat, Texture, Transparent},
+    shrev::EventChannel,
 };
 
-use crate::solar_lords::{Player, Astroid};
+use crate::config::PickupConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Player, Astroid, Fitting, Galaxy, GameEvent, PowerGrid, Velocity};
+use super::pickup::eject_pickup;
 
 #[derive(SystemDesc)]
 pub struct MiningSystem;
@@ -15,38 +19,66 @@ pub struct MiningSystem;
 impl<'s> System<'s> for MiningSystem {
     type SystemData = (
         WriteStorage<'s, Player>,
+        ReadStorage<'s, Fitting>,
         WriteStorage<'s, Astroid>,
-        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Transform>,
         WriteStorage<'s, SpriteRender>,
         ReadStorage<'s, Named>,
+        ReadStorage<'s, PowerGrid>,
         Entities<'s>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, PlayerInput>,
+        Read<'s, PickupConfig>,
+        Read<'s, LazyUpdate>,
+        Read<'s, Galaxy>,
+        Write<'s, EventChannel<GameEvent>>,
     );
-    fn run(&mut self, (mut players, mut astroids, mut transforms, mut sprites, names, entities, input): Self::SystemData){
+    fn run(&mut self, (mut players, fittings, mut astroids, transforms, mut sprites, names, grids, entities, input, config, lazy, galaxy, mut events): Self::SystemData){
 
-        if(input.action_is_down("mine").unwrap()){
+        if input.mine {
 
-            let mut player_target =  None;
-
-            for (player) in (&mut players).join(){
-                player_target = Some(player.target.as_ref().unwrap());
-                //println!("{:?}", player_target.unwrap().to_string());
-            }
+            for (player, fitting, player_transform, grid) in (&mut players, &fittings, &transforms, grids.maybe()).join(){
+                // No beam without power or while overheated
+                if grid.map_or(false, |grid| !grid.mining) {
+                    continue;
+                }
+                let tool = match fitting.mining_tool() {
+                    Some(tool) => tool,
+                    None => continue,
+                };
+                let player_target = match player.target.as_ref() {
+                    Some(target) => target,
+                    None => continue,
+                };
 
-            for (astroid, transform, sprite, name, entity) in (&mut astroids, &mut transforms, &mut sprites, &names, &entities).join(){
-                if player_target.unwrap().to_string() == name.name.to_string() {
-                    astroid.resources -= 1;
-                    if(astroid.resources % 250 == 0){
-                        sprite.sprite_number += 1
-                    }
-                    if(astroid.resources == 0){
-                        entities.delete(entity);
+                for (astroid, transform, sprite, name, entity) in (&mut astroids, &transforms, &mut sprites, &names, &entities).join(){
+                    if *player_target == name.name.to_string() && astroid.resources > 0 {
+                        if astroid.mine(tool, config.chunk_size) {
+                            // Chunks break off towards the ship, fanned out a little
+                            let offset = player_transform.translation() - transform.translation();
+                            let fan = ((astroid.resources / config.chunk_size.max(1)) % 5) as f32 * 0.2 - 0.4;
+                            for (index, (ore, amount)) in astroid.break_off().into_iter().enumerate() {
+                                let heading = offset.y.atan2(offset.x) + fan + index as f32 * 0.3;
+                                let position = transform.translation() + Vector3::new(heading.cos(), heading.sin(), 0.0) * 20.0;
+                                eject_pickup(
+                                    &lazy,
+                                    &entities,
+                                    sprite.sprite_sheet.clone(),
+                                    ore,
+                                    amount,
+                                    position,
+                                    Velocity::new(heading.cos() * config.eject_speed, heading.sin() * config.eject_speed),
+                                );
+                                events.single_write(GameEvent::OreMined { ore, amount, sector: galaxy.sector });
+                            }
+                        }
+                        sprite.sprite_number = astroid.sprite();
+                        if astroid.resources == 0 {
+                            entities.delete(entity).ok();
+                        }
                     }
-                    println!("Hit!");
                 }
-                
             }
         }
         
     }
-}
\ No newline at end of file
+}
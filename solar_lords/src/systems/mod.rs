@@ -5,18 +5,64 @@ use amethyst::core::Transform;
 use amethyst::renderer::{ImageFormat, SpriteRender, SpriteSheet, SpriteSheetFormat, Texture};
 
 mod camera_zoom_system;
+mod capacitor;
+mod collision;
+mod combat;
+mod contracts;
+mod director;
 mod player_control;
 mod background_movement;
+mod docking;
+mod drift;
+mod fragmentation;
+mod fuel;
+mod hud;
+mod input_sample;
+mod jump;
+mod market;
 mod mining;
 mod mouse_raycast;
+mod npc;
+mod pickup;
+mod power;
+mod radar;
+mod refinery;
+mod regen;
+mod replay;
+mod scan_display;
+mod scanner;
+mod targeting;
 //mod player_control;
 
 pub use self::{
     camera_zoom_system::ZoomSystem,
+    capacitor::CapacitorSystem,
+    collision::CollisionSystem,
+    combat::CombatSystem,
+    contracts::ContractSystem,
+    director::EventDirectorSystem,
     player_control::PlayerControlSystem,
     background_movement::BackgroundMovementSystem,
+    docking::DockingSystem,
+    drift::DriftSystem,
+    fragmentation::FragmentationSystem,
+    fuel::FuelSystem,
+    hud::HudSystem,
+    input_sample::InputSampleSystem,
+    jump::JumpSystem,
+    market::MarketSystem,
     mining::MiningSystem,
     mouse_raycast::MouseRaycastSystem,
+    npc::NpcSystem,
+    pickup::PickupSystem,
+    power::PowerSystem,
+    radar::RadarSystem,
+    refinery::RefinerySystem,
+    regen::RegenSystem,
+    replay::{InputRecordSystem, InputReplaySystem},
+    scan_display::ScanDisplaySystem,
+    scanner::ScannerSystem,
+    targeting::TargetingSystem,
 };
 
 /// Load a sprite from a file and sprite number. Returns a SpriteRender.
@@ -1,68 +1,69 @@
 use amethyst::{
-    assets::{AssetStorage, Handle, Loader, Progress, ProgressCounter},
+    assets::{AssetStorage, Loader},
     core::{
         geometry::Plane,
-        math::{Point2, Vector2, Vector3},
-        transform::{Transform, TransformBundle},
-        Named, WithNamed,
+        math::{Point2, Vector2},
+        transform::Transform,
+        Named,
     },
     derive::SystemDesc,
     ecs::{
         prelude::Entity, Entities, Join, Read, ReadExpect, ReadStorage, System, SystemData,
-        WriteStorage, LazyUpdate,
+        Write, LazyUpdate,
     },
-    input::{InputBundle, InputHandler, StringBindings},
-    prelude::{Builder, World, WorldExt},
+    input::InputHandler,
     renderer::{
         camera::{ActiveCamera, Camera},
-        plugins::{RenderFlat2D, RenderToWindow},
-        sprite::{SpriteRender, SpriteSheet, SpriteSheetFormat},
-        types::DefaultBackend,
-        ImageFormat, RenderingBundle, Texture, Transparent,
+        sprite::SpriteSheet,
+        Texture,
     },
-    ui::{RenderUi, UiBundle, UiCreator, UiFinder, UiText},
-    utils::application_root_dir,
     window::ScreenDimensions,
-    Application, GameData, GameDataBuilder, SimpleState, SimpleTrans, StateData, Trans,
 };
 
-use crate::solar_lords::{Player, CAMERA_HEIGHT, CAMERA_WIDTH};
+use crate::input::{Aim, AimMode, AxisBinding, GameBindings, PlayerInput};
+use crate::solar_lords::{Astroid, Heat, Player};
 use crate::systems::load_sprite_system;
 
+//Turns the mouse or right stick into an Aim for the TargetingSystem and draws the mining ray
 #[derive(SystemDesc, Default)]
 pub struct MouseRaycastSystem {
     pub mining_ray: Option<Entity>,
+    pub last_mouse_position: Option<(f32, f32)>,
 }
 
+const AIM_DEAD_ZONE: f32 = 0.3;
+
 
 impl<'s> System<'s> for MouseRaycastSystem {
     type SystemData = (
         Entities<'s>,
-        WriteStorage<'s, Player>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Heat>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Camera>,
-        ReadStorage<'s, SpriteRender>,
-        ReadStorage<'s, Handle<SpriteSheet>>,
         ReadStorage<'s, Named>,
+        ReadStorage<'s, Astroid>,
         Read<'s, AssetStorage<Texture>>,
         Read<'s, AssetStorage<SpriteSheet>>,
         ReadExpect<'s, Loader>,
         ReadExpect<'s, ScreenDimensions>,
         Read<'s, ActiveCamera>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, InputHandler<GameBindings>>,
         Read<'s, LazyUpdate>,
+        Write<'s, AimMode>,
+        Write<'s, PlayerInput>,
     );
 
     fn run(
         &mut self,
         (
             entities,
-            mut players,
+            players,
+            heats,
             ray_transforms,
             cameras,
-            sprites,
-            spritesheet_handles,
             names,
+            astroids,
             texture_storage,
             sheet_storage,
             loader,
@@ -70,92 +71,96 @@ impl<'s> System<'s> for MouseRaycastSystem {
             active_camera,
             input,
             lazy,
+            mut aim_mode,
+            mut player_input,
         ): Self::SystemData,
     ) {
-        // Get the mouse position if its available
-        if let Some(mouse_position) = input.mouse_position() {
-            // Get the active camera if it is spawned and ready
-            let mut camera_join = (&cameras, &ray_transforms).join();
-            if let Some((camera, camera_ray_transform)) = active_camera
-                .entity
-                .and_then(|a| camera_join.get(a, &entities))
-                .or_else(|| camera_join.next())
-            {
-                // Project a ray from the camera to the 0z axis
-                let ray = camera.screen_ray(
-                    Point2::new(mouse_position.0, mouse_position.1),
-                    Vector2::new(screen_dimensions.width(), screen_dimensions.height()),
-                    camera_ray_transform,
-                );
-                let distance = ray.intersect_plane(&Plane::with_z(0.0)).unwrap();
-                let mouse_world_position = ray.at_distance(distance);
+        // Switch aim modes to whichever device was used last
+        let aim_x = input.axis_value(&AxisBinding::AimX).unwrap_or(0.0);
+        let aim_y = input.axis_value(&AxisBinding::AimY).unwrap_or(0.0);
+        let stick_aiming = (aim_x * aim_x + aim_y * aim_y).sqrt() > AIM_DEAD_ZONE;
+        let mouse_position = input.mouse_position();
+        if stick_aiming {
+            *aim_mode = AimMode::Gamepad;
+        } else if self.last_mouse_position.is_some() && mouse_position != self.last_mouse_position {
+            *aim_mode = AimMode::Mouse;
+        }
+        self.last_mouse_position = mouse_position;
 
-                // Find any sprites which the mouse is currently inside
-                let mut found_name = None;
-                for (sprite, ray_transform, name) in (&sprites, &ray_transforms, &names).join() {
-                    let sprite_sheet = sheet_storage.get(&sprite.sprite_sheet).unwrap();
-                    let sprite = &sprite_sheet.sprites[sprite.sprite_number];
-                    let (min_x, max_x, min_y, max_y) = {
-                        // Sprites are centered on a coordinate, so we build out a bbox for the sprite coordinate
-                        // and dimensions
-                        // Notice we ignore z-axis for this example.
-                        (
-                            ray_transform.translation().x - (sprite.width * 0.5),
-                            ray_transform.translation().x + (sprite.width * 0.5),
-                            ray_transform.translation().y - (sprite.height * 0.5),
-                            ray_transform.translation().y + (sprite.height * 0.5),
-                        )
-                    };
-                    if mouse_world_position.x > min_x
-                        && mouse_world_position.x < max_x
-                        && mouse_world_position.y > min_y
-                        && mouse_world_position.y < max_y
+        player_input.aim = None;
+        match *aim_mode {
+            AimMode::Mouse => {
+                // Get the mouse position if its available
+                if let Some(mouse_position) = mouse_position {
+                    // Get the active camera if it is spawned and ready
+                    let mut camera_join = (&cameras, &ray_transforms).join();
+                    if let Some((camera, camera_ray_transform)) = active_camera
+                        .entity
+                        .and_then(|a| camera_join.get(a, &entities))
+                        .or_else(|| camera_join.next())
                     {
-                        found_name = Some(&name.name);
+                        // Project a ray from the camera to the 0z axis
+                        let ray = camera.screen_ray(
+                            Point2::new(mouse_position.0, mouse_position.1),
+                            Vector2::new(screen_dimensions.width(), screen_dimensions.height()),
+                            camera_ray_transform,
+                        );
+                        let distance = ray.intersect_plane(&Plane::with_z(0.0)).unwrap();
+                        let mouse_world_position = ray.at_distance(distance);
+                        player_input.aim = Some(Aim::Point(mouse_world_position.x, mouse_world_position.y));
                     }
                 }
-
-                for (player) in (&mut players).join() {
-                    player.target = Some(match found_name {
-                        None => "".to_string(),
-                        Some(x) => x.to_string(),
-                    });
+            }
+            AimMode::Gamepad => {
+                if stick_aiming {
+                    player_input.aim = Some(Aim::Direction(aim_x, aim_y));
                 }
+            }
+        }
 
-                // This draws the mining rays
-                
-                if input.action_is_down("mine").unwrap() {
-
-                    /*
-                    let sprite_sheet = None;
-                    for (spritesheet_handle) in (&spritesheet_handles).join() {
-                        let sprite_sheet = &spritesheet_handle;
-                        let mut ray_ray_transform = ray_Transform::default();
-                        ray_ray_transform.set_translation_xyz(
-                            CAMERA_WIDTH * 0.5,
-                            CAMERA_HEIGHT * 0.5,
-                            0.0,
-                        );
-                        let sprite = SpriteRender::new(sprite_sheet.unwrap(), 7);
-                    }*/
-                    let ray_sprite = load_sprite_system(
-                        &texture_storage,
-                        &sheet_storage,
-                        &loader,
-                        "solar_lords_spritesheet.png",
-                        6,
-                    );
-
-                    let mut ray_transform = Transform::default();
+        // This draws the mining ray on the target, reusing one entity while mining
+        // An overheated beam shuts off
+        let target_position = (&players, heats.maybe())
+            .join()
+            .filter(|(_, heat)| !heat.map_or(false, |heat| heat.overheated))
+            .filter_map(|(player, _)| player.target.as_ref())
+            .next()
+            .and_then(|target| {
+                (&astroids, &ray_transforms, &names)
+                    .join()
+                    .find(|(_, _, name)| name.name == *target)
+                    .map(|(_, transform, _)| *transform.translation())
+            });
 
-                    ray_transform.set_translation_xyz(mouse_position.0, mouse_position.1, 4.0);
+        match target_position {
+            Some(target_position) if player_input.mine => {
+                let mut ray_transform = Transform::default();
+                ray_transform.set_translation_xyz(target_position.x, target_position.y, 0.05);
 
-                    let mining_ray = Some(
-                        lazy.create_entity(&entities)
-                            .with(ray_sprite)
-                            .with(ray_transform)
-                            .build(),
-                    );
+                match self.mining_ray {
+                    Some(mining_ray) if entities.is_alive(mining_ray) => {
+                        lazy.insert(mining_ray, ray_transform);
+                    }
+                    _ => {
+                        let ray_sprite = load_sprite_system(
+                            &texture_storage,
+                            &sheet_storage,
+                            &loader,
+                            "solar_lords_spritesheet.png",
+                            6,
+                        );
+                        self.mining_ray = Some(
+                            lazy.create_entity(&entities)
+                                .with(ray_sprite)
+                                .with(ray_transform)
+                                .build(),
+                        );
+                    }
+                }
+            }
+            _ => {
+                if let Some(mining_ray) = self.mining_ray.take() {
+                    let _ = entities.delete(mining_ray);
                 }
             }
         }
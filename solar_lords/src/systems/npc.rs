@@ -0,0 +1,363 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Entities, Entity, Join, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+use rand::{rngs::StdRng, Rng};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use crate::config::{FactionConfig, MarketConfig, NpcConfig, PickupConfig};
+use crate::npc::{choose_goal, Situation};
+use crate::solar_lords::{
+    Astroid, Cargo, Collider, ContactKind, Faction, Fitting, Market, MiningTool, Npc, NpcGoal, Ore, Player, RadarContact,
+    Reputation, Station,
+};
+
+//NPC miners all work with the basic laser
+const NPC_MINING_TOOL: MiningTool = MiningTool::Laser;
+//How far a wandering NPC heads off at a time
+const WANDER_RANGE: f32 = 600.0;
+
+//Another ship as it stood at the start of the tick
+struct Contact {
+    entity: Entity,
+    position: (f32, f32),
+    //The player belongs to no faction, NPCs go by its standing instead
+    faction: Option<Faction>,
+    armed: bool,
+}
+
+//Weighs up each NPC's goals every so often using the scores in npc.rs, and flies
+//them towards whatever they picked. Ships of factions at war, or a player with too
+//low a standing, are hunted by armed NPCs and avoided by the rest. Damage is left
+//to the CombatSystem.
+#[derive(SystemDesc)]
+pub struct NpcSystem;
+
+impl<'s> System<'s> for NpcSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Npc>,
+        WriteStorage<'s, Cargo>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Astroid>,
+        WriteStorage<'s, Market>,
+        WriteStorage<'s, RadarContact>,
+        WriteStorage<'s, Collider>,
+        ReadStorage<'s, Station>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Fitting>,
+        ReadStorage<'s, Reputation>,
+        Read<'s, NpcConfig>,
+        Read<'s, FactionConfig>,
+        Read<'s, MarketConfig>,
+        Read<'s, PickupConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut npcs,
+            mut cargos,
+            mut transforms,
+            mut astroids,
+            mut markets,
+            mut contacts,
+            mut colliders,
+            stations,
+            players,
+            fittings,
+            reputations,
+            config,
+            faction_config,
+            market_config,
+            pickup_config,
+            time,
+        ): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+
+        // Everything an NPC might head for
+        let astroid_spots = (&entities, &astroids, &transforms)
+            .join()
+            .filter(|(_, astroid, _)| astroid.resources > 0)
+            .map(|(entity, _, transform)| (entity, position(transform)))
+            .collect::<Vec<_>>();
+        let station_spots = (&entities, &stations, &transforms)
+            .join()
+            .map(|(entity, _, transform)| (entity, position(transform)))
+            .collect::<Vec<_>>();
+        let mut ships = (&entities, &npcs, &transforms)
+            .join()
+            .map(|(entity, npc, transform)| Contact {
+                entity,
+                position: position(transform),
+                faction: Some(npc.faction()),
+                armed: npc.archetype.damage > 0.0,
+            })
+            .collect::<Vec<_>>();
+        // Docked players are out of reach
+        ships.extend(
+            (&entities, &players, &transforms)
+                .join()
+                .filter(|(_, player, _)| player.docked.is_none())
+                .map(|(entity, _, transform)| Contact {
+                    entity,
+                    position: position(transform),
+                    faction: None,
+                    armed: fittings.get(entity).map_or(true, |fitting| fitting.weapon().is_some()),
+                }),
+        );
+        let reputation = (&players, &reputations)
+            .join()
+            .map(|(_, reputation)| reputation.clone())
+            .next()
+            .unwrap_or_else(|| Reputation::new(&faction_config));
+        let hostile = |faction: Faction, other: &Contact| match other.faction {
+            Some(other) => faction_config.at_war(faction, other),
+            None => reputation.is_hostile(faction, &faction_config),
+        };
+        let locate = |target: Entity| {
+            astroid_spots
+                .iter()
+                .chain(station_spots.iter())
+                .find(|(entity, _)| *entity == target)
+                .map(|(_, spot)| *spot)
+                .or_else(|| ships.iter().find(|ship| ship.entity == target).map(|ship| ship.position))
+        };
+
+        for (entity, npc, transform) in (&entities, &mut npcs, &mut transforms).join() {
+            let here = position(transform);
+            let role = npc.role();
+            let faction = npc.faction();
+            let armed = npc.archetype.damage > 0.0;
+            let sight = npc.archetype.sight;
+
+            // The radar shows whoever would attack the player in red
+            if let Some(contact) = contacts.get_mut(entity) {
+                contact.kind = if reputation.is_hostile(faction, &faction_config) {
+                    ContactKind::Hostile
+                } else {
+                    ContactKind::Neutral
+                };
+            }
+
+            // A goal whose target is gone is dropped straight away
+            let target = match npc.goal {
+                NpcGoal::Mine(target)
+                | NpcGoal::Sell(target)
+                | NpcGoal::Trade(target)
+                | NpcGoal::Hunt(target)
+                | NpcGoal::Flee(target) => Some(target),
+                NpcGoal::Wander | NpcGoal::Haul => None,
+            };
+            let lost = target.map_or(false, |target| locate(target).is_none());
+
+            npc.think -= delta;
+            if npc.think <= 0.0 || lost {
+                let cargo = cargos.get(entity).map_or(1.0, |cargo| {
+                    if cargo.capacity > 0 {
+                        cargo.total() as f32 / cargo.capacity as f32
+                    } else {
+                        1.0
+                    }
+                });
+                let in_sight = |found: Option<(Entity, f32)>| found.filter(|(_, distance)| *distance <= sight);
+                // Armed ships go after their enemies, the rest run from the armed ones
+                let enemies = ships
+                    .iter()
+                    .filter(|ship| ship.entity != entity && hostile(faction, *ship))
+                    .filter(|ship| armed || ship.armed)
+                    .map(|ship| (ship.entity, ship.position));
+                let enemy = in_sight(nearest(here, enemies));
+                let situation = Situation {
+                    hull: npc.hull as f32 / npc.archetype.hull.max(1) as f32,
+                    cargo,
+                    astroid: nearest(here, astroid_spots.iter().cloned()),
+                    station: nearest(here, station_spots.iter().cloned()),
+                    threat: if armed { None } else { enemy },
+                    prey: if armed { enemy } else { None },
+                };
+
+                let goal = choose_goal(role, sight, &situation);
+                if goal != npc.goal {
+                    match goal {
+                        NpcGoal::Wander => npc.waypoint = wander_point(&mut npc.rng, here),
+                        NpcGoal::Haul => {
+                            // Anywhere but where the cargo came from, or out of the sector if there's nowhere else
+                            let bought_at = npc.bought_at;
+                            let others = station_spots.iter().filter(|(station, _)| Some(*station) != bought_at);
+                            npc.delivery = nearest(here, others.cloned()).map(|(station, _)| station);
+                            let angle = npc.rng.gen_range(0.0, 2.0 * PI);
+                            npc.waypoint = (angle.cos() * config.exit_distance, angle.sin() * config.exit_distance);
+                        }
+                        _ => {}
+                    }
+                }
+                npc.goal = goal;
+                npc.think = config.think_interval;
+            }
+
+            let step = npc.archetype.speed * delta;
+            match npc.goal {
+                NpcGoal::Wander => {
+                    if approach(transform, npc.waypoint, step, 1.0) {
+                        npc.waypoint = wander_point(&mut npc.rng, here);
+                    }
+                }
+                NpcGoal::Mine(target) => {
+                    let spot = match locate(target) {
+                        Some(spot) => spot,
+                        None => continue,
+                    };
+                    if !approach(transform, spot, step, config.mining_range) {
+                        continue;
+                    }
+                    if let (Some(astroid), Some(cargo)) = (astroids.get_mut(target), cargos.get_mut(entity)) {
+                        // Ore goes straight into the hold, NPCs don't chase pickups
+                        npc.mining_progress += config.mining_rate * delta;
+                        while npc.mining_progress >= 1.0 && astroid.resources > 0 {
+                            npc.mining_progress -= 1.0;
+                            if astroid.mine(NPC_MINING_TOOL, pickup_config.chunk_size) {
+                                for (ore, amount) in astroid.break_off() {
+                                    cargo.add(ore, amount);
+                                }
+                            }
+                        }
+                        if astroid.resources == 0 {
+                            entities.delete(target).expect("Failed to delete mined out astroid");
+                        }
+                    }
+                }
+                NpcGoal::Sell(target) => {
+                    let spot = match locate(target) {
+                        Some(spot) => spot,
+                        None => continue,
+                    };
+                    if !approach(transform, spot, step, config.dock_range) {
+                        continue;
+                    }
+                    if let (Some(market), Some(cargo)) = (markets.get_mut(target), cargos.get_mut(entity)) {
+                        sell_hold(market, cargo, &market_config);
+                    }
+                    npc.think = 0.0;
+                }
+                NpcGoal::Trade(target) => {
+                    let spot = match locate(target) {
+                        Some(spot) => spot,
+                        None => continue,
+                    };
+                    if !approach(transform, spot, step, config.dock_range) {
+                        continue;
+                    }
+                    if let (Some(market), Some(cargo)) = (markets.get_mut(target), cargos.get_mut(entity)) {
+                        // Buy up whatever the station has the most of, that's where it's cheapest
+                        let surplus = Ore::ALL
+                            .iter()
+                            .max_by(|a, b| market.stock(**a).partial_cmp(&market.stock(**b)).unwrap())
+                            .cloned();
+                        if let Some(ore) = surplus {
+                            let space = cargo.capacity.saturating_sub(cargo.total());
+                            let (bought, _) = market.buy(ore, space, &market_config);
+                            cargo.add(ore, bought);
+                            npc.bought_at = Some(target);
+                        }
+                    }
+                    npc.think = 0.0;
+                }
+                NpcGoal::Haul => {
+                    if let Some(station) = npc.delivery {
+                        let spot = match locate(station) {
+                            Some(spot) => spot,
+                            None => {
+                                npc.delivery = None;
+                                continue;
+                            }
+                        };
+                        if approach(transform, spot, step, config.dock_range) {
+                            if let (Some(market), Some(cargo)) = (markets.get_mut(station), cargos.get_mut(entity)) {
+                                sell_hold(market, cargo, &market_config);
+                            }
+                            npc.bought_at = None;
+                            npc.delivery = None;
+                            npc.think = 0.0;
+                        }
+                    } else if approach(transform, npc.waypoint, step, 1.0) {
+                        // Gone long enough for the prices to move, back in from the other side to sell
+                        let arrival = (-npc.waypoint.0, -npc.waypoint.1);
+                        npc.delivery = nearest(arrival, station_spots.iter().cloned()).map(|(station, _)| station);
+                        transform.set_translation_x(arrival.0);
+                        transform.set_translation_y(arrival.1);
+                        // A jump, not a flight across the sector
+                        if let Some(collider) = colliders.get_mut(entity) {
+                            collider.last_position = None;
+                        }
+                        npc.think = 0.0;
+                    }
+                }
+                NpcGoal::Hunt(target) => {
+                    if let Some(spot) = locate(target) {
+                        approach(transform, spot, step, npc.archetype.weapon_range * 0.8);
+                    }
+                }
+                NpcGoal::Flee(target) => {
+                    if let Some(spot) = locate(target) {
+                        let away = (here.0 - spot.0, here.1 - spot.1);
+                        let length = (away.0 * away.0 + away.1 * away.1).sqrt().max(0.001);
+                        let goal = (here.0 + away.0 / length * 100.0, here.1 + away.1 / length * 100.0);
+                        approach(transform, goal, step, 0.0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn position(transform: &Transform) -> (f32, f32) {
+    (transform.translation().x, transform.translation().y)
+}
+
+//Closest of the spots and how far away it is
+fn nearest<I: Iterator<Item = (Entity, (f32, f32))>>(here: (f32, f32), spots: I) -> Option<(Entity, f32)> {
+    spots
+        .map(|(entity, spot)| {
+            let (x, y) = (spot.0 - here.0, spot.1 - here.1);
+            (entity, (x * x + y * y).sqrt())
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+}
+
+//Sells every ore in the hold to the station
+fn sell_hold(market: &mut Market, cargo: &mut Cargo, config: &MarketConfig) {
+    for ore in Ore::ALL.iter() {
+        let amount = cargo.amount(*ore);
+        if amount > 0 {
+            market.sell(*ore, amount, config);
+            cargo.take_ores(&[(*ore, amount)]);
+        }
+    }
+}
+
+fn wander_point(rng: &mut StdRng, here: (f32, f32)) -> (f32, f32) {
+    (
+        here.0 + rng.gen_range(-WANDER_RANGE, WANDER_RANGE),
+        here.1 + rng.gen_range(-WANDER_RANGE, WANDER_RANGE),
+    )
+}
+
+//Flies straight at a point, stopping short by the reach. Returns true once within reach.
+fn approach(transform: &mut Transform, target: (f32, f32), step: f32, reach: f32) -> bool {
+    let x = target.0 - transform.translation().x;
+    let y = target.1 - transform.translation().y;
+    let distance = (x * x + y * y).sqrt();
+    if distance <= reach {
+        return true;
+    }
+    let travel = step.min(distance - reach);
+    transform.prepend_translation_x(x / distance * travel);
+    transform.prepend_translation_y(y / distance * travel);
+    transform.set_rotation_2d(y.atan2(x) - FRAC_PI_2);
+    distance - travel <= reach
+}
@@ -0,0 +1,101 @@
+use amethyst::{
+    assets::Handle,
+    core::{math::Vector3, timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Builder, Entities, Entity, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
+    renderer::{SpriteRender, SpriteSheet},
+};
+
+use crate::config::PickupConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Cargo, Ore, OrePickup, Player, Velocity};
+
+//Pickups reuse the last astroid sprite at this scale
+const PICKUP_SPRITE: usize = 4;
+const PICKUP_SCALE: f32 = 0.2;
+
+//Moves pickups the ship flies over into its cargo, pulls them in with the
+//tractor beam and lets old ones float away
+#[derive(SystemDesc)]
+pub struct PickupSystem;
+
+impl<'s> System<'s> for PickupSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Cargo>,
+        WriteStorage<'s, OrePickup>,
+        WriteStorage<'s, Velocity>,
+        ReadStorage<'s, Transform>,
+        Read<'s, PickupConfig>,
+        Read<'s, PlayerInput>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, players, mut cargos, mut pickups, mut velocities, transforms, config, input, time): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        for (entity, pickup) in (&entities, &mut pickups).join() {
+            pickup.age += delta;
+            if pickup.age > config.lifetime {
+                pickup.amount = 0;
+                entities.delete(entity).expect("Failed to delete expired pickup");
+            }
+        }
+
+        let collect_squared = config.collect_radius * config.collect_radius;
+        let tractor_squared = config.tractor_range * config.tractor_range;
+        for (_, cargo, player_transform) in (&players, &mut cargos, &transforms).join() {
+            let ship = player_transform.translation();
+            for (entity, pickup, transform, velocity) in
+                (&entities, &mut pickups, &transforms, (&mut velocities).maybe()).join()
+            {
+                if pickup.amount == 0 {
+                    continue;
+                }
+                let mut offset = ship - transform.translation();
+                offset.z = 0.0;
+                let distance_squared = offset.norm_squared();
+
+                if distance_squared <= collect_squared {
+                    // Anything that doesn't fit stays floating, and doesn't age while the hold is full
+                    pickup.amount -= cargo.add(pickup.ore, pickup.amount);
+                    if pickup.amount == 0 {
+                        entities.delete(entity).expect("Failed to delete collected pickup");
+                    } else {
+                        pickup.age = 0.0;
+                    }
+                } else if input.tractor && distance_squared <= tractor_squared {
+                    if let Some(velocity) = velocity {
+                        let pull = offset.normalize() * config.tractor_pull * delta;
+                        velocity.x += pull.x;
+                        velocity.y += pull.y;
+                    }
+                }
+            }
+        }
+    }
+}
+
+//Drops a pickup into the world, created once the frame's LazyUpdates run
+pub fn eject_pickup(
+    lazy: &LazyUpdate,
+    entities: &Entities,
+    sprite_sheet: Handle<SpriteSheet>,
+    ore: Ore,
+    amount: u32,
+    position: Vector3<f32>,
+    velocity: Velocity,
+) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation(position);
+    transform.set_scale(Vector3::new(PICKUP_SCALE, PICKUP_SCALE, 1.0));
+    lazy.create_entity(entities)
+        .with(OrePickup::new(ore, amount))
+        .with(velocity)
+        .with(transform)
+        .with(SpriteRender::new(sprite_sheet, PICKUP_SPRITE))
+        .build()
+}
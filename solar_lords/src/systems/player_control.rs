@@ -2,40 +2,66 @@ use amethyst::{
     core::{timing::Time, transform::Transform, math::*},
     derive::SystemDesc,
     ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
-    input::{InputHandler, StringBindings, VirtualKeyCode},
+    input::VirtualKeyCode,
 };
 
-use crate::solar_lords::Player;
+use crate::config::FuelConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Fuel, Player, PowerGrid, Velocity};
 
 #[derive(SystemDesc)]
 pub struct PlayerControlSystem;
 
+pub const MOVE_DEAD_ZONE: f32 = 0.1;
+
 impl<'s> System<'s> for PlayerControlSystem {
     type SystemData = (
         WriteStorage<'s, Player>,
         WriteStorage<'s, Transform>,
-        Read<'s, InputHandler<StringBindings>>,
+        WriteStorage<'s, Fuel>,
+        WriteStorage<'s, Velocity>,
+        ReadStorage<'s, PowerGrid>,
+        Read<'s, PlayerInput>,
+        Read<'s, FuelConfig>,
+        Read<'s, Time>,
     );
 
-    fn run(&mut self, (mut players, mut transforms, input): Self::SystemData){
-        let x_move = input.axis_value("entity_x").unwrap();
-        let y_move = input.axis_value("entity_y").unwrap();
-        
-        for (player, transform) in (&mut players, &mut transforms).join() {
+    fn run(&mut self, (mut players, mut transforms, mut fuels, mut velocities, grids, input, config, time): Self::SystemData){
+        let x_move = input.move_x;
+        let y_move = input.move_y;
+        let delta = time.delta_seconds();
+        let thrusting = x_move.abs() > MOVE_DEAD_ZONE || y_move.abs() > MOVE_DEAD_ZONE;
+
+        for (player, transform, fuel, velocity, grid) in
+            (&mut players, &mut transforms, (&mut fuels).maybe(), (&mut velocities).maybe(), grids.maybe()).join()
+        {
+            if player.docked.is_some() {
+                continue;
+            }
+            // The engines won't fire on a flat capacitor
+            if thrusting && grid.map_or(false, |grid| !grid.thrust) {
+                continue;
+            }
+            if let (true, Some(fuel)) = (thrusting, fuel) {
+                let thrust = (x_move * x_move + y_move * y_move).sqrt().min(1.0);
+                if !fuel.burn(thrust * config.thrust_burn * delta) {
+                    // The engines sputter out and the ship keeps the speed it had
+                    if !fuel.is_empty() && delta > 0.0 {
+                        fuel.amount = 0.0;
+                        if let Some(velocity) = velocity {
+                            *velocity = Velocity::new(x_move * player.speed / delta, y_move * player.speed / delta);
+                        }
+                    }
+                    continue;
+                }
+            }
+
             transform.prepend_translation_x(x_move as f32 * player.speed);
             transform.prepend_translation_y(y_move as f32 * player.speed);
-            let rotation = (x_move as i32, y_move as i32);
-            transform.set_rotation_2d(match rotation {
-                (1,0) => -std::f32::consts::PI/2.0,
-                (1,1) => std::f32::consts::PI*7.0/4.0,
-                (0,1) => 0.0,
-                (-1,1) => std::f32::consts::PI/4.0,
-                (-1,0) => std::f32::consts::PI/2.0,
-                (-1,-1) => std::f32::consts::PI*3.0/4.0,
-                (0,-1) => std::f32::consts::PI,
-                (1,-1) => std::f32::consts::PI*5.0/4.0,
-                (_,_) => continue,
-            });
+            // Face the direction of travel, analog sticks give any angle in between
+            if thrusting {
+                transform.set_rotation_2d(y_move.atan2(x_move) - std::f32::consts::FRAC_PI_2);
+            }
 
         }
     }
@@ -43,4 +69,4 @@ impl<'s> System<'s> for PlayerControlSystem {
 /*
 fn ease_rotation(target: f32, transform: Transform) -> f32 {
     let current_rotation = transform.
-}*/
\ No newline at end of file
+}*/
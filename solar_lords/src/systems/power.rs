@@ -0,0 +1,87 @@
+use amethyst::{
+    core::{timing::Time, Named},
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::config::PowerConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Astroid, Capacitor, Fitting, Heat, Player, PowerGrid};
+use crate::systems::player_control::MOVE_DEAD_ZONE;
+
+//Shares the capacitor between the modules asking for power and tracks heat.
+//The engines come first, then mining, then the weapon, then shield regeneration.
+#[derive(SystemDesc)]
+pub struct PowerSystem;
+
+impl<'s> System<'s> for PowerSystem {
+    type SystemData = (
+        WriteStorage<'s, Player>,
+        ReadStorage<'s, Fitting>,
+        WriteStorage<'s, Capacitor>,
+        WriteStorage<'s, Heat>,
+        WriteStorage<'s, PowerGrid>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, Named>,
+        Read<'s, PowerConfig>,
+        Read<'s, PlayerInput>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (mut players, fittings, mut capacitors, mut heats, mut grids, astroids, names, config, input, time): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        let thrust = (input.move_x * input.move_x + input.move_y * input.move_y).sqrt().min(1.0);
+        let thrusting = input.move_x.abs() > MOVE_DEAD_ZONE || input.move_y.abs() > MOVE_DEAD_ZONE;
+        for (player, fitting, capacitor, heat, grid) in
+            (&mut players, &fittings, &mut capacitors, &mut heats, &mut grids).join()
+        {
+            heat.heat = (heat.heat - config.heat_dissipation * delta).max(0.0);
+            if heat.overheated && heat.heat <= heat.capacity * config.overheat_recover {
+                heat.overheated = false;
+            }
+
+            // Docked ships don't fly
+            grid.thrust = thrusting
+                && player.docked.is_none()
+                && capacitor.draw(thrust * config.thrust_draw * delta);
+
+            // Without a module fitted or a rock to point it at there is nothing to power
+            let has_target = player.target.as_ref().map_or(false, |target| {
+                (&astroids, &names)
+                    .join()
+                    .any(|(astroid, name)| astroid.resources > 0 && name.name == *target)
+            });
+            grid.mining = false;
+            if let Some(tool) = fitting.mining_tool() {
+                grid.mining = input.mine
+                    && has_target
+                    && !heat.overheated
+                    && capacitor.draw(tool.energy_draw() * delta);
+                if grid.mining {
+                    heat.heat += tool.heat_rate() * delta;
+                    if heat.heat >= heat.capacity {
+                        heat.heat = heat.capacity;
+                        heat.overheated = true;
+                    }
+                }
+            }
+
+            grid.weapon = match fitting.weapon() {
+                Some(weapon) => input.fire && capacitor.draw(weapon.energy_draw() * delta),
+                None => false,
+            };
+
+            if player.sheild < player.max_sheild && capacitor.draw(config.shield_regen_draw * delta) {
+                grid.shield_progress += config.shield_regen * delta;
+                let whole = grid.shield_progress.floor();
+                grid.shield_progress -= whole;
+                player.sheild = (player.sheild + whole as u32).min(player.max_sheild);
+            } else {
+                grid.shield_progress = 0.0;
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use amethyst::{
+    core::{transform::Transform, Hidden, Parent},
+    derive::SystemDesc,
+    ecs::prelude::{
+        Entities, Entity, Join, Read, ReadExpect, ReadStorage, System, SystemData, Write,
+        WriteStorage,
+    },
+    ui::{Anchor, UiImage, UiTransform},
+};
+
+use crate::config::RadarConfig;
+use crate::solar_lords::{Astroid, FogOfWar, Minimap, Player, RadarContact};
+
+const FOG_COLOR: [f32; 4] = [0.02, 0.02, 0.04, 0.9];
+const PLAYER_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+#[derive(SystemDesc, Default)]
+pub struct RadarSystem {
+    pub panel: Option<Entity>,
+    pub blips: Vec<Entity>,
+    pub fog_tiles: Vec<Entity>,
+}
+
+//A rectangle to draw on the minimap, in pixels relative to its centre
+struct MapRect {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: [f32; 4],
+}
+
+impl<'s> System<'s> for RadarSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, RadarContact>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiImage>,
+        WriteStorage<'s, Parent>,
+        WriteStorage<'s, Hidden>,
+        Option<Read<'s, Minimap>>,
+        Option<Write<'s, FogOfWar>>,
+        ReadExpect<'s, RadarConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            players,
+            astroids,
+            contacts,
+            transforms,
+            mut ui_transforms,
+            mut images,
+            mut parents,
+            mut hiddens,
+            minimap,
+            fog,
+            config,
+        ): Self::SystemData,
+    ) {
+        let panel = match minimap {
+            Some(minimap) => minimap.panel,
+            None => return,
+        };
+        let mut fog = match fog {
+            Some(fog) => fog,
+            None => return,
+        };
+
+        // A new minimap panel means the old blips went with the previous one
+        if self.panel != Some(panel) {
+            self.panel = Some(panel);
+            self.blips.clear();
+            self.fog_tiles.clear();
+        }
+
+        let (player_x, player_y) = match (&players, &transforms).join().next() {
+            Some((_, transform)) => (transform.translation().x, transform.translation().y),
+            None => return,
+        };
+
+        fog.reveal(player_x, player_y, config.reveal_radius);
+
+        let scale = config.size * 0.5 / config.map_range;
+
+        // Unexplored cells inside the minimap window, clipped to its edges
+        let mut fog_rects = Vec::new();
+        let (min_cell_x, min_cell_y) =
+            fog.cell_at(player_x - config.map_range, player_y - config.map_range);
+        let (max_cell_x, max_cell_y) =
+            fog.cell_at(player_x + config.map_range, player_y + config.map_range);
+        for cell_x in min_cell_x..=max_cell_x {
+            for cell_y in min_cell_y..=max_cell_y {
+                if fog.explored.contains(&(cell_x, cell_y)) {
+                    continue;
+                }
+                let left = (cell_x as f32 * fog.cell_size - player_x).max(-config.map_range);
+                let right =
+                    ((cell_x + 1) as f32 * fog.cell_size - player_x).min(config.map_range);
+                let bottom = (cell_y as f32 * fog.cell_size - player_y).max(-config.map_range);
+                let top = ((cell_y + 1) as f32 * fog.cell_size - player_y).min(config.map_range);
+                fog_rects.push(MapRect {
+                    x: (left + right) * 0.5 * scale,
+                    y: (bottom + top) * 0.5 * scale,
+                    width: (right - left) * scale,
+                    height: (top - bottom) * scale,
+                    color: FOG_COLOR,
+                });
+            }
+        }
+
+        // Contacts in range, static ones also stay visible in explored space
+        let mut blip_rects = vec![MapRect {
+            x: 0.0,
+            y: 0.0,
+            width: config.blip_size,
+            height: config.blip_size,
+            color: PLAYER_COLOR,
+        }];
+        for (entity, contact, transform) in (&entities, &contacts, &transforms).join() {
+            let x = transform.translation().x;
+            let y = transform.translation().y;
+            let (dx, dy) = (x - player_x, y - player_y);
+            if dx.abs() > config.map_range || dy.abs() > config.map_range {
+                continue;
+            }
+            let in_range = (dx * dx + dy * dy).sqrt() <= config.range;
+            if !in_range && !(contact.kind.is_static() && fog.is_explored(x, y)) {
+                continue;
+            }
+            let color = match astroids.get(entity) {
+                Some(astroid) if astroid.scanned => astroid.main_ore().radar_color(),
+                _ => contact.kind.radar_color(),
+            };
+            blip_rects.push(MapRect {
+                x: dx * scale,
+                y: dy * scale,
+                width: config.blip_size,
+                height: config.blip_size,
+                color,
+            });
+        }
+
+        let mut pools = [
+            (&mut self.fog_tiles, fog_rects, 1.0),
+            (&mut self.blips, blip_rects, 2.0),
+        ];
+        for (pool, rects, z) in pools.iter_mut() {
+            for (index, rect) in rects.iter().enumerate() {
+                if index == pool.len() {
+                    let entity = entities
+                        .build_entity()
+                        .with(
+                            UiTransform::new(
+                                "radar_blip".to_string(),
+                                Anchor::Middle,
+                                Anchor::Middle,
+                                0.0,
+                                0.0,
+                                *z,
+                                0.0,
+                                0.0,
+                            ),
+                            &mut ui_transforms,
+                        )
+                        .with(Parent { entity: panel }, &mut parents)
+                        .build();
+                    pool.push(entity);
+                }
+                let entity = pool[index];
+                if let Some(ui_transform) = ui_transforms.get_mut(entity) {
+                    ui_transform.local_x = rect.x;
+                    ui_transform.local_y = rect.y;
+                    ui_transform.width = rect.width;
+                    ui_transform.height = rect.height;
+                }
+                images
+                    .insert(entity, UiImage::SolidColor(rect.color))
+                    .expect("Radar entity was deleted");
+                hiddens.remove(entity);
+            }
+            // Hide whatever is left over in the pool
+            for entity in pool.iter().skip(rects.len()) {
+                hiddens
+                    .insert(*entity, Hidden)
+                    .expect("Radar entity was deleted");
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::config::RecipeBook;
+use crate::solar_lords::{Cargo, RefineJob, Refinery};
+
+//Runs each ship's refinery, taking ore from the hold and returning goods when a batch completes.
+//A finished batch waits in the refinery until the hold has room for all of it.
+#[derive(SystemDesc)]
+pub struct RefinerySystem;
+
+impl<'s> System<'s> for RefinerySystem {
+    type SystemData = (
+        WriteStorage<'s, Refinery>,
+        WriteStorage<'s, Cargo>,
+        Read<'s, RecipeBook>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (mut refineries, mut cargos, book, time): Self::SystemData) {
+        for (refinery, cargo) in (&mut refineries, &mut cargos).join() {
+            let mut finished = None;
+            if let Some(job) = refinery.job.as_mut() {
+                job.remaining -= time.delta_seconds();
+                if job.remaining > 0.0 {
+                    continue;
+                }
+                if let Some(recipe) = book.recipe(&job.recipe) {
+                    let output = recipe.outputs.iter().map(|(_, amount)| amount).sum::<u32>();
+                    if output > cargo.capacity.saturating_sub(cargo.total()) {
+                        job.remaining = 0.0;
+                        continue;
+                    }
+                    for (good, amount) in recipe.outputs.iter() {
+                        cargo.add_good(*good, *amount);
+                    }
+                }
+                finished = refinery.job.take().map(|job| job.recipe);
+            }
+
+            // Start with the recipe after the one that just finished so every enabled recipe gets a turn
+            let count = book.refining.len();
+            let start = finished
+                .and_then(|name| book.refining.iter().position(|recipe| recipe.name == name))
+                .map_or(0, |index| index + 1);
+            for offset in 0..count {
+                let recipe = &book.refining[(start + offset) % count];
+                if refinery.enabled.contains(&recipe.name) && cargo.take_ores(&recipe.inputs) {
+                    refinery.job = Some(RefineJob {
+                        recipe: recipe.name.clone(),
+                        remaining: recipe.seconds,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
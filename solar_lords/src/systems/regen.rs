@@ -0,0 +1,48 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, Write},
+};
+
+use crate::config::RegenConfig;
+use crate::regen::advance;
+use crate::solar_lords::{Astroid, Comet, Meteor, Player, SectorHistory, SectorRegen};
+
+//Keeps the galaxy clock and grows new astroids back in the player's sector
+#[derive(SystemDesc)]
+pub struct RegenSystem;
+
+impl<'s> System<'s> for RegenSystem {
+    type SystemData = (
+        Option<Write<'s, SectorRegen>>,
+        Write<'s, SectorHistory>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, Comet>,
+        ReadStorage<'s, Meteor>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Transform>,
+        Read<'s, RegenConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (regen, mut history, astroids, comets, meteors, players, transforms, config, time): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        history.clock += delta;
+
+        let mut regen = match regen {
+            Some(regen) => regen,
+            None => return,
+        };
+        // Comets and meteors are only passing through and don't take up room
+        let count = (&astroids, !&comets, !&meteors).join().count();
+        let ship = (&players, &transforms)
+            .join()
+            .map(|(_, transform)| (transform.translation().x, transform.translation().y))
+            .next()
+            .unwrap_or((0.0, 0.0));
+        advance(&mut regen, &config, count, delta, ship);
+    }
+}
@@ -0,0 +1,36 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::prelude::{Read, System, SystemData, Write},
+};
+
+use crate::input::PlayerInput;
+use crate::replay::InputTape;
+
+//Replaces the sampled PlayerInput with the recorded one while a replay is running,
+//and the frame's delta with the recorded tick length so every system steps the same
+#[derive(SystemDesc)]
+pub struct InputReplaySystem;
+
+impl<'s> System<'s> for InputReplaySystem {
+    type SystemData = (Write<'s, InputTape>, Write<'s, PlayerInput>, Write<'s, Time>);
+
+    fn run(&mut self, (mut tape, mut player_input, mut time): Self::SystemData) {
+        if let Some((input, delta)) = tape.next_input() {
+            *player_input = input;
+            time.set_delta_seconds(delta);
+        }
+    }
+}
+
+//Appends this tick's PlayerInput and delta to the recording
+#[derive(SystemDesc)]
+pub struct InputRecordSystem;
+
+impl<'s> System<'s> for InputRecordSystem {
+    type SystemData = (Write<'s, InputTape>, Read<'s, PlayerInput>, Read<'s, Time>);
+
+    fn run(&mut self, (mut tape, player_input, time): Self::SystemData) {
+        tape.record(&player_input, time.delta_seconds());
+    }
+}
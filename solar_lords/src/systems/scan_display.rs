@@ -0,0 +1,57 @@
+use amethyst::{
+    core::{math::Vector3, transform::Transform},
+    derive::SystemDesc,
+    ecs::prelude::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+use crate::solar_lords::{Player, ScanPulse};
+
+//Small square star sprite, stretched over the pulse
+const PULSE_SPRITE: usize = 5;
+const PULSE_SPRITE_SIZE: f32 = 3.0;
+
+//Draws scan pulses as a flash that grows and fades out
+#[derive(SystemDesc)]
+pub struct ScanDisplaySystem;
+
+impl<'s> System<'s> for ScanDisplaySystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, ScanPulse>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Tint>,
+        WriteStorage<'s, Transparent>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, pulses, players, mut transforms, mut sprites, mut tints, mut transparents): Self::SystemData,
+    ) {
+        // Pulses borrow the player's sprite sheet
+        let sheet = match (&players, &sprites).join().next() {
+            Some((_, sprite)) => sprite.sprite_sheet.clone(),
+            None => return,
+        };
+
+        for (entity, pulse, transform) in (&entities, &pulses, &mut transforms).join() {
+            if !sprites.contains(entity) {
+                sprites
+                    .insert(entity, SpriteRender::new(sheet.clone(), PULSE_SPRITE))
+                    .expect("Failed to add scan pulse sprite");
+                transparents
+                    .insert(entity, Transparent)
+                    .expect("Failed to make scan pulse transparent");
+            }
+            let scale = pulse.radius * 2.0 / PULSE_SPRITE_SIZE;
+            transform.set_scale(Vector3::new(scale, scale, 1.0));
+            transform.set_translation_z(-0.5);
+            let fade = 1.0 - pulse.radius / pulse.max_radius;
+            tints
+                .insert(entity, Tint(Srgba::new(0.3, 1.0, 0.6, 0.25 * fade)))
+                .expect("Failed to tint scan pulse");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform, Named},
+    derive::SystemDesc,
+    ecs::prelude::{Builder, Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::config::ScannerConfig;
+use crate::input::PlayerInput;
+use crate::solar_lords::{Astroid, Capacitor, Player, ScanPulse, Scanner};
+
+//Sends out scan pulses and reveals the astroids they sweep over
+#[derive(SystemDesc)]
+pub struct ScannerSystem;
+
+impl<'s> System<'s> for ScannerSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Scanner>,
+        WriteStorage<'s, Capacitor>,
+        WriteStorage<'s, ScanPulse>,
+        WriteStorage<'s, Astroid>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Named>,
+        Read<'s, ScannerConfig>,
+        Read<'s, PlayerInput>,
+        Read<'s, Time>,
+        Read<'s, LazyUpdate>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, players, mut scanners, mut capacitors, mut pulses, mut astroids, transforms, names, config, input, time, lazy): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+
+        for (player, scanner, capacitor, transform) in (&players, &mut scanners, &mut capacitors, &transforms).join() {
+            scanner.cooldown = (scanner.cooldown - delta).max(0.0);
+            if !input.scan || scanner.cooldown > 0.0 || !capacitor.draw(config.energy_cost) {
+                continue;
+            }
+            scanner.cooldown = config.cooldown;
+            let origin = (transform.translation().x, transform.translation().y);
+            lazy.create_entity(&entities)
+                .with(ScanPulse::new(origin, config.pulse_range))
+                .with(transform.clone())
+                .build();
+
+            // The target gets a close up look if it is near enough
+            let detail_squared = config.detail_range * config.detail_range;
+            if let Some(target) = player.target.as_ref() {
+                for (astroid, astroid_transform, name) in (&mut astroids, &transforms, &names).join() {
+                    let offset = astroid_transform.translation() - transform.translation();
+                    if name.name == *target && offset.x * offset.x + offset.y * offset.y <= detail_squared {
+                        astroid.scanned = true;
+                        astroid.detailed = true;
+                    }
+                }
+            }
+        }
+
+        for (entity, pulse) in (&entities, &mut pulses).join() {
+            pulse.radius = (pulse.radius + config.pulse_speed * delta).min(pulse.max_radius);
+            let radius_squared = pulse.radius * pulse.radius;
+            for (astroid, transform) in (&mut astroids, &transforms).join() {
+                let x = transform.translation().x - pulse.origin.0;
+                let y = transform.translation().y - pulse.origin.1;
+                if x * x + y * y <= radius_squared {
+                    astroid.scanned = true;
+                }
+            }
+            if pulse.radius >= pulse.max_radius {
+                entities.delete(entity).expect("Failed to delete finished scan pulse");
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use amethyst::{
+    assets::AssetStorage,
+    core::{math::Vector3, transform::Transform, Named},
+    derive::SystemDesc,
+    ecs::prelude::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    renderer::{SpriteRender, SpriteSheet},
+};
+
+use crate::input::{Aim, PlayerInput};
+use crate::solar_lords::{Astroid, Npc, Player, SHIP_SPRITE_SIZE};
+
+//Astroid size used until its sprite sheet has loaded, and when running headless
+pub const DEFAULT_ASTROID_SIZE: (f32, f32) = (48.0, 48.0);
+pub const GAMEPAD_AIM_RANGE: f32 = 400.0;
+
+//Picks the player's target, an astroid or NPC ship, from the Aim in PlayerInput
+#[derive(SystemDesc)]
+pub struct TargetingSystem;
+
+impl<'s> System<'s> for TargetingSystem {
+    type SystemData = (
+        WriteStorage<'s, Player>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Astroid>,
+        ReadStorage<'s, Npc>,
+        ReadStorage<'s, Named>,
+        ReadStorage<'s, SpriteRender>,
+        Read<'s, AssetStorage<SpriteSheet>>,
+        Read<'s, PlayerInput>,
+    );
+
+    fn run(
+        &mut self,
+        (mut players, transforms, astroids, npcs, names, sprites, sheet_storage, input): Self::SystemData,
+    ) {
+        for (player, player_transform) in (&mut players, &transforms).join() {
+            let origin = *player_transform.translation();
+            // Fragments and smaller ships are drawn scaled down
+            let candidate = |transform: &Transform, name, sprite: Option<&SpriteRender>, fallback: (f32, f32)| {
+                let (width, height) = sprite
+                    .and_then(|sprite| sprite_size(&sheet_storage, sprite))
+                    .unwrap_or(fallback);
+                let scale = transform.scale();
+                (*transform.translation(), (width * scale.x, height * scale.y), name)
+            };
+            let ships = (&npcs, &transforms, &names, sprites.maybe())
+                .join()
+                .map(|(_, transform, name, sprite)| candidate(transform, name, sprite, SHIP_SPRITE_SIZE));
+            let candidates = (&astroids, &transforms, &names, sprites.maybe())
+                .join()
+                .map(|(_, transform, name, sprite)| candidate(transform, name, sprite, DEFAULT_ASTROID_SIZE))
+                .chain(ships);
+
+            let mut found_name = None;
+            match input.aim {
+                // Find any sprites which the mouse is currently inside
+                Some(Aim::Point(x, y)) => {
+                    for (position, (width, height), name) in candidates {
+                        if x > position.x - width * 0.5
+                            && x < position.x + width * 0.5
+                            && y > position.y - height * 0.5
+                            && y < position.y + height * 0.5
+                        {
+                            found_name = Some(name);
+                        }
+                    }
+                }
+                // Cast a ray from the ship along the stick and take the first thing it touches
+                Some(Aim::Direction(x, y)) => {
+                    let direction = Vector3::new(x, y, 0.0).normalize();
+                    let mut closest = GAMEPAD_AIM_RANGE;
+                    for (position, (width, height), name) in candidates {
+                        let mut offset = position - origin;
+                        offset.z = 0.0;
+                        let along = offset.dot(&direction);
+                        let across = (offset - direction * along).norm();
+                        if along > 0.0 && along < closest && across < width.max(height) * 0.5 {
+                            closest = along;
+                            found_name = Some(name);
+                        }
+                    }
+                }
+                // Nothing aiming, keep whatever was targeted
+                None => continue,
+            }
+
+            // Aiming at an astroid or ship targets it, aiming at empty space clears the target
+            player.target = found_name.map(|name| name.name.to_string());
+        }
+    }
+}
+
+//Size of a sprite once its sheet has loaded
+pub fn sprite_size(sheet_storage: &AssetStorage<SpriteSheet>, sprite: &SpriteRender) -> Option<(f32, f32)> {
+    let sprite_sheet = sheet_storage.get(&sprite.sprite_sheet)?;
+    let sprite = sprite_sheet.sprites.get(sprite.sprite_number)?;
+    Some((sprite.width, sprite.height))
+}
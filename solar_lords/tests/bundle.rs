@@ -0,0 +1,7 @@
+use solar_lords::headless::HeadlessRunner;
+
+#[test]
+fn the_simulation_dispatcher_builds() {
+    // Systems have to be added after everything they depend on, or the builder panics
+    assert!(HeadlessRunner::new().is_ok());
+}
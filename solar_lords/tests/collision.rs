@@ -0,0 +1,170 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, moving, runner};
+use solar_lords::config::CollisionConfig;
+use solar_lords::collision::{Shape, WorldShape};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, AstroidSize, JumpRequest, Npc, NpcRole, Ore, Player, Velocity};
+
+fn placed(shape: Shape, x: f32, y: f32, angle: f32) -> WorldShape {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(x, y, 0.0);
+    transform.set_rotation_2d(angle);
+    shape.placed(&transform)
+}
+
+fn x_of(runner: &HeadlessRunner, entity: Entity) -> f32 {
+    runner.world.read_storage::<Transform>().get(entity).unwrap().translation().x
+}
+
+fn velocity_x(runner: &HeadlessRunner, entity: Entity) -> f32 {
+    runner.world.read_storage::<Velocity>().get(entity).unwrap().x
+}
+
+//A player with an astroid the given distance to its right
+fn runner_with_astroid_ahead(distance: f32) -> (HeadlessRunner<'static, 'static>, Entity, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let position = *runner.world.read_storage::<Transform>().get(player).unwrap().translation();
+    let astroid = runner.spawn_astroid(Astroid::new(Ore::Ice), position.x + distance, position.y, 0);
+    (runner, player, astroid)
+}
+
+#[test]
+fn circles_overlap_along_the_line_between_them() {
+    let a = placed(Shape::Circle(10.0), 0.0, 0.0, 0.0);
+    let b = placed(Shape::Circle(10.0), 15.0, 0.0, 0.0);
+    let contact = a.contact(&b).unwrap();
+    assert!((contact.depth - 5.0).abs() < 0.001);
+    assert!((contact.normal.0 - 1.0).abs() < 0.001);
+
+    let far = placed(Shape::Circle(10.0), 25.0, 0.0, 0.0);
+    assert_eq!(a.contact(&far), None);
+}
+
+#[test]
+fn normal_points_from_the_first_shape_to_the_second() {
+    let a = placed(Shape::sprite_box(20.0, 20.0), 0.0, 0.0, 0.0);
+    let b = placed(Shape::Circle(10.0), -15.0, 0.0, 0.0);
+    let contact = a.contact(&b).unwrap();
+    assert!((contact.normal.0 + 1.0).abs() < 0.001);
+    assert!((contact.depth - 5.0).abs() < 0.001);
+}
+
+#[test]
+fn rotated_boxes_use_their_rotation() {
+    // A 10x100 box lying flat reaches a circle 40 to its right, standing up it doesn't
+    let circle = placed(Shape::Circle(5.0), 40.0, 0.0, 0.0);
+    let standing = placed(Shape::sprite_box(10.0, 100.0), 0.0, 0.0, 0.0);
+    let lying = placed(Shape::sprite_box(10.0, 100.0), 0.0, 0.0, std::f32::consts::FRAC_PI_2);
+    assert_eq!(standing.contact(&circle), None);
+    assert!(lying.contact(&circle).is_some());
+}
+
+#[test]
+fn circle_near_a_box_corner_only_touches_when_close_enough() {
+    let square = placed(Shape::sprite_box(20.0, 20.0), 0.0, 0.0, 0.0);
+    // 13.4 from the corner diagonally, the boxes around them would overlap
+    let clear = placed(Shape::Circle(5.0), 14.0, 14.0, 0.0);
+    let touching = placed(Shape::Circle(5.0), 12.0, 12.0, 0.0);
+    assert_eq!(square.contact(&clear), None);
+    assert!(square.contact(&touching).is_some());
+}
+
+#[test]
+fn ship_cannot_fly_through_an_astroid() {
+    let (mut runner, player, astroid) = runner_with_astroid_ahead(100.0);
+
+    runner.run(180, |_, _| moving(1.0, 0.0));
+
+    assert!(x_of(&runner, player) < x_of(&runner, astroid));
+    // Facing right the ship is 96 long, the astroid 48 across
+    assert!(x_of(&runner, astroid) - x_of(&runner, player) > 70.0);
+}
+
+#[test]
+fn astroids_bounce_off_each_other() {
+    let mut runner = runner();
+    let moving_astroid = runner.spawn_astroid(Astroid::new(Ore::Ice).fragment(AstroidSize::Medium, 50), 0.0, 0.0, 0);
+    let resting_astroid = runner.spawn_astroid(Astroid::new(Ore::Ice).fragment(AstroidSize::Medium, 50), 100.0, 0.0, 1);
+    runner.world.write_storage::<Velocity>().get_mut(moving_astroid).unwrap().x = 100.0;
+
+    runner.run(120, |_, _| PlayerInput::default());
+
+    // Equal masses swap their speed
+    assert!(velocity_x(&runner, resting_astroid) > 10.0);
+    assert!(velocity_x(&runner, moving_astroid).abs() < velocity_x(&runner, resting_astroid));
+}
+
+#[test]
+fn hard_impacts_damage_shields_first() {
+    let (mut runner, player, _) = runner_with_astroid_ahead(100.0);
+    runner.world.write_storage::<Velocity>().get_mut(player).unwrap().x = 300.0;
+
+    runner.run(30, |_, _| PlayerInput::default());
+
+    let players = runner.world.read_storage::<Player>();
+    let player = players.get(player).unwrap();
+    assert!(player.sheild < 100);
+    assert_eq!(player.hull, 100);
+}
+
+#[test]
+fn npc_ships_take_impact_damage_too() {
+    let mut runner = runner();
+    let npc = runner.spawn_npc(NpcRole::Miner, 0.0, 0.0);
+    let astroid = runner.spawn_astroid(Astroid::new(Ore::Ice).fragment(AstroidSize::Medium, 50), -100.0, 0.0, 0);
+    runner.world.write_storage::<Velocity>().get_mut(astroid).unwrap().x = 300.0;
+
+    runner.run(30, |_, _| PlayerInput::default());
+
+    let npcs = runner.world.read_storage::<Npc>();
+    let npc = npcs.get(npc).unwrap();
+    assert!(npc.hull < npc.archetype.hull);
+}
+
+#[test]
+fn gentle_bumps_do_no_damage() {
+    let (mut runner, player, _) = runner_with_astroid_ahead(100.0);
+
+    runner.run(180, |_, _| moving(0.5, 0.0));
+
+    let players = runner.world.read_storage::<Player>();
+    assert_eq!(players.get(player).unwrap().sheild, 100);
+}
+
+#[test]
+fn bounced_ship_is_knocked_back() {
+    let (mut runner, player, _) = runner_with_astroid_ahead(100.0);
+    runner.world.write_storage::<Velocity>().get_mut(player).unwrap().x = 300.0;
+
+    runner.run(30, |_, _| PlayerInput::default());
+
+    assert!(velocity_x(&runner, player) < 0.0);
+}
+
+#[test]
+fn jumping_into_a_sector_is_not_an_impact() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.run(60, |_, _| moving(1.0, 0.0));
+    runner.world.insert(JumpRequest(Some((1, 0))));
+    runner.step(PlayerInput::default());
+
+    // Right where the ship comes out, the jump mustn't count as flying into it
+    let position = *runner.world.read_storage::<Transform>().get(player).unwrap().translation();
+    runner.spawn_astroid(Astroid::new(Ore::Ice), position.x + 20.0, position.y, 99);
+    runner.step(PlayerInput::default());
+
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().sheild, 100);
+}
+
+#[test]
+fn shipped_collision_matches_the_defaults() {
+    assert_shipped_config::<CollisionConfig>("collision.ron");
+}
@@ -0,0 +1,69 @@
+//Helpers shared by the integration tests, not every test file uses all of them
+#![allow(dead_code)]
+
+use amethyst::config::Config;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+use std::fmt::Debug;
+use std::path::Path;
+
+use solar_lords::config::{ModuleConfig, ModuleEffect, SlotKind};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, Fitting, MiningTool, Ore, Player};
+
+pub fn runner() -> HeadlessRunner<'static, 'static> {
+    HeadlessRunner::new().expect("Failed to build the headless simulation")
+}
+
+//A player with one ice astroid next to it, named "astroid 0"
+pub fn runner_with_astroid() -> (HeadlessRunner<'static, 'static>, Entity, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let astroid = runner.spawn_astroid(Astroid::new(Ore::Ice), 0.0, 0.0, 0);
+    (runner, player, astroid)
+}
+
+pub fn set_target(runner: &mut HeadlessRunner, player: Entity, target: Option<&str>) {
+    runner
+        .world
+        .write_storage::<Player>()
+        .get_mut(player)
+        .unwrap()
+        .target = target.map(|target| target.to_string());
+}
+
+pub fn mining() -> PlayerInput {
+    PlayerInput {
+        mine: true,
+        ..Default::default()
+    }
+}
+
+pub fn moving(move_x: f32, move_y: f32) -> PlayerInput {
+    PlayerInput {
+        move_x,
+        move_y,
+        ..Default::default()
+    }
+}
+
+//Swaps the fitted mining module for the catalogue one with this tool
+pub fn fit_mining_tool(runner: &mut HeadlessRunner, player: Entity, tool: MiningTool) {
+    let module = ModuleConfig::default()
+        .modules
+        .into_iter()
+        .find(|module| module.effect == ModuleEffect::MiningTool(tool))
+        .expect("Every mining tool has a module");
+    let mut fittings = runner.world.write_storage::<Fitting>();
+    let fitting = fittings.get_mut(player).unwrap();
+    fitting.modules.retain(|module| module.slot != SlotKind::Mining);
+    fitting.modules.push(module);
+}
+
+//Loads a file from config/ and checks it holds exactly what the game falls back to without it
+pub fn assert_shipped_config<C: Config + Default + PartialEq + Debug>(file: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("config").join(file);
+    let shipped = C::load(&path).unwrap_or_else(|err| panic!("{} should parse: {}", file, err));
+    assert_eq!(shipped, C::default(), "{} has drifted from the defaults", file);
+}
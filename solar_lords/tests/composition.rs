@@ -0,0 +1,79 @@
+mod common;
+
+use amethyst::ecs::prelude::Join;
+use amethyst::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::HashMap;
+
+use common::{fit_mining_tool, mining, runner, set_target};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::solar_lords::{Astroid, MiningTool, Ore, OrePickup};
+
+//Total of each ore lying around as pickups
+fn dropped(runner: &HeadlessRunner) -> HashMap<Ore, u32> {
+    let mut totals = HashMap::new();
+    for pickup in (&runner.world.read_storage::<OrePickup>()).join() {
+        *totals.entry(pickup.ore).or_insert(0) += pickup.amount;
+    }
+    totals
+}
+
+fn mine_with(tool: MiningTool, composition: Vec<(Ore, f32)>, ticks: u64) -> HashMap<Ore, u32> {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_astroid(Astroid::with_composition(composition, 1000), 0.0, 0.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+    fit_mining_tool(&mut runner, player, tool);
+    runner.run(ticks, |_, _| mining());
+    dropped(&runner)
+}
+
+#[test]
+fn each_ore_is_extracted_by_share_and_tool() {
+    let ore = mine_with(MiningTool::Laser, vec![(Ore::Iron, 0.5), (Ore::Gold, 0.5)], 10);
+    assert_eq!(ore.get(&Ore::Iron), Some(&4));
+    assert_eq!(ore.get(&Ore::Gold), Some(&2));
+}
+
+#[test]
+fn better_tools_recover_more_of_the_rare_ores() {
+    let composition = vec![(Ore::Iron, 0.5), (Ore::Gold, 0.5)];
+    let laser = mine_with(MiningTool::Laser, composition.clone(), 100);
+    let disintegrator = mine_with(MiningTool::MatterDisintegrator, composition, 100);
+    assert!(disintegrator[&Ore::Gold] > laser[&Ore::Gold]);
+    assert!(disintegrator[&Ore::Iron] > laser[&Ore::Iron]);
+}
+
+#[test]
+fn basic_laser_gets_nothing_from_plasma() {
+    let ore = mine_with(MiningTool::Laser, vec![(Ore::Plasma, 1.0)], 100);
+    assert_eq!(ore.get(&Ore::Plasma), None);
+}
+
+#[test]
+fn fractions_carry_over_between_chunks() {
+    // 0.7 * 0.8 = 0.56 Iron and 0.3 * 0.8 = 0.24 Copper per unit of rock
+    let ore = mine_with(MiningTool::Laser, vec![(Ore::Iron, 0.7), (Ore::Copper, 0.3)], 20);
+    assert_eq!(ore.get(&Ore::Iron), Some(&11));
+    assert_eq!(ore.get(&Ore::Copper), Some(&4));
+}
+
+#[test]
+fn raw_ore_split_keeps_every_unit() {
+    let astroid = Astroid::with_composition(vec![(Ore::Iron, 0.7), (Ore::Copper, 0.25), (Ore::Gold, 0.05)], 1000);
+    assert_eq!(astroid.main_ore(), Ore::Iron);
+    assert_eq!(astroid.ore_in(15), vec![(Ore::Iron, 12), (Ore::Copper, 3)]);
+    assert_eq!(astroid.ore_in(100), vec![(Ore::Iron, 70), (Ore::Copper, 25), (Ore::Gold, 5)]);
+}
+
+#[test]
+fn random_astroids_are_a_full_mix_of_ores() {
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0..100 {
+        let astroid = Astroid::random(&mut rng);
+        let total = astroid.composition.iter().map(|(_, share)| share).sum::<f32>();
+        assert!((total - 1.0).abs() < 0.001);
+        assert!(astroid.composition.len() <= 3);
+        assert!(!astroid.scanned);
+    }
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use common::{assert_shipped_config, mining, runner, runner_with_astroid, set_target};
+use solar_lords::config::{ContractConfig, FactionConfig, MarketConfig, NpcConfig};
+use solar_lords::contracts::generate_contracts;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{
+    accept_contract, Cargo, Contract, ContractBoard, ContractKind, ContractStatus, Faction, Npc, NpcRole, Ore, Player,
+    QuestLog, Reputation,
+};
+
+fn position(runner: &HeadlessRunner, entity: Entity) -> (f32, f32) {
+    let transforms = runner.world.read_storage::<Transform>();
+    let translation = transforms.get(entity).unwrap().translation();
+    (translation.x, translation.y)
+}
+
+fn offers(seed: u64) -> Vec<Contract> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let config = ContractConfig {
+        offers: 50,
+        ..Default::default()
+    };
+    generate_contracts(
+        &mut rng,
+        "Depot",
+        (0, 0),
+        &config,
+        &MarketConfig::default(),
+        &NpcConfig::default(),
+        &FactionConfig::default(),
+    )
+}
+
+//Puts a contract straight into the player's log
+fn take(runner: &mut HeadlessRunner, player: Entity, kind: ContractKind, time_limit: f32) {
+    let contract = Contract::new(kind, Faction::StationAuthority, "Depot".to_string(), 500.0, 10.0, time_limit);
+    runner.world.write_storage::<QuestLog>().get_mut(player).unwrap().contracts.push(contract);
+}
+
+fn contract(runner: &HeadlessRunner, player: Entity) -> Contract {
+    runner.world.read_storage::<QuestLog>().get(player).unwrap().contracts[0].clone()
+}
+
+fn dock() -> PlayerInput {
+    PlayerInput {
+        dock: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn boards_offer_every_kind_of_work() {
+    let rolled = offers(3);
+    let config = ContractConfig::default();
+    let market = MarketConfig::default();
+
+    assert_eq!(rolled.len(), 50);
+    for offer in rolled.iter() {
+        match offer.kind {
+            ContractKind::Deliver { ore, amount } => {
+                assert_eq!(offer.reward, amount as f32 * market.base_price(ore) * config.delivery_premium);
+                assert_eq!(offer.station, "Depot");
+            }
+            // Only the pirates are at war with the station authority
+            ContractKind::Destroy { role, .. } => assert_eq!(role, NpcRole::Pirate),
+            ContractKind::Survey { sector } => {
+                assert_ne!(sector, (0, 0));
+                assert!(sector.0.abs() <= config.survey_range && sector.1.abs() <= config.survey_range);
+                assert_eq!(offer.issuer, Faction::MinersGuild);
+            }
+        }
+    }
+    assert!(rolled.iter().any(|offer| matches!(offer.kind, ContractKind::Deliver { .. })));
+    assert!(rolled.iter().any(|offer| matches!(offer.kind, ContractKind::Destroy { .. })));
+    assert!(rolled.iter().any(|offer| matches!(offer.kind, ContractKind::Survey { .. })));
+    // The same seed rolls the same board
+    assert_eq!(rolled, offers(3));
+}
+
+#[test]
+fn contracts_are_taken_from_the_board_while_docked() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let station = runner.spawn_station("Depot", 0.0, 0.0);
+    let offered = runner.world.read_storage::<ContractBoard>().get(station).unwrap().offers.clone();
+    assert_eq!(offered.len(), ContractConfig::default().offers);
+
+    assert!(accept_contract(&mut runner.world, player, 0).is_err());
+
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = Some("Depot".to_string());
+    let taken = accept_contract(&mut runner.world, player, 0).unwrap();
+
+    assert_eq!(taken, offered[0]);
+    assert_eq!(contract(&runner, player), offered[0]);
+    assert_eq!(runner.world.read_storage::<ContractBoard>().get(station).unwrap().offers, offered[1..].to_vec());
+}
+
+#[test]
+fn only_so_many_contracts_at_once() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_station("Depot", 0.0, 0.0);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = Some("Depot".to_string());
+    for _ in 0..ContractConfig::default().max_active {
+        take(&mut runner, player, ContractKind::Survey { sector: (1, 0) }, 100.0);
+    }
+
+    assert!(accept_contract(&mut runner.world, player, 0).is_err());
+}
+
+#[test]
+fn docking_hands_over_deliveries() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    runner.spawn_station("Depot", x + 100.0, y);
+    take(&mut runner, player, ContractKind::Deliver { ore: Ore::Copper, amount: 300 }, 600.0);
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add(Ore::Copper, 200);
+
+    // Part of the way there
+    runner.step(dock());
+    assert_eq!(contract(&runner, player).progress, 200);
+    assert_eq!(contract(&runner, player).status, ContractStatus::Active);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().amount(Ore::Copper), 0);
+
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = None;
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add(Ore::Copper, 150);
+    runner.step(dock());
+
+    assert_eq!(contract(&runner, player).status, ContractStatus::Completed);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().amount(Ore::Copper), 50);
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 500.0);
+    let reputations = runner.world.read_storage::<Reputation>();
+    assert_eq!(reputations.get(player).unwrap().standing(Faction::StationAuthority), 10.0);
+}
+
+#[test]
+fn bounties_count_ships_the_player_wrecks() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let pirate = runner.spawn_npc(NpcRole::Pirate, x + 200.0, y);
+    runner.world.write_storage::<Npc>().get_mut(pirate).unwrap().hull = 1;
+    set_target(&mut runner, player, Some("Pirate 0"));
+    take(&mut runner, player, ContractKind::Destroy { role: NpcRole::Pirate, count: 1 }, 600.0);
+
+    runner.run(30, |_, _| PlayerInput {
+        fire: true,
+        ..Default::default()
+    });
+
+    assert!(!runner.world.is_alive(pirate));
+    assert_eq!(contract(&runner, player).status, ContractStatus::Completed);
+}
+
+#[test]
+fn bounties_skip_ships_someone_else_finished_off() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let miner = runner.spawn_npc(NpcRole::Miner, x + 200.0, y);
+    set_target(&mut runner, player, Some("Miner 0"));
+    take(&mut runner, player, ContractKind::Destroy { role: NpcRole::Miner, count: 1 }, 600.0);
+    runner.run(30, |_, _| PlayerInput {
+        fire: true,
+        ..Default::default()
+    });
+
+    // A pirate right next to the miner lands the last hit
+    runner.world.write_storage::<Npc>().get_mut(miner).unwrap().hull = 1;
+    runner.spawn_npc(NpcRole::Pirate, x + 220.0, y);
+    runner.run(120, |_, _| PlayerInput::default());
+
+    assert!(!runner.world.is_alive(miner));
+    assert_eq!(contract(&runner, player).status, ContractStatus::Active);
+}
+
+#[test]
+fn surveys_need_ore_mined_in_the_sector() {
+    let (mut runner, player, _) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    take(&mut runner, player, ContractKind::Survey { sector: (1, 0) }, 600.0);
+    take(&mut runner, player, ContractKind::Survey { sector: (0, 0) }, 600.0);
+
+    runner.run(60, |_, _| mining());
+
+    let logs = runner.world.read_storage::<QuestLog>();
+    let contracts = &logs.get(player).unwrap().contracts;
+    assert_eq!(contracts[0].status, ContractStatus::Active);
+    assert_eq!(contracts[1].status, ContractStatus::Completed);
+}
+
+#[test]
+fn running_out_of_time_fails_the_contract() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    take(&mut runner, player, ContractKind::Survey { sector: (1, 0) }, 0.5);
+
+    runner.run(60, |_, _| PlayerInput::default());
+
+    assert_eq!(contract(&runner, player).status, ContractStatus::Failed);
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 0.0);
+    let reputations = runner.world.read_storage::<Reputation>();
+    assert_eq!(
+        reputations.get(player).unwrap().standing(Faction::StationAuthority),
+        -ContractConfig::default().failure_penalty
+    );
+}
+
+#[test]
+fn shipped_contracts_match_the_defaults() {
+    assert_shipped_config::<ContractConfig>("contracts.ron");
+}
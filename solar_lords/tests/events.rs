@@ -0,0 +1,169 @@
+mod common;
+
+use amethyst::core::{transform::Transform, Named};
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::EventConfig;
+use solar_lords::director::{choose_event, event_interval};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{
+    Astroid, AstroidSize, Comet, Npc, NpcRole, Ore, Player, SectorEventKind, SectorEvents, Velocity,
+};
+
+//Only this event can happen, and the first one is due right away
+fn only(runner: &mut HeadlessRunner, kind: SectorEventKind, danger: f32) {
+    let config = EventConfig {
+        events: vec![(kind, 1.0, 0.0)],
+        ..Default::default()
+    };
+    let mut events = SectorEvents::new(danger, 7, &config);
+    events.next_event = 0.0;
+    runner.world.insert(config);
+    runner.world.insert(events);
+}
+
+fn named(runner: &HeadlessRunner, prefix: &str) -> Vec<Entity> {
+    let entities = runner.world.entities();
+    let names = runner.world.read_storage::<Named>();
+    (&entities, &names)
+        .join()
+        .filter(|(_, name)| name.name.starts_with(prefix))
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn idle() -> PlayerInput {
+    PlayerInput::default()
+}
+
+#[test]
+fn far_sectors_are_more_dangerous() {
+    let config = EventConfig::default();
+
+    assert_eq!(config.danger((0, 0)), 0.0);
+    assert!((config.danger((1, -1)) - 0.4).abs() < 0.001);
+    assert_eq!(config.danger((10, 10)), 1.0);
+    assert_eq!(event_interval(&config, 0.0), config.quiet_interval);
+    assert_eq!(event_interval(&config, 1.0), config.danger_interval);
+}
+
+#[test]
+fn pirates_never_raid_the_home_sector() {
+    let config = EventConfig::default();
+    let mut rng = StdRng::seed_from_u64(5);
+
+    let home = (0..500)
+        .filter_map(|_| choose_event(&mut rng, &config, 0.0))
+        .collect::<Vec<_>>();
+    let frontier = (0..500)
+        .filter_map(|_| choose_event(&mut rng, &config, 1.0))
+        .collect::<Vec<_>>();
+
+    assert_eq!(home.len(), 500);
+    assert!(!home.contains(&SectorEventKind::PirateRaid));
+    assert!(frontier.contains(&SectorEventKind::PirateRaid));
+}
+
+#[test]
+fn meteor_showers_bring_small_fast_astroids() {
+    let mut runner = runner();
+    runner.spawn_player();
+    only(&mut runner, SectorEventKind::MeteorShower, 0.0);
+
+    runner.step(idle());
+
+    let meteors = named(&runner, "meteor");
+    let config = EventConfig::default();
+    assert!(meteors.len() as u32 >= config.meteor_count.0 && meteors.len() as u32 <= config.meteor_count.1);
+    let astroids = runner.world.read_storage::<Astroid>();
+    let velocities = runner.world.read_storage::<Velocity>();
+    for meteor in meteors {
+        assert_eq!(astroids.get(meteor).unwrap().size, AstroidSize::Small);
+        let velocity = velocities.get(meteor).unwrap();
+        assert!((velocity.x * velocity.x + velocity.y * velocity.y).sqrt() > config.meteor_speed * 0.9);
+    }
+    let events = runner.world.read_resource::<SectorEvents>();
+    assert_eq!(events.last_event.map(|(kind, _)| kind), Some(SectorEventKind::MeteorShower));
+    assert!(events.next_event > 0.0);
+}
+
+#[test]
+fn solar_flares_drain_shields_outside_stations() {
+    let flare_shield = |docked: bool| {
+        let mut runner = runner();
+        let player = runner.spawn_player();
+        if docked {
+            runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = Some("Depot".to_string());
+        }
+        only(&mut runner, SectorEventKind::SolarFlare, 0.0);
+
+        runner.run(120, |_, _| idle());
+
+        let players = runner.world.read_storage::<Player>();
+        let player = players.get(player).unwrap();
+        (player.sheild, player.max_sheild)
+    };
+
+    let (shield, max) = flare_shield(false);
+    assert!(shield < max);
+    let (shield, max) = flare_shield(true);
+    assert_eq!(shield, max);
+}
+
+#[test]
+fn pirate_raids_spawn_raiders() {
+    let mut runner = runner();
+    runner.spawn_player();
+    only(&mut runner, SectorEventKind::PirateRaid, 1.0);
+
+    runner.step(idle());
+
+    let raiders = named(&runner, "Pirate raider");
+    let config = EventConfig::default();
+    assert!(raiders.len() as u32 >= config.raid_size.0 && raiders.len() as u32 <= config.raid_size.1);
+    let npcs = runner.world.read_storage::<Npc>();
+    assert!(raiders.iter().all(|raider| npcs.get(*raider).unwrap().role() == NpcRole::Pirate));
+}
+
+#[test]
+fn comets_carry_plasma_across_the_sector_and_move_on() {
+    let mut runner = runner();
+    runner.spawn_player();
+    only(&mut runner, SectorEventKind::CometFlyby, 0.0);
+
+    runner.step(idle());
+    let comet = named(&runner, "comet")[0];
+    assert!(runner.world.read_storage::<Astroid>().get(comet).unwrap().composition[0].0 == Ore::Plasma);
+    let lifetime = runner.world.read_storage::<Comet>().get(comet).unwrap().time_left;
+    let start = runner.world.read_storage::<Transform>().get(comet).unwrap().translation().clone();
+
+    // Keeps its speed where drag would slow an astroid down
+    runner.run(120, |_, _| idle());
+    let end = runner.world.read_storage::<Transform>().get(comet).unwrap().translation().clone();
+    let travelled = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+    assert!(travelled > EventConfig::default().comet_speed * 1.9);
+
+    runner.run((lifetime * 60.0) as u64, |_, _| idle());
+    assert!(!runner.world.is_alive(comet));
+}
+
+#[test]
+fn entering_a_sector_resets_the_director() {
+    let mut runner = runner();
+    runner.spawn_sector(11);
+
+    let events = runner.world.read_resource::<SectorEvents>();
+    let config = EventConfig::default();
+    assert_eq!(events.danger, 0.0);
+    assert!(events.next_event >= config.quiet_interval * 0.5);
+    assert!(events.last_event.is_none());
+}
+
+#[test]
+fn shipped_events_match_the_defaults() {
+    assert_shipped_config::<EventConfig>("events.ron");
+}
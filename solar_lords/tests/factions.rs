@@ -0,0 +1,214 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::FactionConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{
+    sell_ore, Cargo, ContactKind, Faction, Npc, NpcRole, Ore, Player, RadarContact, Reputation,
+};
+
+fn position(runner: &HeadlessRunner, entity: Entity) -> (f32, f32) {
+    let transforms = runner.world.read_storage::<Transform>();
+    let translation = transforms.get(entity).unwrap().translation();
+    (translation.x, translation.y)
+}
+
+fn standing(runner: &HeadlessRunner, player: Entity, faction: Faction) -> f32 {
+    runner.world.read_storage::<Reputation>().get(player).unwrap().standing(faction)
+}
+
+fn set_standing(runner: &mut HeadlessRunner, player: Entity, faction: Faction, standing: f32) {
+    let mut reputations = runner.world.write_storage::<Reputation>();
+    reputations.get_mut(player).unwrap().standing.insert(faction, standing);
+}
+
+fn fire() -> PlayerInput {
+    PlayerInput {
+        fire: true,
+        ..Default::default()
+    }
+}
+
+//Sells 100 Iron at a fresh station and returns what it paid
+fn sell_iron(runner: &mut HeadlessRunner, player: Entity) -> f32 {
+    runner.spawn_station("Depot", 0.0, 0.0);
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add(Ore::Iron, 100);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = Some("Depot".to_string());
+    sell_ore(&mut runner.world, player, Ore::Iron).unwrap().1
+}
+
+#[test]
+fn new_pilots_start_out_wanted_by_pirates() {
+    let config = FactionConfig::default();
+    let reputation = Reputation::new(&config);
+
+    assert!(reputation.is_hostile(Faction::Pirates, &config));
+    assert!(!reputation.is_hostile(Faction::MinersGuild, &config));
+    assert!(reputation.can_dock(&config));
+    assert_eq!(reputation.price_factor(&config), 1.0);
+}
+
+#[test]
+fn standing_stays_within_bounds() {
+    let config = FactionConfig::default();
+    let mut reputation = Reputation::new(&config);
+
+    reputation.change(Faction::Pirates, -500.0);
+    reputation.change(Faction::MinersGuild, 500.0);
+
+    assert_eq!(reputation.standing(Faction::Pirates), -100.0);
+    assert_eq!(reputation.standing(Faction::MinersGuild), 100.0);
+}
+
+#[test]
+fn shooting_a_miner_costs_guild_standing() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    runner.spawn_npc(NpcRole::Miner, x + 200.0, y);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().target = Some("Miner 0".to_string());
+
+    runner.run(60, |_, _| fire());
+
+    // 9 or 10 damage at half a point each
+    let guild = standing(&runner, player, Faction::MinersGuild);
+    assert!(guild <= -4.5 && guild >= -5.0);
+    assert_eq!(standing(&runner, player, Faction::StationAuthority), 0.0);
+}
+
+#[test]
+fn wrecking_a_pirate_pleases_its_rivals() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let pirate = runner.spawn_npc(NpcRole::Pirate, x + 200.0, y);
+    runner.world.write_storage::<Npc>().get_mut(pirate).unwrap().hull = 1;
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().target = Some("Pirate 0".to_string());
+
+    runner.run(30, |_, _| fire());
+
+    assert!(!runner.world.is_alive(pirate));
+    let config = FactionConfig::default();
+    assert_eq!(
+        standing(&runner, player, Faction::Pirates),
+        -50.0 - config.attack_penalty - config.kill_penalty
+    );
+    assert_eq!(standing(&runner, player, Faction::MinersGuild), config.kill_bonus);
+    assert_eq!(standing(&runner, player, Faction::StationAuthority), config.kill_bonus);
+}
+
+#[test]
+fn wrecks_someone_else_finished_off_are_not_held_against_the_player() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let miner = runner.spawn_npc(NpcRole::Miner, x + 200.0, y);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().target = Some("Miner 0".to_string());
+    runner.run(30, |_, _| fire());
+    let shot_at = standing(&runner, player, Faction::MinersGuild);
+
+    // A pirate right next to the miner lands the last hit
+    runner.world.write_storage::<Npc>().get_mut(miner).unwrap().hull = 1;
+    runner.spawn_npc(NpcRole::Pirate, x + 220.0, y);
+    runner.run(120, |_, _| PlayerInput::default());
+
+    assert!(!runner.world.is_alive(miner));
+    assert_eq!(standing(&runner, player, Faction::MinersGuild), shot_at);
+}
+
+#[test]
+fn selling_ore_earns_guild_standing() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+
+    sell_iron(&mut runner, player);
+
+    assert_eq!(standing(&runner, player, Faction::MinersGuild), 100.0 * FactionConfig::default().sale_bonus);
+}
+
+#[test]
+fn trusted_pilots_get_better_prices() {
+    let mut neutral = runner();
+    let player = neutral.spawn_player();
+    let base = sell_iron(&mut neutral, player);
+
+    let mut trusted = runner();
+    let player = trusted.spawn_player();
+    set_standing(&mut trusted, player, Faction::StationAuthority, 100.0);
+    let paid = sell_iron(&mut trusted, player);
+
+    assert!((paid - base * 1.2).abs() < 0.01);
+}
+
+#[test]
+fn stations_turn_away_pilots_they_distrust() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    runner.spawn_station("Depot", x + 100.0, y);
+    let dock = || PlayerInput {
+        dock: true,
+        ..Default::default()
+    };
+
+    set_standing(&mut runner, player, Faction::StationAuthority, -60.0);
+    runner.step(dock());
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().docked, None);
+
+    set_standing(&mut runner, player, Faction::StationAuthority, -40.0);
+    runner.step(dock());
+    assert_eq!(
+        runner.world.read_storage::<Player>().get(player).unwrap().docked,
+        Some("Depot".to_string())
+    );
+}
+
+#[test]
+fn pirates_only_attack_pilots_they_are_hostile_to() {
+    let shield_after_meeting_a_pirate = |pirate_standing: f32| {
+        let mut runner = runner();
+        let player = runner.spawn_player();
+        let (x, y) = position(&runner, player);
+        let pirate = runner.spawn_npc(NpcRole::Pirate, x + 200.0, y);
+        set_standing(&mut runner, player, Faction::Pirates, pirate_standing);
+
+        runner.run(120, |_, _| PlayerInput::default());
+
+        let kind = runner.world.read_storage::<RadarContact>().get(pirate).unwrap().kind;
+        let players = runner.world.read_storage::<Player>();
+        let player = players.get(player).unwrap();
+        (player.sheild, player.max_sheild, kind)
+    };
+
+    let (shield, max, kind) = shield_after_meeting_a_pirate(-50.0);
+    assert!(shield < max);
+    assert!(kind == ContactKind::Hostile);
+
+    let (shield, max, kind) = shield_after_meeting_a_pirate(0.0);
+    assert_eq!(shield, max);
+    assert!(kind == ContactKind::Neutral);
+}
+
+#[test]
+fn miners_run_from_a_pilot_the_guild_has_turned_on() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let miner = runner.spawn_npc(NpcRole::Miner, x + 200.0, y);
+    set_standing(&mut runner, player, Faction::MinersGuild, -50.0);
+
+    runner.run(60, |_, _| PlayerInput::default());
+
+    assert!(position(&runner, miner).0 > x + 200.0);
+    assert!(runner.world.read_storage::<RadarContact>().get(miner).unwrap().kind == ContactKind::Hostile);
+}
+
+#[test]
+fn shipped_factions_match_the_defaults() {
+    assert_shipped_config::<FactionConfig>("factions.ron");
+}
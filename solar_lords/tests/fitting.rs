@@ -0,0 +1,137 @@
+mod common;
+
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, mining, runner, runner_with_astroid, set_target};
+use solar_lords::config::{ModuleConfig, ModuleDef, ShipClass, ShipyardConfig};
+use solar_lords::fitting::{mining_tool, validate, weapon, ShipStats};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::solar_lords::{
+    buy_ship, fit_module, unfit_module, Astroid, Cargo, Fitting, MiningTool, Ore, Player, Weapon,
+};
+
+fn module(name: &str) -> ModuleDef {
+    ModuleConfig::default().module(name).unwrap().clone()
+}
+
+fn class(name: &str) -> ShipClass {
+    ShipyardConfig::default().class(name).unwrap().clone()
+}
+
+fn fitted(runner: &HeadlessRunner, player: Entity) -> Vec<String> {
+    let fittings = runner.world.read_storage::<Fitting>();
+    fittings.get(player).unwrap().modules.iter().map(|module| module.name.clone()).collect()
+}
+
+fn with_spare(name: &str) -> (HeadlessRunner<'static, 'static>, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.world.write_storage::<Fitting>().get_mut(player).unwrap().spares.push(module(name));
+    (runner, player)
+}
+
+#[test]
+fn new_ships_get_the_starting_loadout() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+
+    assert_eq!(fitted(&runner, player), vec!["Laser", "Pulse Laser"]);
+    let fittings = runner.world.read_storage::<Fitting>();
+    let fitting = fittings.get(player).unwrap();
+    assert_eq!(fitting.mining_tool(), Some(MiningTool::Laser));
+    assert_eq!(fitting.weapon(), Some(Weapon::Laser));
+}
+
+#[test]
+fn loadouts_have_to_fit_the_hull() {
+    let miner = class("Miner");
+    assert!(validate(&miner, &[module("Laser"), module("Pulse Laser")]).is_ok());
+
+    // One utility slot
+    assert!(validate(&miner, &[module("Cargo Expander"), module("Heat Sink")]).is_err());
+    // The hauler only takes modules up to size 2
+    assert!(validate(&class("Hauler"), &[module("Matter Disintegrator")]).is_err());
+    // 18 + 12 + 25 power on a grid of 50
+    let heavy = [module("Matter Disintegrator"), module("Particle Disruptor"), module("Void Beam")];
+    assert!(validate(&miner, &heavy).is_err());
+
+    let low_cpu = ShipClass {
+        power: 1000.0,
+        cpu: 10.0,
+        ..miner
+    };
+    assert!(validate(&low_cpu, &[module("Laser"), module("Pulse Laser")]).is_err());
+}
+
+#[test]
+fn module_stats_add_to_the_hull() {
+    let miner = class("Miner");
+    let stats = ShipStats::new(&miner, &[module("Cargo Expander"), module("Armor Plating")]);
+    assert_eq!(stats.cargo, miner.cargo + 250);
+    assert_eq!(stats.hull, miner.hull + 50);
+    assert_eq!(stats.shield, miner.shield);
+}
+
+#[test]
+fn fitting_a_cargo_expander_grows_the_hold() {
+    let (mut runner, player) = with_spare("Cargo Expander");
+
+    assert!(fit_module(&mut runner.world, player, 0).is_ok());
+
+    assert_eq!(fitted(&runner, player), vec!["Laser", "Pulse Laser", "Cargo Expander"]);
+    assert!(runner.world.read_storage::<Fitting>().get(player).unwrap().spares.is_empty());
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().capacity, 750);
+}
+
+#[test]
+fn a_full_hold_keeps_the_expander_fitted() {
+    let (mut runner, player) = with_spare("Cargo Expander");
+    fit_module(&mut runner.world, player, 0).unwrap();
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add(Ore::Iron, 600);
+
+    assert!(unfit_module(&mut runner.world, player, 2).is_err());
+
+    assert_eq!(fitted(&runner, player).len(), 3);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().capacity, 750);
+}
+
+#[test]
+fn modules_the_new_hull_cannot_take_go_to_the_spares() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().wealth = 5000.0;
+
+    // The hauler has no weapon slots
+    buy_ship(&mut runner.world, player, &class("Hauler")).unwrap();
+
+    assert_eq!(fitted(&runner, player), vec!["Laser"]);
+    let fittings = runner.world.read_storage::<Fitting>();
+    let fitting = fittings.get(player).unwrap();
+    assert_eq!(fitting.spares, vec![module("Pulse Laser")]);
+    assert_eq!(fitting.weapon(), None);
+}
+
+#[test]
+fn no_mining_without_a_mining_module() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    let resources = runner.world.read_storage::<Astroid>().get(astroid).unwrap().resources;
+
+    unfit_module(&mut runner.world, player, 0).unwrap();
+    runner.run(60, |_, _| mining());
+
+    assert_eq!(runner.world.read_storage::<Astroid>().get(astroid).unwrap().resources, resources);
+}
+
+#[test]
+fn the_best_fitted_tool_and_weapon_are_used() {
+    let modules = vec![module("Mining Laser"), module("Laser"), module("Void Beam"), module("Pulse Laser")];
+    assert_eq!(mining_tool(&modules), Some(MiningTool::MiningLaser));
+    assert_eq!(weapon(&modules), Some(Weapon::VoidBeam));
+}
+
+#[test]
+fn shipped_modules_match_the_defaults() {
+    assert_shipped_config::<ModuleConfig>("modules.ron");
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use amethyst::core::{transform::Transform, Named};
+use amethyst::ecs::prelude::Join;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, fit_mining_tool, mining, runner, runner_with_astroid, set_target};
+use solar_lords::config::AstroidConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, AstroidSize, Cargo, MiningTool, Ore, OrePickup, Player, Velocity};
+
+//Name, size and resources of every astroid left
+fn astroids(runner: &HeadlessRunner) -> Vec<(String, AstroidSize, u32)> {
+    let names = runner.world.read_storage::<Named>();
+    let astroids = runner.world.read_storage::<Astroid>();
+    let mut found = (&names, &astroids)
+        .join()
+        .map(|(name, astroid)| (name.name.to_string(), astroid.size, astroid.resources))
+        .collect::<Vec<_>>();
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    found
+}
+
+fn pickups(runner: &HeadlessRunner) -> Vec<(Ore, u32)> {
+    (&runner.world.read_storage::<OrePickup>())
+        .join()
+        .map(|pickup| (pickup.ore, pickup.amount))
+        .collect()
+}
+
+#[test]
+fn large_astroid_splits_into_medium_fragments() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(799, |_, _| mining());
+    assert!(runner.world.is_alive(astroid));
+
+    runner.run(1, |_, _| mining());
+    assert!(!runner.world.is_alive(astroid));
+    assert_eq!(
+        astroids(&runner),
+        vec![
+            ("astroid 0-0".to_string(), AstroidSize::Medium, 100),
+            ("astroid 0-1".to_string(), AstroidSize::Medium, 100),
+        ]
+    );
+    let mined = pickups(&runner).iter().map(|(_, amount)| amount).sum::<u32>();
+    assert_eq!(mined, 800);
+}
+
+#[test]
+fn fragments_keep_the_ore_and_the_players_target() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_astroid(Astroid::new(Ore::Copper), 0.0, 0.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(800, |_, _| mining());
+
+    let astroids = runner.world.read_storage::<Astroid>();
+    assert!((&astroids).join().all(|astroid| astroid.composition == vec![(Ore::Copper, 1.0)]));
+    let players = runner.world.read_storage::<Player>();
+    assert_eq!(players.get(player).unwrap().target.as_deref(), Some("astroid 0-0"));
+}
+
+#[test]
+fn fragments_drift_apart_and_slow_down() {
+    let (mut runner, player, _) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    runner.run(800, |_, _| mining());
+
+    let speed = |runner: &HeadlessRunner| {
+        let velocities = runner.world.read_storage::<Velocity>();
+        (&velocities).join().map(|v| (v.x * v.x + v.y * v.y).sqrt()).sum::<f32>()
+    };
+    let start_speed = speed(&runner);
+    assert!(start_speed > 0.0);
+
+    runner.run(60, |_, _| PlayerInput::default());
+
+    assert!(speed(&runner) < start_speed);
+    let transforms = runner.world.read_storage::<Transform>();
+    let astroids = runner.world.read_storage::<Astroid>();
+    let positions = (&astroids, &transforms)
+        .join()
+        .map(|(_, transform)| (transform.translation().x, transform.translation().y))
+        .collect::<Vec<_>>();
+    let (a, b) = (positions[0], positions[1]);
+    assert!(((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt() > 20.0);
+}
+
+#[test]
+fn small_astroid_breaks_into_ore_pickups() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_astroid(Astroid::new(Ore::Gold).fragment(AstroidSize::Small, 15), 0.0, 0.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(5, |_, _| mining());
+
+    // The five already mined and the ten left both come out at the laser's 40% on gold
+    assert!(astroids(&runner).is_empty());
+    let mut dropped = pickups(&runner).iter().map(|(_, amount)| *amount).collect::<Vec<_>>();
+    dropped.sort();
+    assert_eq!(dropped, vec![3, 3]);
+    assert!(pickups(&runner).iter().all(|(ore, _)| *ore == Ore::Gold));
+}
+
+#[test]
+fn rubble_yields_what_the_tool_would_have_mined() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_astroid(Astroid::new(Ore::Gold).fragment(AstroidSize::Small, 15), 0.0, 0.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+    fit_mining_tool(&mut runner, player, MiningTool::ParticleDisruptor);
+
+    runner.run(5, |_, _| mining());
+
+    let dropped = pickups(&runner).iter().map(|(_, amount)| *amount).sum::<u32>();
+    assert_eq!(dropped, 13);
+}
+
+#[test]
+fn flying_over_a_pickup_collects_it() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let position = {
+        let transforms = runner.world.read_storage::<Transform>();
+        *transforms.get(player).unwrap().translation()
+    };
+    runner.spawn_pickup(Ore::Iron, 10, position.x + 10.0, position.y);
+
+    runner.run(5, |_, _| PlayerInput::default());
+    assert!(pickups(&runner).is_empty());
+    let cargo = runner.world.read_storage::<Cargo>();
+    assert_eq!(cargo.get(player).unwrap().amount(Ore::Iron), 10);
+}
+
+#[test]
+fn pickups_that_do_not_fit_stay_behind() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().capacity = 3;
+    let position = {
+        let transforms = runner.world.read_storage::<Transform>();
+        *transforms.get(player).unwrap().translation()
+    };
+    runner.spawn_pickup(Ore::Iron, 10, position.x + 10.0, position.y);
+
+    runner.run(5, |_, _| PlayerInput::default());
+
+    assert_eq!(pickups(&runner), vec![(Ore::Iron, 7)]);
+}
+
+#[test]
+fn shipped_astroids_match_the_defaults() {
+    assert_shipped_config::<AstroidConfig>("astroids.ron");
+}
@@ -0,0 +1,221 @@
+mod common;
+
+use amethyst::core::{transform::Transform, Named};
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, moving, runner};
+use solar_lords::config::FuelConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::save::SaveGame;
+use solar_lords::solar_lords::{Astroid, Cargo, Fuel, Galaxy, Good, MessageLog, Player, SectorSeed};
+
+fn fuel(runner: &HeadlessRunner, player: Entity) -> f32 {
+    runner.world.read_storage::<Fuel>().get(player).unwrap().amount
+}
+
+fn set_fuel(runner: &mut HeadlessRunner, player: Entity, amount: f32) {
+    runner.world.write_storage::<Fuel>().get_mut(player).unwrap().amount = amount;
+}
+
+fn position(runner: &HeadlessRunner, player: Entity) -> (f32, f32) {
+    let transforms = runner.world.read_storage::<Transform>();
+    let translation = transforms.get(player).unwrap().translation();
+    (translation.x, translation.y)
+}
+
+fn astroid_names(runner: &HeadlessRunner) -> Vec<String> {
+    let astroids = runner.world.read_storage::<Astroid>();
+    let names = runner.world.read_storage::<Named>();
+    let transforms = runner.world.read_storage::<Transform>();
+    let mut layout = (&astroids, &names, &transforms)
+        .join()
+        .map(|(_, name, transform)| format!("{} {:.0} {:.0}", name.name, transform.translation().x, transform.translation().y))
+        .collect::<Vec<_>>();
+    layout.sort();
+    layout
+}
+
+fn jump() -> PlayerInput {
+    PlayerInput {
+        jump: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn thrust_burns_fuel() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+
+    runner.run(60, |_, _| PlayerInput::default());
+    assert_eq!(fuel(&runner, player), 100.0);
+
+    runner.run(120, |_, _| moving(1.0, 0.0));
+    let burnt = 100.0 - fuel(&runner, player);
+    assert!((burnt - 2.0 * FuelConfig::default().thrust_burn).abs() < 0.01);
+}
+
+#[test]
+fn an_empty_tank_leaves_the_ship_drifting() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    set_fuel(&mut runner, player, 0.01);
+
+    // The engines cut out almost at once but the ship keeps going
+    runner.run(10, |_, _| moving(1.0, 0.0));
+    assert_eq!(fuel(&runner, player), 0.0);
+    let cut_out = position(&runner, player);
+    runner.run(30, |_, _| PlayerInput::default());
+    let drifted = position(&runner, player);
+    assert!(drifted.0 > cut_out.0 + 10.0);
+
+    // Thrust no longer changes course
+    runner.run(30, |_, _| moving(-1.0, 0.0));
+    assert!(position(&runner, player).0 > drifted.0);
+}
+
+#[test]
+fn refined_fuel_tops_up_the_tank() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    set_fuel(&mut runner, player, 90.0);
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add_good(Good::Fuel, 5);
+
+    runner.step(PlayerInput::default());
+
+    // Only whole units that fit are used
+    let per_unit = FuelConfig::default().per_refined_unit;
+    assert_eq!(fuel(&runner, player), 90.0 + 2.0 * per_unit);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().good_amount(Good::Fuel), 3);
+}
+
+#[test]
+fn distress_call_brings_fuel_for_a_fee() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    set_fuel(&mut runner, player, 0.0);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().wealth = 150.0;
+
+    let config = FuelConfig::default();
+    runner.step(PlayerInput {
+        distress: true,
+        ..Default::default()
+    });
+    assert!(runner.world.read_storage::<Fuel>().get(player).unwrap().distress.is_some());
+
+    runner.run((config.distress_delay * 60.0) as u64 + 5, |_, _| PlayerInput::default());
+    assert_eq!(fuel(&runner, player), config.distress_fuel);
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 150.0 - config.distress_fee);
+}
+
+#[test]
+fn no_distress_call_with_fuel_left() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.step(PlayerInput {
+        distress: true,
+        ..Default::default()
+    });
+    assert!(runner.world.read_storage::<Fuel>().get(player).unwrap().distress.is_none());
+}
+
+#[test]
+fn jumping_burns_fuel_and_lays_out_a_new_sector() {
+    let mut runner = runner();
+    let player = runner.spawn_sector(7);
+    let home = astroid_names(&runner);
+
+    // The ship starts out facing up
+    runner.step(jump());
+    assert_eq!(runner.world.read_resource::<Galaxy>().sector, (0, 1));
+    assert_eq!(fuel(&runner, player), 100.0 - FuelConfig::default().jump_cost);
+    assert_ne!(runner.world.read_resource::<SectorSeed>().0, 7);
+    assert_ne!(astroid_names(&runner), home);
+    assert_eq!(runner.world.read_storage::<Astroid>().join().count(), 4);
+
+    // Turning around and jumping back finds the same astroids
+    runner.run(2, |_, _| moving(0.0, -1.0));
+    runner.step(jump());
+    assert_eq!(runner.world.read_resource::<Galaxy>().sector, (0, 0));
+    assert_eq!(runner.world.read_resource::<SectorSeed>().0, 7);
+    assert_eq!(astroid_names(&runner), home);
+}
+
+#[test]
+fn no_jump_without_enough_fuel() {
+    let mut runner = runner();
+    let player = runner.spawn_sector(7);
+    set_fuel(&mut runner, player, 10.0);
+
+    runner.step(jump());
+
+    assert_eq!(runner.world.read_resource::<Galaxy>().sector, (0, 0));
+    assert_eq!(fuel(&runner, player), 10.0);
+    let messages = runner.world.read_resource::<MessageLog>();
+    assert!(messages.lines().any(|line| line.starts_with("Not enough fuel to jump")));
+}
+
+#[test]
+fn loaded_games_pick_up_where_the_ship_was() {
+    let mut original = runner();
+    let player = original.spawn_sector(7);
+    {
+        let mut transforms = original.world.write_storage::<Transform>();
+        let transform = transforms.get_mut(player).unwrap();
+        transform.set_translation_x(1234.0);
+        transform.set_translation_y(-567.0);
+    }
+
+    let mut loaded = runner();
+    let player = loaded.load_game(SaveGame::capture(&original.world));
+
+    assert_eq!(position(&loaded, player), (1234.0, -567.0));
+}
+
+#[test]
+fn docking_needs_a_station_in_range() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    runner.spawn_station("Far", x + 1000.0, y);
+
+    let dock = || PlayerInput {
+        dock: true,
+        ..Default::default()
+    };
+    runner.step(dock());
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().docked, None);
+
+    runner.spawn_station("Near", x + 150.0, y);
+    runner.step(dock());
+    assert_eq!(
+        runner.world.read_storage::<Player>().get(player).unwrap().docked,
+        Some("Near".to_string())
+    );
+
+    // Docked ships stay put
+    runner.run(30, |_, _| moving(1.0, 0.0));
+    assert_eq!(position(&runner, player), (x, y));
+}
+
+#[test]
+fn stations_sell_what_the_wealth_covers() {
+    let mut fuel = Fuel::new(100.0);
+    fuel.amount = 20.0;
+    let mut wealth = 100.0;
+
+    assert_eq!(fuel.buy(&mut wealth, 2.0), 50.0);
+    assert_eq!(fuel.amount, 70.0);
+    assert_eq!(wealth, 0.0);
+
+    let mut wealth = 1000.0;
+    assert_eq!(fuel.buy(&mut wealth, 2.0), 30.0);
+    assert_eq!(wealth, 940.0);
+}
+
+#[test]
+fn shipped_fuel_matches_the_defaults() {
+    assert_shipped_config::<FuelConfig>("fuel.ron");
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+use amethyst::renderer::SpriteRender;
+
+use common::{mining, runner, runner_with_astroid, set_target};
+use solar_lords::config::AstroidConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::{Aim, PlayerInput};
+use solar_lords::solar_lords::{Astroid, Cargo, Ore, OrePickup, Player};
+
+fn resources(runner: &HeadlessRunner, astroid: Entity) -> u32 {
+    runner.world.read_storage::<Astroid>().get(astroid).unwrap().resources
+}
+
+fn pickups(runner: &HeadlessRunner) -> Vec<(Ore, u32)> {
+    (&runner.world.read_storage::<OrePickup>())
+        .join()
+        .map(|pickup| (pickup.ore, pickup.amount))
+        .collect()
+}
+
+fn sprite_number(runner: &HeadlessRunner, astroid: Entity) -> usize {
+    runner.world.read_storage::<SpriteRender>().get(astroid).unwrap().sprite_number
+}
+
+#[test]
+fn mining_the_target_breaks_off_ore_pickups() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(9, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 991);
+    assert_eq!(pickups(&runner), vec![]);
+
+    runner.run(1, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 990);
+    assert_eq!(pickups(&runner), vec![(Ore::Ice, 10)]);
+    // The ship is too far away to have collected it
+    let cargo = runner.world.read_storage::<Cargo>();
+    assert_eq!(cargo.get(player).unwrap().amount(Ore::Ice), 0);
+}
+
+#[test]
+fn resources_are_untouched_without_the_mine_input() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(10, |_, _| PlayerInput::default());
+
+    assert_eq!(resources(&runner, astroid), 1000);
+}
+
+#[test]
+fn mining_without_a_target_does_not_panic() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, None);
+
+    runner.run(10, |_, _| mining());
+
+    assert_eq!(resources(&runner, astroid), 1000);
+}
+
+fn target(runner: &HeadlessRunner, player: Entity) -> Option<String> {
+    runner.world.read_storage::<Player>().get(player).unwrap().target.clone()
+}
+
+fn aim_at(x: f32, y: f32) -> PlayerInput {
+    PlayerInput {
+        aim: Some(Aim::Point(x, y)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn aiming_at_an_astroid_targets_it_until_the_aim_moves_off() {
+    let (mut runner, player, _) = runner_with_astroid();
+
+    runner.step(aim_at(5.0, -5.0));
+    assert_eq!(target(&runner, player), Some("astroid 0".to_string()));
+
+    // No aim at all, as when headless, leaves the target alone
+    runner.step(PlayerInput::default());
+    assert_eq!(target(&runner, player), Some("astroid 0".to_string()));
+
+    runner.step(aim_at(300.0, 300.0));
+    assert_eq!(target(&runner, player), None);
+}
+
+#[test]
+fn mining_a_different_astroid_leaves_this_one_alone() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 7"));
+
+    runner.run(10, |_, _| mining());
+
+    assert_eq!(resources(&runner, astroid), 1000);
+}
+
+#[test]
+fn sprite_advances_a_stage_every_250_resources() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    assert_eq!(sprite_number(&runner, astroid), 1);
+
+    runner.run(249, |_, _| mining());
+    assert_eq!(sprite_number(&runner, astroid), 1);
+
+    runner.run(1, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 750);
+    assert_eq!(sprite_number(&runner, astroid), 2);
+
+    runner.run(250, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 500);
+    assert_eq!(sprite_number(&runner, astroid), 3);
+
+    runner.run(250, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 250);
+    assert_eq!(sprite_number(&runner, astroid), 4);
+}
+
+#[test]
+fn big_astroids_stay_on_the_astroid_frames() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let astroid = runner.spawn_astroid(Astroid::with_composition(vec![(Ore::Ice, 1.0)], 1200), 0.0, 0.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+    let mut config = AstroidConfig::default();
+    config.large.split_at = 0;
+    runner.world.insert(config);
+
+    // Stages go by the share mined out, a quarter of this rock is 300
+    runner.run(300, |_, _| mining());
+    assert_eq!(sprite_number(&runner, astroid), 2);
+
+    runner.run(899, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), 1);
+    assert_eq!(sprite_number(&runner, astroid), 4);
+}
+
+#[test]
+fn depleted_astroid_is_deleted() {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    // Without a split threshold the astroid is mined right down
+    let mut config = AstroidConfig::default();
+    config.large.split_at = 0;
+    runner.world.insert(config);
+
+    runner.run(999, |_, _| mining());
+    assert!(runner.world.is_alive(astroid));
+
+    runner.run(1, |_, _| mining());
+    assert!(!runner.world.is_alive(astroid));
+
+    // Carrying on with a dead target is harmless
+    runner.run(10, |_, _| mining());
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+use std::f32::consts::PI;
+
+use common::{moving, runner};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::Player;
+
+fn position(runner: &HeadlessRunner, player: Entity) -> (f32, f32) {
+    let transforms = runner.world.read_storage::<Transform>();
+    let translation = transforms.get(player).unwrap().translation();
+    (translation.x, translation.y)
+}
+
+fn heading(runner: &HeadlessRunner, player: Entity) -> f32 {
+    let transforms = runner.world.read_storage::<Transform>();
+    transforms.get(player).unwrap().rotation().euler_angles().2
+}
+
+fn assert_angle(actual: f32, expected: f32) {
+    let difference = (actual - expected).rem_euclid(2.0 * PI);
+    assert!(
+        difference < 0.001 || difference > 2.0 * PI - 0.001,
+        "expected heading {} but was {}",
+        expected,
+        actual
+    );
+}
+
+//Moves the player for ten ticks and returns how far it went
+fn travel(move_x: f32, move_y: f32) -> ((f32, f32), f32) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let speed = runner.world.read_storage::<Player>().get(player).unwrap().speed;
+    let start = position(&runner, player);
+
+    runner.run(10, |_, _| moving(move_x, move_y));
+
+    let end = position(&runner, player);
+    assert!(((end.0 - start.0) - move_x * speed * 10.0).abs() < 0.001);
+    assert!(((end.1 - start.1) - move_y * speed * 10.0).abs() < 0.001);
+    ((end.0 - start.0, end.1 - start.1), heading(&runner, player))
+}
+
+#[test]
+fn moving_right_faces_right() {
+    let ((dx, dy), heading) = travel(1.0, 0.0);
+    assert!(dx > 0.0 && dy == 0.0);
+    assert_angle(heading, -PI / 2.0);
+}
+
+#[test]
+fn moving_left_faces_left() {
+    let ((dx, dy), heading) = travel(-1.0, 0.0);
+    assert!(dx < 0.0 && dy == 0.0);
+    assert_angle(heading, PI / 2.0);
+}
+
+#[test]
+fn moving_up_faces_up() {
+    let ((dx, dy), heading) = travel(0.0, 1.0);
+    assert!(dx == 0.0 && dy > 0.0);
+    assert_angle(heading, 0.0);
+}
+
+#[test]
+fn moving_down_faces_down() {
+    let ((dx, dy), heading) = travel(0.0, -1.0);
+    assert!(dx == 0.0 && dy < 0.0);
+    assert_angle(heading, PI);
+}
+
+#[test]
+fn moving_diagonally_faces_between_axes() {
+    let (_, heading) = travel(1.0, 1.0);
+    assert_angle(heading, PI * 7.0 / 4.0);
+
+    let (_, heading) = travel(-1.0, -1.0);
+    assert_angle(heading, PI * 3.0 / 4.0);
+}
+
+#[test]
+fn no_input_keeps_position_and_heading() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let start = position(&runner, player);
+    let start_heading = heading(&runner, player);
+
+    runner.run(10, |_, _| PlayerInput::default());
+
+    assert_eq!(position(&runner, player), start);
+    assert_angle(heading(&runner, player), start_heading);
+}
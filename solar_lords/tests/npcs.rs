@@ -0,0 +1,256 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::{MarketConfig, NpcConfig};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::npc::{choose_goal, Situation};
+use solar_lords::solar_lords::{
+    sell_ore, Astroid, Cargo, Market, Npc, NpcGoal, NpcRole, Ore, OrePickup, Player,
+};
+
+fn sight(role: NpcRole) -> f32 {
+    NpcConfig::default().archetype(role).unwrap().sight
+}
+
+fn hull(runner: &HeadlessRunner, npc: Entity) -> u32 {
+    runner.world.read_storage::<Npc>().get(npc).unwrap().hull
+}
+
+fn load(runner: &mut HeadlessRunner, ship: Entity, ore: Ore, amount: u32) {
+    runner.world.write_storage::<Cargo>().get_mut(ship).unwrap().add(ore, amount);
+}
+
+fn position(runner: &HeadlessRunner, entity: Entity) -> (f32, f32) {
+    let transforms = runner.world.read_storage::<Transform>();
+    let translation = transforms.get(entity).unwrap().translation();
+    (translation.x, translation.y)
+}
+
+#[test]
+fn miners_fill_up_then_sell() {
+    let mut runner = runner();
+    let astroid = runner.world.create_entity().build();
+    let station = runner.world.create_entity().build();
+    let mut situation = Situation {
+        hull: 1.0,
+        astroid: Some((astroid, 100.0)),
+        station: Some((station, 800.0)),
+        ..Default::default()
+    };
+    let sight = sight(NpcRole::Miner);
+
+    assert_eq!(choose_goal(NpcRole::Miner, sight, &situation), NpcGoal::Mine(astroid));
+    situation.cargo = 1.0;
+    assert_eq!(choose_goal(NpcRole::Miner, sight, &situation), NpcGoal::Sell(station));
+}
+
+#[test]
+fn damaged_ships_run_from_pirates_sooner() {
+    let mut runner = runner();
+    let astroid = runner.world.create_entity().build();
+    let pirate = runner.world.create_entity().build();
+    let mut situation = Situation {
+        hull: 1.0,
+        cargo: 0.2,
+        astroid: Some((astroid, 100.0)),
+        threat: Some((pirate, 100.0)),
+        ..Default::default()
+    };
+    let sight = sight(NpcRole::Miner);
+
+    assert_eq!(choose_goal(NpcRole::Miner, sight, &situation), NpcGoal::Mine(astroid));
+    situation.hull = 0.5;
+    assert_eq!(choose_goal(NpcRole::Miner, sight, &situation), NpcGoal::Flee(pirate));
+}
+
+#[test]
+fn traders_buy_when_empty_and_haul_when_loaded() {
+    let mut runner = runner();
+    let station = runner.world.create_entity().build();
+    let mut situation = Situation {
+        hull: 1.0,
+        station: Some((station, 500.0)),
+        ..Default::default()
+    };
+    let sight = sight(NpcRole::Trader);
+
+    assert_eq!(choose_goal(NpcRole::Trader, sight, &situation), NpcGoal::Trade(station));
+    situation.cargo = 0.8;
+    assert_eq!(choose_goal(NpcRole::Trader, sight, &situation), NpcGoal::Haul);
+}
+
+#[test]
+fn pirates_hunt_what_they_can_see() {
+    let mut runner = runner();
+    let prey = runner.world.create_entity().build();
+    let mut situation = Situation {
+        hull: 1.0,
+        ..Default::default()
+    };
+    let sight = sight(NpcRole::Pirate);
+
+    assert_eq!(choose_goal(NpcRole::Pirate, sight, &situation), NpcGoal::Wander);
+    situation.prey = Some((prey, 300.0));
+    assert_eq!(choose_goal(NpcRole::Pirate, sight, &situation), NpcGoal::Hunt(prey));
+}
+
+#[test]
+fn sectors_are_populated() {
+    let mut runner = runner();
+    runner.spawn_sector(7);
+    let npcs = runner.world.read_storage::<Npc>();
+    assert!((&npcs).join().any(|npc| npc.role() == NpcRole::Miner));
+}
+
+#[test]
+fn npc_miners_take_rock_out_of_astroids() {
+    let mut runner = runner();
+    let astroid = runner.spawn_astroid(Astroid::new(Ore::Iron), 0.0, 0.0, 0);
+    let miner = runner.spawn_npc(NpcRole::Miner, 100.0, 0.0);
+
+    runner.run(120, |_, _| PlayerInput::default());
+
+    assert!(runner.world.read_storage::<Astroid>().get(astroid).unwrap().resources < 1000);
+    assert!(runner.world.read_storage::<Cargo>().get(miner).unwrap().amount(Ore::Iron) > 0);
+}
+
+#[test]
+fn selling_ore_lowers_the_station_price() {
+    let mut runner = runner();
+    let station = runner.spawn_station("Depot", 0.0, 0.0);
+    let miner = runner.spawn_npc(NpcRole::Miner, 100.0, 0.0);
+    load(&mut runner, miner, Ore::Iron, 200);
+
+    runner.step(PlayerInput::default());
+
+    assert_eq!(runner.world.read_storage::<Cargo>().get(miner).unwrap().total(), 0);
+    let config = MarketConfig::default();
+    let markets = runner.world.read_storage::<Market>();
+    let market = markets.get(station).unwrap();
+    assert!(market.price(Ore::Iron, &config) < config.base_price(Ore::Iron));
+    assert_eq!(market.price(Ore::Copper, &config), config.base_price(Ore::Copper));
+}
+
+//A trader with a full hold of iron it bought at the station
+fn loaded_trader(runner: &mut HeadlessRunner, bought_at: Entity, x: f32) -> Entity {
+    let trader = runner.spawn_npc(NpcRole::Trader, x, 0.0);
+    load(runner, trader, Ore::Iron, 300);
+    runner.world.write_storage::<Npc>().get_mut(trader).unwrap().bought_at = Some(bought_at);
+    trader
+}
+
+fn iron_price(runner: &HeadlessRunner, station: Entity) -> f32 {
+    runner.world.read_storage::<Market>().get(station).unwrap().price(Ore::Iron, &MarketConfig::default())
+}
+
+#[test]
+fn traders_haul_cargo_to_another_station() {
+    let mut runner = runner();
+    let bought_at = runner.spawn_station("Depot", 0.0, 0.0);
+    let other = runner.spawn_station("Outpost", 300.0, 0.0);
+    let trader = loaded_trader(&mut runner, bought_at, 50.0);
+
+    runner.run(180, |_, _| PlayerInput::default());
+
+    assert_eq!(runner.world.read_storage::<Cargo>().get(trader).unwrap().total(), 0);
+    let base = MarketConfig::default().base_price(Ore::Iron);
+    assert!(iron_price(&runner, other) < base);
+    assert_eq!(iron_price(&runner, bought_at), base);
+}
+
+#[test]
+fn traders_with_one_station_sell_back_after_leaving_the_sector() {
+    let mut runner = runner();
+    let station = runner.spawn_station("Depot", 0.0, 0.0);
+    let trader = loaded_trader(&mut runner, station, 50.0);
+
+    // Still carrying it on the way out
+    runner.run(60 * 20, |_, _| PlayerInput::default());
+    assert_eq!(runner.world.read_storage::<Cargo>().get(trader).unwrap().total(), 300);
+
+    runner.run(60 * 70, |_, _| PlayerInput::default());
+    assert_eq!(runner.world.read_storage::<Cargo>().get(trader).unwrap().total(), 0);
+    assert!(iron_price(&runner, station) < MarketConfig::default().base_price(Ore::Iron));
+}
+
+#[test]
+fn pirates_wreck_their_prey_and_the_cargo_spills() {
+    let mut runner = runner();
+    let miner = runner.spawn_npc(NpcRole::Miner, 0.0, 0.0);
+    runner.spawn_npc(NpcRole::Pirate, 200.0, 0.0);
+    load(&mut runner, miner, Ore::Gold, 50);
+
+    // The miner runs but the pirate is faster and keeps it in range
+    runner.run(120, |_, _| PlayerInput::default());
+    assert!(position(&runner, miner).0 < 0.0);
+    assert!(hull(&runner, miner) < 60);
+
+    runner.run(600, |_, _| PlayerInput::default());
+    assert!(!runner.world.is_alive(miner));
+    let spilled = (&runner.world.read_storage::<OrePickup>())
+        .join()
+        .filter(|pickup| pickup.ore == Ore::Gold)
+        .map(|pickup| pickup.amount)
+        .sum::<u32>();
+    assert_eq!(spilled, 50);
+}
+
+#[test]
+fn player_weapons_damage_the_targeted_ship() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let (x, y) = position(&runner, player);
+    let miner = runner.spawn_npc(NpcRole::Miner, x + 200.0, y);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().target = Some("Miner 0".to_string());
+
+    runner.run(60, |_, _| PlayerInput {
+        fire: true,
+        ..Default::default()
+    });
+
+    let damage = 60 - hull(&runner, miner);
+    assert!(damage >= 9 && damage <= 10);
+}
+
+#[test]
+fn docked_players_sell_ore_at_market_prices() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.spawn_station("Depot", 0.0, 0.0);
+    load(&mut runner, player, Ore::Iron, 100);
+
+    assert!(sell_ore(&mut runner.world, player, Ore::Iron).is_err());
+
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().docked = Some("Depot".to_string());
+    let (sold, paid) = sell_ore(&mut runner.world, player, Ore::Iron).unwrap();
+
+    // Each unit sold knocks the price down a little
+    assert_eq!(sold, 100);
+    assert!(paid < 400.0 && paid > 350.0);
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, paid);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().amount(Ore::Iron), 0);
+}
+
+#[test]
+fn market_stock_drifts_back_to_the_target() {
+    let config = MarketConfig::default();
+    let mut market = Market::new(&config);
+    market.sell(Ore::Ice, 100, &config);
+    market.buy(Ore::Gold, 100, &config);
+
+    market.restock(&config, 10.0);
+
+    assert_eq!(market.stock(Ore::Ice), config.stock_target + 100.0 - 10.0 * config.restock_rate);
+    assert_eq!(market.stock(Ore::Gold), config.stock_target - 100.0 + 10.0 * config.restock_rate);
+}
+
+#[test]
+fn shipped_npc_and_market_configs_match_the_defaults() {
+    assert_shipped_config::<NpcConfig>("npcs.ron");
+    assert_shipped_config::<MarketConfig>("market.ron");
+}
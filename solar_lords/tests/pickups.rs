@@ -0,0 +1,118 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, mining, runner, set_target};
+use solar_lords::config::PickupConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, Cargo, Ore, OrePickup};
+
+fn tractor() -> PlayerInput {
+    PlayerInput {
+        tractor: true,
+        ..Default::default()
+    }
+}
+
+fn pickup_count(runner: &HeadlessRunner) -> usize {
+    (&runner.world.read_storage::<OrePickup>()).join().count()
+}
+
+fn ice(runner: &HeadlessRunner, player: Entity) -> u32 {
+    runner.world.read_storage::<Cargo>().get(player).unwrap().amount(Ore::Ice)
+}
+
+//A player with an astroid the given distance to its left
+fn runner_with_astroid_at(distance: f32) -> (HeadlessRunner<'static, 'static>, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let position = *runner.world.read_storage::<Transform>().get(player).unwrap().translation();
+    runner.spawn_astroid(Astroid::new(Ore::Ice), position.x - distance, position.y, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+    (runner, player)
+}
+
+#[test]
+fn chunks_drifting_onto_the_ship_are_collected() {
+    let (mut runner, player) = runner_with_astroid_at(60.0);
+
+    runner.run(10, |_, _| mining());
+    assert_eq!(pickup_count(&runner), 1);
+
+    runner.run(120, |_, _| PlayerInput::default());
+    assert_eq!(pickup_count(&runner), 0);
+    assert_eq!(ice(&runner, player), 10);
+}
+
+#[test]
+fn tractor_beam_pulls_chunks_in_from_range() {
+    let (mut runner, player) = runner_with_astroid_at(250.0);
+    runner.run(10, |_, _| mining());
+
+    // Drifting alone never reaches the ship
+    runner.run(300, |_, _| PlayerInput::default());
+    assert_eq!(ice(&runner, player), 0);
+
+    runner.run(300, |_, _| tractor());
+    assert_eq!(pickup_count(&runner), 0);
+    assert_eq!(ice(&runner, player), 10);
+}
+
+#[test]
+fn tractor_beam_ignores_chunks_out_of_range() {
+    let (mut runner, player) = runner_with_astroid_at(600.0);
+    runner.run(10, |_, _| mining());
+
+    runner.run(300, |_, _| tractor());
+    assert_eq!(pickup_count(&runner), 1);
+    assert_eq!(ice(&runner, player), 0);
+}
+
+#[test]
+fn uncollected_chunks_despawn() {
+    let (mut runner, _) = runner_with_astroid_at(600.0);
+    let mut config = PickupConfig::default();
+    config.lifetime = 2.0;
+    runner.world.insert(config);
+    runner.run(10, |_, _| mining());
+
+    runner.run(60, |_, _| PlayerInput::default());
+    assert_eq!(pickup_count(&runner), 1);
+
+    runner.run(90, |_, _| PlayerInput::default());
+    assert_eq!(pickup_count(&runner), 0);
+}
+
+#[test]
+fn a_full_hold_leaves_chunks_floating() {
+    let (mut runner, player) = runner_with_astroid_at(60.0);
+    let mut config = PickupConfig::default();
+    config.lifetime = 2.0;
+    runner.world.insert(config);
+    {
+        let mut cargos = runner.world.write_storage::<Cargo>();
+        let cargo = cargos.get_mut(player).unwrap();
+        let capacity = cargo.capacity;
+        cargo.add(Ore::Iron, capacity);
+    }
+
+    // Mined into a full hold, and kept well past its lifetime
+    runner.run(10, |_, _| mining());
+    runner.run(300, |_, _| PlayerInput::default());
+    assert_eq!(pickup_count(&runner), 1);
+    assert_eq!(ice(&runner, player), 0);
+
+    // Collected once there is room again
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().take_ores(&[(Ore::Iron, 20)]);
+    runner.step(PlayerInput::default());
+    assert_eq!(pickup_count(&runner), 0);
+    assert_eq!(ice(&runner, player), 10);
+}
+
+#[test]
+fn shipped_pickups_match_the_defaults() {
+    assert_shipped_config::<PickupConfig>("pickups.ron");
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, fit_mining_tool, mining, moving, runner_with_astroid, set_target};
+use solar_lords::config::PowerConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, Capacitor, Heat, MiningTool, Player};
+
+fn miner(tool: MiningTool) -> (HeadlessRunner<'static, 'static>, Entity, Entity) {
+    let (mut runner, player, astroid) = runner_with_astroid();
+    set_target(&mut runner, player, Some("astroid 0"));
+    fit_mining_tool(&mut runner, player, tool);
+    (runner, player, astroid)
+}
+
+fn charge(runner: &HeadlessRunner, player: Entity) -> f32 {
+    runner.world.read_storage::<Capacitor>().get(player).unwrap().charge
+}
+
+fn heat(runner: &HeadlessRunner, player: Entity) -> (f32, bool) {
+    let heats = runner.world.read_storage::<Heat>();
+    let heat = heats.get(player).unwrap();
+    (heat.heat, heat.overheated)
+}
+
+fn resources(runner: &HeadlessRunner, astroid: Entity) -> u32 {
+    runner.world.read_storage::<Astroid>().get(astroid).unwrap().resources
+}
+
+#[test]
+fn stronger_tools_drain_the_capacitor() {
+    let (mut runner, player, _) = miner(MiningTool::MatterDisintegrator);
+    runner.run(60, |_, _| mining());
+    assert!(charge(&runner, player) < 95.0);
+
+    let (mut runner, player, _) = miner(MiningTool::Laser);
+    runner.run(60, |_, _| mining());
+    assert!(charge(&runner, player) > 99.0);
+}
+
+#[test]
+fn holding_the_beam_without_a_target_draws_nothing() {
+    let (mut runner, player, _) = miner(MiningTool::MatterDisintegrator);
+    set_target(&mut runner, player, None);
+
+    runner.run(60, |_, _| mining());
+
+    assert_eq!(charge(&runner, player), 100.0);
+}
+
+#[test]
+fn no_mining_without_energy() {
+    let (mut runner, player, astroid) = miner(MiningTool::Laser);
+    {
+        let mut capacitors = runner.world.write_storage::<Capacitor>();
+        let capacitor = capacitors.get_mut(player).unwrap();
+        capacitor.charge = 0.0;
+        capacitor.recharge = 0.0;
+    }
+
+    runner.run(60, |_, _| mining());
+
+    assert_eq!(resources(&runner, astroid), 1000);
+}
+
+#[test]
+fn continuous_mining_overheats_and_forces_a_cooldown() {
+    let (mut runner, player, astroid) = miner(MiningTool::MatterDisintegrator);
+
+    runner.run(60 * 6, |_, _| mining());
+    let (_, overheated) = heat(&runner, player);
+    assert!(overheated);
+
+    // Holding the beam does nothing while it cools
+    let stopped_at = resources(&runner, astroid);
+    runner.run(60 * 5, |_, _| mining());
+    assert_eq!(resources(&runner, astroid), stopped_at);
+
+    runner.run(60 * 11, |_, _| PlayerInput::default());
+    assert_eq!(heat(&runner, player).1, false);
+    runner.run(10, |_, _| mining());
+    assert!(resources(&runner, astroid) < stopped_at);
+}
+
+#[test]
+fn basic_laser_runs_cool() {
+    let (mut runner, player, _) = miner(MiningTool::Laser);
+    runner.run(60 * 10, |_, _| mining());
+    let (heat, overheated) = heat(&runner, player);
+    assert!(heat < 15.0);
+    assert!(!overheated);
+}
+
+#[test]
+fn heat_dissipates_when_idle() {
+    let (mut runner, player, _) = miner(MiningTool::ParticleDisruptor);
+    runner.run(60 * 3, |_, _| mining());
+    let hot = heat(&runner, player).0;
+    runner.run(60, |_, _| PlayerInput::default());
+    assert!(heat(&runner, player).0 < hot);
+}
+
+#[test]
+fn shields_regenerate_using_energy() {
+    let (mut runner, player, _) = runner_with_astroid();
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().sheild = 90;
+
+    runner.run(60, |_, _| PlayerInput::default());
+
+    // Two points a second
+    let sheild = runner.world.read_storage::<Player>().get(player).unwrap().sheild;
+    assert!(sheild == 91 || sheild == 92, "shield was {}", sheild);
+    assert!(charge(&runner, player) < 100.0);
+}
+
+#[test]
+fn firing_drains_energy() {
+    let (mut runner, player, _) = runner_with_astroid();
+    runner.run(60, |_, _| PlayerInput {
+        fire: true,
+        ..Default::default()
+    });
+    assert!(charge(&runner, player) < 100.0);
+}
+
+#[test]
+fn thrusting_drains_energy() {
+    let (mut runner, player, _) = runner_with_astroid();
+    runner.run(60, |_, _| moving(1.0, 0.0));
+    assert!(charge(&runner, player) < 100.0);
+}
+
+#[test]
+fn the_engines_need_power() {
+    let (mut runner, player, _) = runner_with_astroid();
+    {
+        let mut capacitors = runner.world.write_storage::<Capacitor>();
+        let capacitor = capacitors.get_mut(player).unwrap();
+        capacitor.charge = 0.0;
+        capacitor.recharge = 0.0;
+    }
+    let x = |runner: &HeadlessRunner| runner.world.read_storage::<Transform>().get(player).unwrap().translation().x;
+    let start = x(&runner);
+
+    runner.run(60, |_, _| moving(1.0, 0.0));
+
+    assert_eq!(x(&runner), start);
+}
+
+#[test]
+fn shipped_power_matches_the_defaults() {
+    assert_shipped_config::<PowerConfig>("power.ron");
+}
@@ -0,0 +1,9 @@
+mod common;
+
+use common::assert_shipped_config;
+use solar_lords::config::RadarConfig;
+
+#[test]
+fn shipped_radar_matches_the_defaults() {
+    assert_shipped_config::<RadarConfig>("radar.ron");
+}
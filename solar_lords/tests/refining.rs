@@ -0,0 +1,127 @@
+mod common;
+
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::{ModuleEffect, RecipeBook};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{craft_module, Cargo, Fitting, Good, MiningTool, Ore, Refinery};
+
+fn refining(recipes: &[&str], ores: &[(Ore, u32)]) -> (HeadlessRunner<'static, 'static>, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    {
+        let mut refineries = runner.world.write_storage::<Refinery>();
+        let refinery = refineries.get_mut(player).unwrap();
+        for recipe in recipes {
+            refinery.toggle(recipe);
+        }
+        let mut cargos = runner.world.write_storage::<Cargo>();
+        let cargo = cargos.get_mut(player).unwrap();
+        for (ore, amount) in ores {
+            cargo.add(*ore, *amount);
+        }
+    }
+    (runner, player)
+}
+
+fn cargo(runner: &HeadlessRunner, player: Entity) -> (u32, u32, u32) {
+    let cargos = runner.world.read_storage::<Cargo>();
+    let cargo = cargos.get(player).unwrap();
+    (cargo.amount(Ore::Ice), cargo.good_amount(Good::Water), cargo.total())
+}
+
+#[test]
+fn refinery_turns_ore_into_goods_over_time() {
+    let (mut runner, player) = refining(&["Water"], &[(Ore::Ice, 10)]);
+
+    // Ore leaves the hold as soon as the batch starts
+    runner.step(PlayerInput::default());
+    assert_eq!(cargo(&runner, player), (0, 0, 0));
+
+    // Water takes 4 seconds
+    runner.run(180, |_, _| PlayerInput::default());
+    assert_eq!(cargo(&runner, player).1, 0);
+    runner.run(70, |_, _| PlayerInput::default());
+    assert_eq!(cargo(&runner, player), (0, 8, 8));
+}
+
+#[test]
+fn finished_batches_wait_for_room_in_the_hold() {
+    let (mut runner, player) = refining(&["Water"], &[(Ore::Ice, 10)]);
+    runner.step(PlayerInput::default());
+    {
+        let mut cargos = runner.world.write_storage::<Cargo>();
+        let cargo = cargos.get_mut(player).unwrap();
+        let space = cargo.capacity - cargo.total();
+        cargo.add(Ore::Copper, space);
+    }
+
+    runner.run(300, |_, _| PlayerInput::default());
+    assert_eq!(cargo(&runner, player).1, 0);
+    assert!(runner.world.read_storage::<Refinery>().get(player).unwrap().job.is_some());
+
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().take_ores(&[(Ore::Copper, 8)]);
+    runner.step(PlayerInput::default());
+    assert_eq!(cargo(&runner, player).1, 8);
+}
+
+#[test]
+fn disabled_recipes_leave_the_ore_alone() {
+    let (mut runner, player) = refining(&[], &[(Ore::Ice, 10)]);
+    runner.run(300, |_, _| PlayerInput::default());
+    assert_eq!(cargo(&runner, player), (10, 0, 10));
+}
+
+#[test]
+fn batches_need_every_input() {
+    let (mut runner, player) = refining(&["Alloy"], &[(Ore::Iron, 10), (Ore::Copper, 4)]);
+    runner.run(60, |_, _| PlayerInput::default());
+
+    let cargos = runner.world.read_storage::<Cargo>();
+    let cargo = cargos.get(player).unwrap();
+    assert_eq!(cargo.amount(Ore::Iron), 10);
+    assert!(runner.world.read_storage::<Refinery>().get(player).unwrap().job.is_none());
+}
+
+#[test]
+fn enabled_recipes_take_turns() {
+    let (mut runner, player) = refining(&["Water", "Fuel"], &[(Ore::Ice, 60)]);
+    runner.run(60 * 13, |_, _| PlayerInput::default());
+
+    let cargos = runner.world.read_storage::<Cargo>();
+    let cargo = cargos.get(player).unwrap();
+    assert_eq!(cargo.good_amount(Good::Water), 8);
+    assert_eq!(cargo.good_amount(Good::Fuel), 5);
+}
+
+#[test]
+fn crafting_spends_goods_and_builds_a_spare_module() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let book = RecipeBook::default();
+    let blueprint = book
+        .crafting
+        .iter()
+        .find(|blueprint| blueprint.module == "Mining Laser")
+        .unwrap();
+
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add_good(Good::Alloy, 10);
+    assert!(craft_module(&mut runner.world, player, blueprint).is_err());
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().good_amount(Good::Alloy), 10);
+
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add_good(Good::Alloy, 15);
+    assert!(craft_module(&mut runner.world, player, blueprint).is_ok());
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().good_amount(Good::Alloy), 5);
+    let fittings = runner.world.read_storage::<Fitting>();
+    let spares = &fittings.get(player).unwrap().spares;
+    assert_eq!(spares.len(), 1);
+    assert_eq!(spares[0].effect, ModuleEffect::MiningTool(MiningTool::MiningLaser));
+}
+
+#[test]
+fn shipped_recipes_match_the_defaults() {
+    assert_shipped_config::<RecipeBook>("recipes.ron");
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use amethyst::core::Named;
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::RegenConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::regen::{drift_mix, roll_policy};
+use solar_lords::save::SaveGame;
+use solar_lords::solar_lords::{Astroid, Galaxy, JumpRequest, Meteor, Ore, SectorRegen};
+
+fn quick_config() -> RegenConfig {
+    RegenConfig {
+        respawn_interval: (1.0, 1.0),
+        max_astroids: (5, 5),
+        ..Default::default()
+    }
+}
+
+//An empty sector that regrows an astroid every second, up to five
+fn quick_regrowth() -> HeadlessRunner<'static, 'static> {
+    let mut runner = runner();
+    runner.spawn_player();
+    let config = quick_config();
+    runner.world.insert(SectorRegen::new(7, &config));
+    runner.world.insert(config);
+    runner
+}
+
+fn astroids(runner: &HeadlessRunner) -> Vec<Entity> {
+    let entities = runner.world.entities();
+    let astroids = runner.world.read_storage::<Astroid>();
+    (&entities, &astroids).join().map(|(entity, _)| entity).collect()
+}
+
+fn jump_to(runner: &mut HeadlessRunner, sector: (i32, i32)) {
+    runner.world.insert(JumpRequest(Some(sector)));
+    runner.step(PlayerInput::default());
+    assert_eq!(runner.world.read_resource::<Galaxy>().sector, sector);
+}
+
+fn seconds(runner: &mut HeadlessRunner, seconds: f32) {
+    runner.run((seconds * 60.0) as u64, |_, _| PlayerInput::default());
+}
+
+#[test]
+fn sectors_roll_their_own_policy() {
+    let config = RegenConfig::default();
+    let policy = roll_policy(&mut StdRng::seed_from_u64(3), &config);
+
+    assert!(policy.respawn_interval >= config.respawn_interval.0 && policy.respawn_interval <= config.respawn_interval.1);
+    assert!(policy.max_astroids >= config.max_astroids.0 && policy.max_astroids <= config.max_astroids.1);
+    let total = policy.ore_mix.iter().map(|(_, share)| share).sum::<f32>();
+    assert!((total - 1.0).abs() < 0.001);
+    assert_eq!(policy, roll_policy(&mut StdRng::seed_from_u64(3), &config));
+}
+
+#[test]
+fn the_ore_mix_drifts_over_time() {
+    let config = RegenConfig::default();
+    let mut rng = StdRng::seed_from_u64(9);
+    let start = roll_policy(&mut rng, &config).ore_mix;
+    let mut mix = start.clone();
+
+    for _ in 0..50 {
+        drift_mix(&mut rng, &mut mix, config.ore_drift);
+    }
+
+    assert_ne!(mix, start);
+    let total = mix.iter().map(|(_, share)| share).sum::<f32>();
+    assert!((total - 1.0).abs() < 0.001);
+    assert!(mix.iter().all(|(_, share)| *share > 0.0));
+}
+
+#[test]
+fn depleted_sectors_grow_new_astroids() {
+    let mut runner = quick_regrowth();
+
+    seconds(&mut runner, 2.5);
+
+    let names = astroids(&runner)
+        .iter()
+        .map(|astroid| runner.world.read_storage::<Named>().get(*astroid).unwrap().name.to_string())
+        .collect::<Vec<_>>();
+    // Numbered on from the astroids the sector started with
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"astroid 4".to_string()));
+    assert!(names.contains(&"astroid 5".to_string()));
+}
+
+#[test]
+fn regrowth_stops_at_the_sector_limit() {
+    let mut runner = quick_regrowth();
+
+    seconds(&mut runner, 8.0);
+
+    assert_eq!(astroids(&runner).len(), 5);
+}
+
+#[test]
+fn sectors_are_left_as_they_were() {
+    let mut runner = quick_regrowth();
+    let kept = runner.spawn_astroid(Astroid::new(Ore::Ice), 0.0, 0.0, 0);
+    runner.world.write_storage::<Astroid>().get_mut(kept).unwrap().resources = 321;
+
+    jump_to(&mut runner, (1, 0));
+    jump_to(&mut runner, (0, 0));
+
+    // Straight back, so nothing has grown yet
+    let found = astroids(&runner);
+    assert_eq!(found.len(), 1);
+    assert_eq!(runner.world.read_storage::<Astroid>().get(found[0]).unwrap().resources, 321);
+}
+
+#[test]
+fn sectors_regrow_while_the_player_is_away() {
+    let mut runner = quick_regrowth();
+
+    jump_to(&mut runner, (1, 0));
+    seconds(&mut runner, 3.5);
+    jump_to(&mut runner, (0, 0));
+
+    // Three and a half seconds away at one astroid a second
+    assert_eq!(astroids(&runner).len(), 3);
+}
+
+#[test]
+fn meteors_leave_room_for_regrowth() {
+    let mut runner = quick_regrowth();
+    for index in 0..5 {
+        let meteor = runner.spawn_astroid(Astroid::new(Ore::Iron), index as f32 * 60.0, 0.0, 90 + index);
+        runner.world.write_storage::<Meteor>().insert(meteor, Meteor).unwrap();
+    }
+
+    seconds(&mut runner, 2.5);
+
+    assert_eq!(astroids(&runner).len(), 7);
+}
+
+#[test]
+fn saved_games_keep_every_sector_and_its_regrowth() {
+    use amethyst::config::Config;
+
+    let mut original = quick_regrowth();
+    seconds(&mut original, 2.5);
+    jump_to(&mut original, (1, 0));
+    let policy = original.world.read_resource::<SectorRegen>().policy.clone();
+
+    // Written out and read back the way the pause menu and Load Game do it
+    let path = std::env::temp_dir().join("solar_lords_regen_save.ron");
+    SaveGame::capture(&original.world).write(&path).unwrap();
+    let save = SaveGame::load(&path).expect("the save should read back");
+
+    let mut loaded = runner();
+    loaded.world.insert(quick_config());
+    loaded.load_game(save);
+    assert_eq!(loaded.world.read_resource::<Galaxy>().sector, (1, 0));
+    assert_eq!(loaded.world.read_resource::<SectorRegen>().policy, policy);
+
+    // The two astroids grown before the save are still back home
+    jump_to(&mut loaded, (0, 0));
+    assert_eq!(astroids(&loaded).len(), 2);
+}
+
+#[test]
+fn shipped_regen_matches_the_defaults() {
+    assert_shipped_config::<RegenConfig>("regen.ron");
+}
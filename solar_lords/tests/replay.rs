@@ -0,0 +1,173 @@
+mod common;
+
+use amethyst::core::{transform::Transform, Named};
+use amethyst::ecs::prelude::Join;
+use amethyst::prelude::*;
+
+use common::{mining, moving, runner};
+use solar_lords::headless::{HeadlessRunner, TICK_SECONDS};
+use solar_lords::input::{Aim, PlayerInput};
+use solar_lords::replay::{InputTape, Recording};
+use solar_lords::solar_lords::{Astroid, Cargo, Ore};
+
+//Every named entity's position, sorted so two runs can be compared
+fn snapshot(runner: &HeadlessRunner) -> Vec<(String, f32, f32)> {
+    let names = runner.world.read_storage::<Named>();
+    let transforms = runner.world.read_storage::<Transform>();
+    let mut entities = (&names, &transforms)
+        .join()
+        .map(|(name, transform)| {
+            (
+                name.name.to_string(),
+                transform.translation().x,
+                transform.translation().y,
+            )
+        })
+        .collect::<Vec<_>>();
+    entities.sort_by(|a, b| a.0.cmp(&b.0));
+    entities
+}
+
+//Where "astroid 0" starts out, for the script to aim at
+fn first_astroid(runner: &HeadlessRunner) -> (f32, f32) {
+    let names = runner.world.read_storage::<Named>();
+    let transforms = runner.world.read_storage::<Transform>();
+    (&names, &transforms)
+        .join()
+        .find(|(name, _)| name.name == "astroid 0")
+        .map(|(_, transform)| (transform.translation().x, transform.translation().y))
+        .unwrap()
+}
+
+fn script(tick: u64, aim: (f32, f32)) -> PlayerInput {
+    match tick {
+        0 => PlayerInput {
+            aim: Some(Aim::Point(aim.0, aim.1)),
+            ..Default::default()
+        },
+        1..=30 => mining(),
+        31..=60 => moving(1.0, 0.5),
+        _ => moving(-0.3, 0.0),
+    }
+}
+
+//An uneven frame rate, as a busy window would have
+fn frame_length(tick: u64) -> f32 {
+    match tick % 3 {
+        0 => 1.0 / 30.0,
+        1 => 1.0 / 90.0,
+        _ => 1.0 / 45.0,
+    }
+}
+
+#[test]
+fn repeated_inputs_are_stored_once() {
+    let mut recording = Recording::new(7);
+    for tick in 0..90 {
+        recording.push(&script(tick, (0.0, 0.0)), TICK_SECONDS);
+    }
+    assert_eq!(recording.frames.len(), 4);
+    assert_eq!(recording.ticks(), 90);
+    let replayed = recording.inputs().cloned().collect::<Vec<_>>();
+    let expected = (0..90).map(|tick| script(tick, (0.0, 0.0))).collect::<Vec<_>>();
+    assert_eq!(replayed, expected);
+}
+
+#[test]
+fn tape_plays_back_what_it_recorded() {
+    let mut tape = InputTape::record_to("unused.ron".into());
+    tape.begin(3);
+    for tick in 0..40 {
+        tape.record(&script(tick, (0.0, 0.0)), frame_length(tick));
+    }
+    let recording = match tape {
+        InputTape::Recording { recording, .. } => recording,
+        _ => unreachable!(),
+    };
+
+    let mut replay = InputTape::replay(recording);
+    assert_eq!(replay.replay_seed(), Some(3));
+    for tick in 0..40 {
+        assert_eq!(replay.next_input(), Some((script(tick, (0.0, 0.0)), frame_length(tick))));
+    }
+    assert_eq!(replay.next_input(), None);
+}
+
+#[test]
+fn same_seed_generates_the_same_sector() {
+    let mut first = runner();
+    first.spawn_sector(42);
+    let mut second = runner();
+    second.spawn_sector(42);
+    assert_eq!(snapshot(&first), snapshot(&second));
+
+    let mut other = runner();
+    other.spawn_sector(43);
+    assert_ne!(snapshot(&first), snapshot(&other));
+}
+
+#[test]
+fn replay_reproduces_the_recorded_run() {
+    let mut recording = Recording::new(1234);
+    let mut original = runner();
+    let player = original.spawn_sector(recording.seed);
+    let aim = first_astroid(&original);
+    original.run(90, |tick, _| {
+        let input = script(tick, aim);
+        recording.push(&input, TICK_SECONDS);
+        input
+    });
+
+    let mut replayed = runner();
+    let replayed_player = replayed.replay(&recording);
+
+    assert_eq!(replayed.ticks, original.ticks);
+    assert_eq!(snapshot(&replayed), snapshot(&original));
+    assert_eq!(
+        replayed.world.read_storage::<Cargo>().get(replayed_player).unwrap().amount(Ore::Ice),
+        original.world.read_storage::<Cargo>().get(player).unwrap().amount(Ore::Ice),
+    );
+    let resources = |runner: &HeadlessRunner| {
+        (&runner.world.read_storage::<Astroid>())
+            .join()
+            .map(|astroid| astroid.resources)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(resources(&replayed), resources(&original));
+}
+
+#[test]
+fn replay_steps_each_tick_as_long_as_it_was_recorded() {
+    let mut recording = Recording::new(99);
+    let mut original = runner();
+    original.spawn_sector(recording.seed);
+    let aim = first_astroid(&original);
+    for tick in 0..90 {
+        let input = script(tick, aim);
+        recording.push(&input, frame_length(tick));
+        original.step_for(input, frame_length(tick));
+    }
+
+    let mut replayed = runner();
+    replayed.replay(&recording);
+    assert_eq!(snapshot(&replayed), snapshot(&original));
+
+    // Stepping the same inputs at the headless tick ends up somewhere else
+    let mut fixed = runner();
+    fixed.spawn_sector(recording.seed);
+    for input in recording.inputs() {
+        fixed.step(input.clone());
+    }
+    assert_ne!(snapshot(&fixed), snapshot(&original));
+}
+
+#[test]
+fn recordings_without_deltas_replay_at_the_headless_tick() {
+    let mut recording = Recording::new(5);
+    recording.push(&mining(), 1.0 / 30.0);
+    recording.deltas.clear();
+
+    assert_eq!(recording.delta(0), TICK_SECONDS);
+    let mut tape = InputTape::replay(recording);
+    assert_eq!(tape.next_input(), Some((mining(), TICK_SECONDS)));
+}
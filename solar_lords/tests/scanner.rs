@@ -0,0 +1,124 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::{Entity, Join};
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, runner, set_target};
+use solar_lords::config::ScannerConfig;
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::input::PlayerInput;
+use solar_lords::solar_lords::{Astroid, Capacitor, Ore, ScanPulse};
+
+fn scan() -> PlayerInput {
+    PlayerInput {
+        scan: true,
+        ..Default::default()
+    }
+}
+
+//Spawns an astroid the given distance to the right of the player
+fn astroid_at(runner: &mut HeadlessRunner, player: Entity, distance: f32, i: u32) -> Entity {
+    let position = *runner.world.read_storage::<Transform>().get(player).unwrap().translation();
+    runner.spawn_astroid(Astroid::new(Ore::Iron), position.x + distance, position.y, i)
+}
+
+fn scanned(runner: &HeadlessRunner, astroid: Entity) -> (bool, bool) {
+    let astroids = runner.world.read_storage::<Astroid>();
+    let astroid = astroids.get(astroid).unwrap();
+    (astroid.scanned, astroid.detailed)
+}
+
+fn charge(runner: &HeadlessRunner, player: Entity) -> f32 {
+    runner.world.read_storage::<Capacitor>().get(player).unwrap().charge
+}
+
+fn pulse_count(runner: &HeadlessRunner) -> usize {
+    (&runner.world.read_storage::<ScanPulse>()).join().count()
+}
+
+#[test]
+fn pulse_reveals_astroids_as_it_passes_them() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let near = astroid_at(&mut runner, player, 100.0, 0);
+    let far = astroid_at(&mut runner, player, 1100.0, 1);
+    let beyond = astroid_at(&mut runner, player, 1500.0, 2);
+
+    runner.run(1, |_, _| scan());
+    runner.run(15, |_, _| PlayerInput::default());
+    assert_eq!(scanned(&runner, near), (true, false));
+    assert_eq!(scanned(&runner, far), (false, false));
+
+    runner.run(120, |_, _| PlayerInput::default());
+    assert_eq!(scanned(&runner, far), (true, false));
+    assert_eq!(scanned(&runner, beyond), (false, false));
+    assert_eq!(pulse_count(&runner), 0);
+}
+
+#[test]
+fn scanning_costs_energy_and_has_a_cooldown() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+
+    runner.run(1, |_, _| scan());
+    assert!(charge(&runner, player) < 75.0);
+    runner.run(1, |_, _| PlayerInput::default());
+    assert_eq!(pulse_count(&runner), 1);
+
+    // Holding scan does nothing until the cooldown is over
+    runner.run(60, |_, _| scan());
+    assert_eq!(pulse_count(&runner), 1);
+    assert!(charge(&runner, player) > 75.0);
+
+    // Ready again five seconds after the first pulse
+    runner.run(245, |_, _| scan());
+    assert_eq!(pulse_count(&runner), 1);
+    assert!(charge(&runner, player) < 75.0);
+}
+
+#[test]
+fn no_pulse_without_enough_energy() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.world.write_storage::<Capacitor>().get_mut(player).unwrap().charge = 10.0;
+    let near = astroid_at(&mut runner, player, 100.0, 0);
+
+    runner.run(30, |_, _| scan());
+
+    assert_eq!(scanned(&runner, near), (false, false));
+    assert!(charge(&runner, player) > 10.0);
+}
+
+#[test]
+fn nearby_target_gets_a_detailed_scan() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let target = astroid_at(&mut runner, player, 300.0, 0);
+    let other = astroid_at(&mut runner, player, -300.0, 1);
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(1, |_, _| scan());
+    runner.run(60, |_, _| PlayerInput::default());
+
+    assert_eq!(scanned(&runner, target), (true, true));
+    assert_eq!(scanned(&runner, other), (true, false));
+}
+
+#[test]
+fn distant_target_only_gets_the_pulse() {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    let target = astroid_at(&mut runner, player, 800.0, 0);
+    set_target(&mut runner, player, Some("astroid 0"));
+
+    runner.run(1, |_, _| scan());
+    runner.run(120, |_, _| PlayerInput::default());
+
+    assert_eq!(scanned(&runner, target), (true, false));
+}
+
+#[test]
+fn shipped_scanner_matches_the_defaults() {
+    assert_shipped_config::<ScannerConfig>("scanner.ron");
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use amethyst::core::transform::Transform;
+use amethyst::ecs::prelude::Entity;
+use amethyst::prelude::*;
+
+use common::{assert_shipped_config, runner};
+use solar_lords::config::{ShipClass, ShipyardConfig};
+use solar_lords::headless::HeadlessRunner;
+use solar_lords::solar_lords::{buy_ship, Capacitor, Cargo, Collider, Fuel, Ore, Player, Ship};
+
+fn class(name: &str) -> ShipClass {
+    ShipyardConfig::default().class(name).unwrap().clone()
+}
+
+fn with_wealth(wealth: f32) -> (HeadlessRunner<'static, 'static>, Entity) {
+    let mut runner = runner();
+    let player = runner.spawn_player();
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().wealth = wealth;
+    (runner, player)
+}
+
+fn flying(runner: &HeadlessRunner, player: Entity) -> String {
+    runner.world.read_storage::<Ship>().get(player).unwrap().class.name.clone()
+}
+
+#[test]
+fn the_starting_ship_takes_its_stats_from_the_class() {
+    let (runner, player) = with_wealth(0.0);
+    let miner = class("Miner");
+
+    assert_eq!(flying(&runner, player), "Miner");
+    let players = runner.world.read_storage::<Player>();
+    let player_stats = players.get(player).unwrap();
+    assert_eq!(player_stats.max_hull, miner.hull);
+    assert_eq!(player_stats.max_sheild, miner.shield);
+    assert_eq!(player_stats.speed, miner.speed);
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().capacity, miner.cargo);
+}
+
+#[test]
+fn buying_a_hull_changes_the_ship() {
+    let (mut runner, player) = with_wealth(5000.0);
+    let hauler = class("Hauler");
+
+    assert!(buy_ship(&mut runner.world, player, &hauler).is_ok());
+
+    assert_eq!(flying(&runner, player), "Hauler");
+    let players = runner.world.read_storage::<Player>();
+    let player_stats = players.get(player).unwrap();
+    assert_eq!(player_stats.wealth, 5000.0 - hauler.price);
+    assert_eq!(player_stats.hull, hauler.hull);
+    assert_eq!(player_stats.speed, hauler.speed);
+    assert!(player_stats.hangar.contains(&hauler.name));
+    assert_eq!(runner.world.read_storage::<Cargo>().get(player).unwrap().capacity, hauler.cargo);
+    assert_eq!(runner.world.read_storage::<Capacitor>().get(player).unwrap().capacity, hauler.capacitor);
+    assert_eq!(runner.world.read_storage::<Fuel>().get(player).unwrap().capacity, hauler.fuel);
+    assert_eq!(runner.world.read_storage::<Collider>().get(player).unwrap().mass, hauler.mass);
+    assert_eq!(runner.world.read_storage::<Transform>().get(player).unwrap().scale().x, hauler.scale);
+}
+
+#[test]
+fn a_new_hull_takes_on_the_damage() {
+    let (mut runner, player) = with_wealth(5000.0);
+    runner.world.write_storage::<Player>().get_mut(player).unwrap().hull = 50;
+
+    buy_ship(&mut runner.world, player, &class("Hauler")).unwrap();
+    buy_ship(&mut runner.world, player, &class("Miner")).unwrap();
+
+    // Half a hauler's hull and back to half the miner's, no repairs on the way
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().hull, 50);
+}
+
+#[test]
+fn every_hull_looks_different() {
+    let ships = ShipyardConfig::default().ships;
+    for (index, ship) in ships.iter().enumerate() {
+        assert!(ships[index + 1..].iter().all(|other| other.sprite != ship.sprite), "{} shares a sprite", ship.name);
+    }
+}
+
+#[test]
+fn hulls_cost_wealth() {
+    let (mut runner, player) = with_wealth(1000.0);
+
+    assert!(buy_ship(&mut runner.world, player, &class("Corvette")).is_err());
+
+    assert_eq!(flying(&runner, player), "Miner");
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 1000.0);
+}
+
+#[test]
+fn switching_back_to_an_owned_hull_is_free() {
+    let (mut runner, player) = with_wealth(3000.0);
+    buy_ship(&mut runner.world, player, &class("Explorer")).unwrap();
+
+    assert!(buy_ship(&mut runner.world, player, &class("Miner")).is_ok());
+
+    assert_eq!(flying(&runner, player), "Miner");
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 0.0);
+}
+
+#[test]
+fn cargo_has_to_fit_in_the_new_hold() {
+    let (mut runner, player) = with_wealth(3000.0);
+    runner.world.write_storage::<Cargo>().get_mut(player).unwrap().add(Ore::Iron, 400);
+
+    assert!(buy_ship(&mut runner.world, player, &class("Explorer")).is_err());
+
+    assert_eq!(flying(&runner, player), "Miner");
+    assert_eq!(runner.world.read_storage::<Player>().get(player).unwrap().wealth, 3000.0);
+}
+
+#[test]
+fn shipped_hulls_match_the_defaults() {
+    assert_shipped_config::<ShipyardConfig>("ships.ron");
+}